@@ -6,11 +6,46 @@ fn render_table_grid(size: usize) -> String {
     table()
         .child(tbody().children(1..=size, |row| {
             tr().map_when(row % 2 == 0, |n| n.class("even"))
-                .children(1..=size, |col| td().text(format!("{}x{}", row, col)))
+                .children(1..=size, |col| {
+                    td().text_fmt(format_args!("{}x{}", row, col))
+                })
         }))
         .into()
 }
 
+#[cfg(feature = "rayon")]
+fn render_table_grid_par(size: usize) -> String {
+    table()
+        .child(tbody().children_par(1..=size, |row| {
+            tr().map_when(row % 2 == 0, |n| n.class("even"))
+                .children(1..=size, |col| {
+                    td().text_fmt(format_args!("{}x{}", row, col))
+                })
+        }))
+        .render()
+}
+
+fn render_table_grid_in_place(size: usize) -> String {
+    table()
+        .child_with(|w| {
+            w.tbody(|tbody| {
+                tbody.child_with(|w| {
+                    for row in 1..=size {
+                        w.tr(|tr| {
+                            tr.map_when(row % 2 == 0, |n| n.class("even"))
+                                .child_with(|w| {
+                                    for col in 1..=size {
+                                        w.td(|td| td.text_fmt(format_args!("{}x{}", row, col)));
+                                    }
+                                })
+                        });
+                    }
+                })
+            });
+        })
+        .into()
+}
+
 fn benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("Lira Benchmarks");
 
@@ -24,6 +59,17 @@ fn benchmark(c: &mut Criterion) {
     group.bench_function("render table grid: 100x100", |b| {
         b.iter(|| render_table_grid(100))
     });
+    group.bench_function("render table grid with buffer pool: 100x100", |b| {
+        let mut pool = BufferPool::new();
+        b.iter(|| pool.scope(|| render_table_grid(100)))
+    });
+    #[cfg(feature = "rayon")]
+    group.bench_function("render table grid in parallel: 100x100", |b| {
+        b.iter(|| render_table_grid_par(100))
+    });
+    group.bench_function("render table grid in place: 100x100", |b| {
+        b.iter(|| render_table_grid_in_place(100))
+    });
     group.finish();
 }
 
@@ -0,0 +1,205 @@
+use proc_macro2::{Delimiter, Literal, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitStr, Token, bracketed};
+
+/// Arguments of `#[check_classes("app.css", allow = ["js-*"])]`.
+struct Args {
+    stylesheets: Vec<LitStr>,
+    allow: Vec<String>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args {
+            stylesheets: Vec::new(),
+            allow: Vec::new(),
+        };
+
+        while !input.is_empty() {
+            if input.peek(LitStr) {
+                args.stylesheets.push(input.parse()?);
+            } else {
+                let ident: Ident = input.parse()?;
+                if ident != "allow" {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "unknown check_classes argument",
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                let content;
+                bracketed!(content in input);
+                let allowed = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                args.allow.extend(allowed.iter().map(LitStr::value));
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+pub(crate) fn expand(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(args)?;
+    if args.stylesheets.is_empty() && args.allow.is_empty() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "check_classes needs a stylesheet or an allow list",
+        ));
+    }
+
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let mut known = Vec::new();
+    let mut tracked = Vec::new();
+    for stylesheet in &args.stylesheets {
+        let path = std::path::Path::new(&root).join(stylesheet.value());
+        let css = std::fs::read_to_string(&path).map_err(|err| {
+            syn::Error::new(
+                stylesheet.span(),
+                format!("can't read {}: {}", path.display(), err),
+            )
+        })?;
+        known.extend(class_names(&css));
+
+        // recompiles the item when the stylesheet changes
+        let path = path.to_string_lossy().into_owned();
+        tracked.push(quote!(
+            const _: &[u8] = ::std::include_bytes!(#path);
+        ));
+    }
+
+    let mut errors = Vec::new();
+    for (literal, classes) in class_literals(item.clone()) {
+        for class in classes.split_ascii_whitespace() {
+            let allowed = args
+                .allow
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => class.starts_with(prefix),
+                    None => class == pattern,
+                });
+            if !allowed && !known.iter().any(|known| known == class) {
+                errors.push(syn::Error::new(
+                    literal.span(),
+                    format!("unknown class `{}`", class),
+                ));
+            }
+        }
+    }
+
+    let errors = errors.into_iter().map(syn::Error::into_compile_error);
+    Ok(quote! {
+        #(#tracked)*
+        #(#errors)*
+        #item
+    })
+}
+
+// string literals passed to `.class(...)`
+fn class_literals(tokens: TokenStream) -> Vec<(Literal, String)> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut found = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let TokenTree::Group(group) = token else {
+            continue;
+        };
+        found.extend(class_literals(group.stream()));
+
+        // `.class(...)` with a single string literal
+        if group.delimiter() != Delimiter::Parenthesis || i < 2 {
+            continue;
+        }
+        let is_class_call = matches!(
+            (&tokens[i - 2], &tokens[i - 1]),
+            (TokenTree::Punct(dot), TokenTree::Ident(name)) if dot.as_char() == '.' && name == "class"
+        );
+        let args: Vec<TokenTree> = group.stream().into_iter().collect();
+        if let (true, [TokenTree::Literal(literal)]) = (is_class_call, args.as_slice())
+            && let Ok(lit) = syn::parse2::<LitStr>(TokenTree::Literal(literal.clone()).into())
+        {
+            found.push((literal.clone(), lit.value()));
+        }
+    }
+
+    found
+}
+
+/// Class names used in the selectors of a stylesheet, with CSS escapes like `md\:p-4` resolved.
+fn class_names(css: &str) -> Vec<String> {
+    let css = strip_comments(css);
+    let mut names = Vec::new();
+
+    // selectors are whatever comes before a `{`, declarations end at `;` or `}`
+    let mut prelude = String::new();
+    for c in css.chars() {
+        match c {
+            '{' => {
+                if !prelude.trim_start().starts_with('@') {
+                    collect_classes(&prelude, &mut names);
+                }
+                prelude.clear();
+            }
+            ';' | '}' => prelude.clear(),
+            c => prelude.push(c),
+        }
+    }
+
+    names
+}
+
+fn collect_classes(selector: &str, names: &mut Vec<String>) {
+    let mut chars = selector.chars().peekable();
+    let mut in_brackets = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            // attribute selectors can contain dots, `[href$=".pdf"]`
+            '[' => in_brackets = true,
+            ']' => in_brackets = false,
+            '.' if !in_brackets => {
+                let mut name = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next == '\\' {
+                        chars.next();
+                        if let Some(escaped) = chars.next() {
+                            name.push(escaped);
+                        }
+                    } else if next.is_alphanumeric()
+                        || next == '-'
+                        || next == '_'
+                        || !next.is_ascii()
+                    {
+                        name.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if !name.is_empty() && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,85 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+use crate::attrs;
+
+/// Options of a `#[dl(...)]` attribute.
+#[derive(Default)]
+struct DlAttrs {
+    rename: Option<String>,
+    with: Option<syn::Path>,
+    skip: bool,
+}
+
+impl DlAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut dl = DlAttrs::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("dl")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    dl.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("with") {
+                    dl.with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    dl.skip = true;
+                } else {
+                    return Err(meta.error("unknown dl attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(dl)
+    }
+}
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields,
+            _ => return Err(unsupported(input)),
+        },
+        _ => return Err(unsupported(input)),
+    };
+
+    let mut entries = Vec::new();
+
+    for field in &fields.named {
+        let dl = DlAttrs::parse(&field.attrs)?;
+        if dl.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let term = dl
+            .rename
+            .unwrap_or_else(|| attrs::humanize(&ident.to_string()));
+
+        let ty = &field.ty;
+        let description = match dl.with {
+            Some(with) => quote!(#with(&self.#ident)),
+            None => quote!(<#ty as ::lira::lists::Description>::description(&self.#ident)),
+        };
+        entries.push(quote!(.child(::lira::html5::dt().text(#term)).child(#description)));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::lira::lists::ToDescriptionList for #ident #ty_generics #where_clause {
+            fn entries(&self) -> ::lira::core::Fragment {
+                ::lira::core::fragment() #(#entries)*
+            }
+        }
+    })
+}
+
+fn unsupported(input: &DeriveInput) -> syn::Error {
+    syn::Error::new_spanned(
+        &input.ident,
+        "ToDescriptionList can only be derived for structs with named fields",
+    )
+}
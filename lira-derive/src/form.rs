@@ -0,0 +1,239 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+use crate::attrs::{self, Serde};
+
+/// Options of a `#[form(...)]` attribute.
+#[derive(Default)]
+struct FormAttrs {
+    rename: Option<String>,
+    label: Option<String>,
+    input_type: Option<String>,
+    placeholder: Option<String>,
+    required: bool,
+    textarea: bool,
+    skip: bool,
+}
+
+impl FormAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut form = FormAttrs::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("form")) {
+            attr.parse_nested_meta(|meta| {
+                let string = |meta: &syn::meta::ParseNestedMeta| -> syn::Result<String> {
+                    Ok(meta.value()?.parse::<LitStr>()?.value())
+                };
+
+                if meta.path.is_ident("rename") {
+                    form.rename = Some(string(&meta)?);
+                } else if meta.path.is_ident("label") {
+                    form.label = Some(string(&meta)?);
+                } else if meta.path.is_ident("type") {
+                    form.input_type = Some(string(&meta)?);
+                } else if meta.path.is_ident("placeholder") {
+                    form.placeholder = Some(string(&meta)?);
+                } else if meta.path.is_ident("required") {
+                    form.required = true;
+                } else if meta.path.is_ident("textarea") {
+                    form.textarea = true;
+                } else if meta.path.is_ident("skip") {
+                    form.skip = true;
+                } else {
+                    return Err(meta.error("unknown form attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(form)
+    }
+}
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => expand_struct(input, fields),
+            _ => Err(unsupported(input)),
+        },
+        Data::Enum(data) => expand_enum(input, data),
+        Data::Union(_) => Err(unsupported(input)),
+    }
+}
+
+fn unsupported(input: &DeriveInput) -> syn::Error {
+    syn::Error::new_spanned(
+        &input.ident,
+        "Form can only be derived for structs with named fields and enums with unit variants",
+    )
+}
+
+fn option(value: &Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(#value)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+fn expand_struct(input: &DeriveInput, fields: &syn::FieldsNamed) -> syn::Result<TokenStream> {
+    let container = Serde::parse(&input.attrs)?;
+
+    let mut filled = Vec::new();
+    let mut empty = Vec::new();
+    let mut bounds = Vec::new();
+
+    for field in &fields.named {
+        let form = FormAttrs::parse(&field.attrs)?;
+        if form.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let ident_name = ident.to_string();
+        let ident_name = ident_name.trim_start_matches("r#");
+
+        let name = match (form.rename, Serde::parse(&field.attrs)?.rename) {
+            (Some(name), _) | (None, Some(name)) => name,
+            (None, None) => match container.rename_all {
+                Some(rule) => rule.apply_to_field(ident_name),
+                None => ident_name.to_string(),
+            },
+        };
+        let label = form.label.unwrap_or_else(|| attrs::humanize(ident_name));
+        let input_type = option(&form.input_type);
+        let placeholder = option(&form.placeholder);
+        let required = form.required;
+        let textarea = form.textarea;
+        let ty = &field.ty;
+
+        let spec = quote! {
+            &::lira::forms::Field {
+                name: #name,
+                label: #label,
+                input_type: #input_type,
+                placeholder: #placeholder,
+                required: #required,
+                textarea: #textarea,
+            }
+        };
+
+        filled.push(quote! {
+            .child(<#ty as ::lira::forms::FormField>::render_field(
+                #spec,
+                ::core::option::Option::Some(&self.#ident),
+            ))
+        });
+        empty.push(quote! {
+            .child(<#ty as ::lira::forms::FormField>::render_field(#spec, ::core::option::Option::None))
+        });
+        bounds.push(quote!(#ty: ::lira::forms::FormField));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut predicates: Vec<_> = where_clause
+        .map(|clause| clause.predicates.iter().map(|p| quote!(#p)).collect())
+        .unwrap_or_default();
+    if !input.generics.params.is_empty() {
+        predicates.extend(bounds);
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::lira::forms::Form for #ident #ty_generics where #(#predicates),* {
+            fn fields(&self) -> ::lira::core::Fragment {
+                ::lira::core::fragment() #(#filled)*
+            }
+
+            fn empty_fields() -> ::lira::core::Fragment {
+                ::lira::core::fragment() #(#empty)*
+            }
+        }
+    })
+}
+
+/// A unit variant of an enum rendered as an `<option>`.
+pub(crate) struct SelectVariant {
+    pub(crate) ident: syn::Ident,
+    /// The submitted value, following `rename` and the serde attributes.
+    pub(crate) value: String,
+    pub(crate) label: String,
+    /// Accepted as a value but not offered as an option.
+    pub(crate) skip: bool,
+}
+
+/// Reads the variants of an enum with `#[form(rename, label, skip)]` options, shared with `SelectOptions`.
+pub(crate) fn select_variants(
+    derive: &str,
+    input: &DeriveInput,
+    data: &syn::DataEnum,
+) -> syn::Result<Vec<SelectVariant>> {
+    let container = Serde::parse(&input.attrs)?;
+
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!(
+                    "{} can only be derived for enums with unit variants",
+                    derive
+                ),
+            ));
+        }
+
+        let form = FormAttrs::parse(&variant.attrs)?;
+        let ident_name = variant.ident.to_string();
+
+        let value = match (form.rename, Serde::parse(&variant.attrs)?.rename) {
+            (Some(name), _) | (None, Some(name)) => name,
+            (None, None) => match container.rename_all {
+                Some(rule) => rule.apply_to_variant(&ident_name),
+                None => ident_name.clone(),
+            },
+        };
+        let label = form.label.unwrap_or_else(|| attrs::humanize(&ident_name));
+
+        variants.push(SelectVariant {
+            ident: variant.ident.clone(),
+            value,
+            label,
+            skip: form.skip,
+        });
+    }
+
+    Ok(variants)
+}
+
+fn expand_enum(input: &DeriveInput, data: &syn::DataEnum) -> syn::Result<TokenStream> {
+    let variants = select_variants("Form", input, data)?;
+
+    let arms = variants.iter().map(|variant| {
+        let (ident, value) = (&variant.ident, &variant.value);
+        quote!(Self::#ident => #value)
+    });
+    let options = variants
+        .iter()
+        .filter(|variant| !variant.skip)
+        .map(|variant| {
+            let (value, label) = (&variant.value, &variant.label);
+            quote!((#value, #label))
+        });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::lira::forms::FormField for #ident #ty_generics #where_clause {
+            fn render_field(
+                field: &::lira::forms::Field<'_>,
+                value: ::core::option::Option<&Self>,
+            ) -> ::lira::core::Fragment {
+                let selected = value.map(|value| match *value {
+                    #(#arms),*
+                });
+                ::lira::forms::select_field(field, &[#(#options),*], selected)
+            }
+        }
+    })
+}
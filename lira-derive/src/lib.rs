@@ -0,0 +1,65 @@
+//! Derive macros for lira, use them through the `derive` feature of the `lira` crate.
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+mod attrs;
+mod classes;
+mod description;
+mod form;
+mod names;
+mod select;
+mod table;
+
+/// Renders a struct as labeled form controls, see `lira::forms::Form`.
+#[proc_macro_derive(Form, attributes(form))]
+pub fn derive_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    form::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Adds a constant with the serde name of every field, see `lira::forms::FieldNames`.
+#[proc_macro_derive(FieldNames)]
+pub fn derive_field_names(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    names::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Lists the unit variants of an enum as `<option>`s, see `lira::forms::SelectOptions`.
+#[proc_macro_derive(SelectOptions, attributes(form))]
+pub fn derive_select_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    select::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Renders a struct as a table row, see `lira::table::ToTableRow`.
+#[proc_macro_derive(ToTableRow, attributes(table))]
+pub fn derive_to_table_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    table::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Renders a struct as the entries of a description list, see `lira::lists::ToDescriptionList`.
+#[proc_macro_derive(ToDescriptionList, attributes(dl))]
+pub fn derive_to_description_list(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    description::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Checks the class names passed to `.class("...")` against a stylesheet, see `lira::css::check_classes`.
+#[proc_macro_attribute]
+pub fn check_classes(args: TokenStream, item: TokenStream) -> TokenStream {
+    classes::expand(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
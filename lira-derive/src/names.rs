@@ -0,0 +1,56 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields};
+
+use crate::attrs::Serde;
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields,
+            _ => return Err(unsupported(input)),
+        },
+        _ => return Err(unsupported(input)),
+    };
+
+    let container = Serde::parse(&input.attrs)?;
+    let mut consts = Vec::new();
+
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        let ident_name = ident.to_string();
+        let ident_name = ident_name.trim_start_matches("r#");
+
+        // the name serde deserializes the field from
+        let name = match Serde::parse(&field.attrs)?.rename {
+            Some(name) => name,
+            None => match container.rename_all {
+                Some(rule) => rule.apply_to_field(ident_name),
+                None => ident_name.to_string(),
+            },
+        };
+
+        let doc = format!("Form field name of `{}`.", ident_name);
+        let const_ident = format_ident!("{}", ident_name.to_ascii_uppercase());
+        consts.push(quote! {
+            #[doc = #doc]
+            pub const #const_ident: &'static str = #name;
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#consts)*
+        }
+    })
+}
+
+fn unsupported(input: &DeriveInput) -> syn::Error {
+    syn::Error::new_spanned(
+        &input.ident,
+        "FieldNames can only be derived for structs with named fields",
+    )
+}
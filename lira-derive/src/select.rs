@@ -0,0 +1,42 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput};
+
+use crate::form;
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "SelectOptions can only be derived for enums with unit variants",
+        ));
+    };
+    let variants = form::select_variants("SelectOptions", input, data)?;
+
+    let arms = variants.iter().map(|variant| {
+        let (ident, value) = (&variant.ident, &variant.value);
+        quote!(Self::#ident => #value)
+    });
+    let options = variants
+        .iter()
+        .filter(|variant| !variant.skip)
+        .map(|variant| {
+            let (value, label) = (&variant.value, &variant.label);
+            quote!((#value, #label))
+        });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::lira::forms::SelectOptions for #ident #ty_generics #where_clause {
+            const OPTIONS: &'static [(&'static str, &'static str)] = &[#(#options),*];
+
+            fn value(&self) -> &'static str {
+                match *self {
+                    #(#arms),*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,90 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+use crate::attrs;
+
+/// Options of a `#[table(...)]` attribute.
+#[derive(Default)]
+struct TableAttrs {
+    label: Option<String>,
+    with: Option<syn::Path>,
+    skip: bool,
+}
+
+impl TableAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut table = TableAttrs::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("table")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    table.label = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("with") {
+                    table.with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    table.skip = true;
+                } else {
+                    return Err(meta.error("unknown table attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(table)
+    }
+}
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields,
+            _ => return Err(unsupported(input)),
+        },
+        _ => return Err(unsupported(input)),
+    };
+
+    let mut columns = Vec::new();
+    let mut cells = Vec::new();
+
+    for field in &fields.named {
+        let table = TableAttrs::parse(&field.attrs)?;
+        if table.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let label = table
+            .label
+            .unwrap_or_else(|| attrs::humanize(&ident.to_string()));
+        columns.push(quote!(::std::borrow::Cow::Borrowed(#label)));
+
+        let ty = &field.ty;
+        cells.push(match table.with {
+            Some(with) => quote!(.child(#with(&self.#ident))),
+            None => quote!(.child(<#ty as ::lira::table::TableCell>::cell(&self.#ident))),
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::lira::table::ToTableRow for #ident #ty_generics #where_clause {
+            fn columns() -> ::std::vec::Vec<::std::borrow::Cow<'static, str>> {
+                ::std::vec![#(#columns),*]
+            }
+
+            fn cells(&self) -> ::lira::core::Fragment {
+                ::lira::core::fragment() #(#cells)*
+            }
+        }
+    })
+}
+
+fn unsupported(input: &DeriveInput) -> syn::Error {
+    syn::Error::new_spanned(
+        &input.ident,
+        "ToTableRow can only be derived for structs with named fields",
+    )
+}
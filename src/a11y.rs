@@ -0,0 +1,167 @@
+use crate::core::{Content, HasAttributes, Node, Open, Renderable};
+use crate::css::{Stylesheet, rule, stylesheet};
+use crate::html5::{
+    A, Button, Div, HasGlobalAttributes, HasHref, Main, Span, a, button, div, main, span,
+};
+
+/// Class of text that is only there for screen readers, see [`a11y_styles`].
+pub const VISUALLY_HIDDEN: &str = "visually-hidden";
+
+/// Class of elements that are visually hidden until they are focused, like the [`skip_link`].
+pub const VISUALLY_HIDDEN_FOCUSABLE: &str = "visually-hidden-focusable";
+
+/// Styles for [`VISUALLY_HIDDEN`] and [`VISUALLY_HIDDEN_FOCUSABLE`], include them once in the page, e.g.
+/// `style().child(a11y_styles())`.
+pub fn a11y_styles() -> Stylesheet {
+    let selector = format!(
+        ".{}, .{}:not(:focus):not(:focus-within)",
+        VISUALLY_HIDDEN, VISUALLY_HIDDEN_FOCUSABLE
+    );
+
+    stylesheet().rule(
+        rule(selector)
+            .position("absolute")
+            .width("1px")
+            .height("1px")
+            .padding("0")
+            .margin("-1px")
+            .overflow("hidden")
+            .prop("clip", "rect(0, 0, 0, 0)")
+            .prop("white-space", "nowrap")
+            .border("0"),
+    )
+}
+
+/// Text read by screen readers but not shown on screen.
+pub fn visually_hidden(text: impl AsRef<str>) -> Node<Span, Content> {
+    span().class(VISUALLY_HIDDEN).text(text)
+}
+
+impl Node<Button, Open> {
+    /// Adds `icon` hidden from assistive technology together with `accessible_name` as visually hidden text, so
+    /// the button is announced by its name instead of not at all.
+    pub fn icon_label(
+        self,
+        icon: impl Renderable,
+        accessible_name: impl AsRef<str>,
+    ) -> Node<Button, Content> {
+        self.child(span().attr("aria-hidden", "true").child(icon))
+            .child(visually_hidden(accessible_name))
+    }
+}
+
+/// The "Skip to main content" link, which should be the first focusable element of the page so keyboard users
+/// can jump past the navigation. Only visible while focused.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = body()
+///     .child(skip_link("content"))
+///     .child(nav().text("..."))
+///     .child(main_content("content").text("..."))
+///     .render();
+///
+/// assert_eq!(
+///     "<body><a href=\"#content\" class=\"visually-hidden-focusable\">Skip to main content</a><nav>...</nav>\
+///      <main id=\"content\" tabindex=\"-1\">...</main></body>",
+///     res
+/// );
+/// ```
+pub fn skip_link(target_id: impl AsRef<str>) -> Node<A, Content> {
+    skip_link_with(target_id, "Skip to main content")
+}
+
+/// Like [`skip_link`] with a custom link text, e.g. a translation.
+pub fn skip_link_with(target_id: impl AsRef<str>, text: impl AsRef<str>) -> Node<A, Content> {
+    a().href(format!("#{}", target_id.as_ref()))
+        .class(VISUALLY_HIDDEN_FOCUSABLE)
+        .text(text)
+}
+
+/// The `<main>` element targeted by a [`skip_link`].
+///
+/// `tabindex="-1"` lets it receive focus without adding it to the tab order, so following the link moves keyboard
+/// focus along in every browser instead of only scrolling.
+pub fn main_content(id: impl AsRef<str>) -> Node<Main, Open> {
+    main().id(id).tabindex(-1)
+}
+
+/// A button only showing an icon, named for assistive technology through visually hidden text.
+///
+/// Use [`icon_label`](Node::icon_label) to add attributes to the button.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<button type=\"button\"><span aria-hidden=\"true\">✕</span>\
+///      <span class=\"visually-hidden\">Close</span></button>",
+///     icon_button(fragment().text("✕"), "Close").render()
+/// );
+/// ```
+pub fn icon_button(
+    icon: impl Renderable,
+    accessible_name: impl AsRef<str>,
+) -> Node<Button, Content> {
+    button().type_("button").icon_label(icon, accessible_name)
+}
+
+/// How urgently screen readers announce changes of a [`live_region`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Politeness {
+    /// Changes are not announced.
+    Off,
+
+    /// Changes are announced once the user is idle.
+    Polite,
+
+    /// Changes interrupt whatever is being announced.
+    Assertive,
+}
+
+impl Politeness {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Politeness::Off => "off",
+            Politeness::Polite => "polite",
+            Politeness::Assertive => "assertive",
+        }
+    }
+}
+
+/// A region whose content changes are announced by screen readers, e.g. the target of an htmx swap.
+///
+/// The region has to be part of the page before its content changes, a region inserted together with its
+/// message is not announced in most screen readers. Changes are announced as a whole (`aria-atomic`) so the
+/// message stays understandable when only part of it was replaced.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<div aria-live=\"polite\" aria-atomic=\"true\" id=\"cart-count\">3 items</div>",
+///     live_region(Politeness::Polite).id("cart-count").text("3 items").render()
+/// );
+/// ```
+pub fn live_region(politeness: Politeness) -> Node<Div, Open> {
+    div()
+        .attr("aria-live", politeness.as_str())
+        .attr("aria-atomic", "true")
+}
+
+/// A politely announced live region for advisory messages like "Saved" or search result counts.
+pub fn status() -> Node<Div, Open> {
+    div()
+        .attr("role", "status")
+        .attr("aria-live", Politeness::Polite.as_str())
+        .attr("aria-atomic", "true")
+}
+
+/// An assertively announced live region for important, time-sensitive messages like errors.
+pub fn alert() -> Node<Div, Open> {
+    div()
+        .attr("role", "alert")
+        .attr("aria-live", Politeness::Assertive.as_str())
+        .attr("aria-atomic", "true")
+}
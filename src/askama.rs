@@ -0,0 +1,30 @@
+use ::askama::filters::HtmlSafe;
+
+use crate::core::{Content, Fragment, Node, Void};
+
+// finished nodes written into askama templates, `{{ sidebar }}`, are already escaped. Marking them as safe keeps
+// askama from escaping them a second time, without needing `|safe` at every use
+
+/// Lets askama templates embed finished nodes like any other value.
+///
+/// ```
+/// use askama::Template;
+/// use lira::core::{Content, Node};
+/// use lira::html5::Nav;
+/// use lira::prelude::*;
+///
+/// #[derive(Template)]
+/// #[template(source = "<body>{{ nav }}</body>", ext = "html")]
+/// struct Layout {
+///     nav: Node<Nav, Content>,
+/// }
+///
+/// let layout = Layout { nav: nav().child(a().href("/").text("Home")) };
+///
+/// assert_eq!("<body><nav><a href=\"/\">Home</a></nav></body>", layout.render().unwrap());
+/// ```
+impl<Tag> HtmlSafe for Node<Tag, Content> {}
+
+impl<Tag> HtmlSafe for Node<Tag, Void> {}
+
+impl HtmlSafe for Fragment {}
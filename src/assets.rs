@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::context;
+use crate::core::{Node, Open, Void};
+use crate::html5::{
+    CrossOrigin, HasCrossOrigin, HasHref, HasRel, HasSrc, Link, Rel, Script, link, script,
+};
+use crate::json::{self, Value};
+
+/// A single resolved asset from a bundler manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asset {
+    /// Hashed public path of the asset.
+    pub file: String,
+
+    /// Subresource Integrity value, if the manifest provides one.
+    pub integrity: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError {
+    message: String,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid asset manifest: {}", self.message)
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+/// Maps logical asset names like `app.js` to their cache-busted paths.
+///
+/// Register it on the [`RenderContext`](crate::context::RenderContext) to have [`asset_script`] and
+/// [`asset_css`] resolve through it.
+#[derive(Debug, Clone, Default)]
+pub struct Assets {
+    base: String,
+    entries: HashMap<String, Asset>,
+}
+
+impl Assets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a manifest as written by Vite (`{"app.js": {"file": "assets/app-4889e940.js"}}`) or by
+    /// webpack-manifest-plugin (`{"app.js": "/dist/app.4889e940.js"}`). An `integrity` key on Vite style entries
+    /// is picked up as well.
+    pub fn from_manifest(json: &str) -> Result<Self, ManifestError> {
+        let value = json::parse(json).map_err(|err| ManifestError {
+            message: format!("{} at offset {}", err.message, err.offset),
+        })?;
+
+        let Value::Object(items) = value else {
+            return Err(ManifestError {
+                message: "expected an object at the top level".to_string(),
+            });
+        };
+
+        let mut assets = Assets::new();
+
+        for (name, entry) in items {
+            let asset = match &entry {
+                Value::String(file) => Asset {
+                    file: file.clone(),
+                    integrity: None,
+                },
+                Value::Object(_) => Asset {
+                    file: entry
+                        .get("file")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| ManifestError {
+                            message: format!("entry \"{}\" has no \"file\"", name),
+                        })?
+                        .to_string(),
+                    integrity: entry
+                        .get("integrity")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                },
+                _ => {
+                    return Err(ManifestError {
+                        message: format!("entry \"{}\" is neither a string nor an object", name),
+                    });
+                }
+            };
+
+            assets.entries.insert(name, asset);
+        }
+
+        Ok(assets)
+    }
+
+    /// Prefix put in front of every resolved path, e.g. `/static/`.
+    pub fn base(mut self, value: impl AsRef<str>) -> Self {
+        self.base = value.as_ref().to_string();
+        self
+    }
+
+    /// Adds or replaces a single entry.
+    pub fn insert(mut self, name: impl AsRef<str>, asset: Asset) -> Self {
+        self.entries.insert(name.as_ref().to_string(), asset);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Asset> {
+        self.entries.get(name)
+    }
+
+    /// Public path of the asset, or the logical name itself if the manifest doesn't know it.
+    pub fn path(&self, name: &str) -> String {
+        let file = self.get(name).map_or(name, |asset| asset.file.as_str());
+
+        if self.base.is_empty() || file.contains("://") {
+            return file.to_string();
+        }
+
+        format!(
+            "{}/{}",
+            self.base.trim_end_matches('/'),
+            file.trim_start_matches('/')
+        )
+    }
+
+    pub fn integrity(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(|asset| asset.integrity.as_deref())
+    }
+}
+
+fn resolve(name: &str) -> (String, Option<String>) {
+    context::current_assets().map_or_else(
+        || (name.to_string(), None),
+        |assets| {
+            (
+                assets.path(name),
+                assets.integrity(name).map(str::to_string),
+            )
+        },
+    )
+}
+
+/// A `<script>` for the logical asset `name`, resolved through the assets of the active render context.
+pub fn asset_script(name: impl AsRef<str>) -> Node<Script, Open> {
+    let (src, integrity) = resolve(name.as_ref());
+    let node = script().src(src);
+
+    match integrity {
+        Some(integrity) => node
+            .integrity(integrity)
+            .crossorigin(CrossOrigin::Anonymous),
+        None => node,
+    }
+}
+
+/// A stylesheet `<link>` for the logical asset `name`, resolved through the assets of the active render context.
+pub fn asset_css(name: impl AsRef<str>) -> Node<Link, Void> {
+    let (href, integrity) = resolve(name.as_ref());
+    let node = link().rel(Rel::Stylesheet).href(href);
+
+    match integrity {
+        Some(integrity) => node
+            .integrity(integrity)
+            .crossorigin(CrossOrigin::Anonymous),
+        None => node,
+    }
+}
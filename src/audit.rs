@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::core::Renderable;
+use crate::tree::{self, Element, TreeNode};
+
+/// Accessibility problem found in rendered HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// `<img>` without an `alt` attribute, use `alt=""` for decorative images.
+    MissingAlt,
+    /// Form control that has no `<label>`, `aria-label`, `aria-labelledby` or `title`.
+    UnlabeledControl,
+    /// `<button>` without text or any other accessible name.
+    UnnamedButton,
+    /// Heading that skips levels, like an `<h4>` following an `<h2>`.
+    HeadingLevelJump { from: u8, to: u8 },
+    /// Another `<h1>` after the first one, a page should have a single top level heading.
+    MultipleH1,
+    /// `tabindex` greater than 0, which breaks the natural tab order.
+    PositiveTabindex(i32),
+}
+
+/// A single finding of the audit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub issue: Issue,
+    /// Name of the offending element.
+    pub element: String,
+    /// Byte offset of the element's start tag in the rendered HTML.
+    pub offset: usize,
+    /// Line of the start tag, starting at 1.
+    pub line: usize,
+    /// Column of the start tag in characters, starting at 1.
+    pub column: usize,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<{}> at {}:{} (byte {}): ",
+            self.element, self.line, self.column, self.offset
+        )?;
+        match &self.issue {
+            Issue::MissingAlt => write!(f, "image without alt attribute"),
+            Issue::UnlabeledControl => write!(f, "form control without label"),
+            Issue::UnnamedButton => write!(f, "button without accessible name"),
+            Issue::HeadingLevelJump { from, to } => {
+                write!(f, "heading level jumps from h{} to h{}", from, to)
+            }
+            Issue::MultipleH1 => write!(f, "more than one h1"),
+            Issue::PositiveTabindex(index) => write!(f, "positive tabindex {}", index),
+        }
+    }
+}
+
+/// All warnings collected while rendering, in document order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    warnings: Vec<Warning>,
+}
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Warning> {
+        self.warnings.iter()
+    }
+}
+
+impl IntoIterator for Report {
+    type Item = Warning;
+    type IntoIter = std::vec::IntoIter<Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.warnings.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Report {
+    type Item = &'a Warning;
+    type IntoIter = std::slice::Iter<'a, Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.warnings.iter()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for warning in &self.warnings {
+            writeln!(f, "{}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders while auditing the output for common accessibility problems.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let (html, report) = div().child(img().src("/logo.png")).render_with_report();
+///
+/// assert_eq!("<div><img src=\"/logo.png\" /></div>", html);
+/// assert_eq!(1, report.len());
+/// ```
+pub trait RenderWithReport: Renderable + Sized {
+    fn render_with_report(self) -> (String, Report) {
+        let html = self.render();
+        let report = audit(&html);
+        (html, report)
+    }
+}
+
+impl<R> RenderWithReport for R where R: Renderable {}
+
+/// Audits only the heading outline of rendered HTML: levels that are skipped and more than one `<h1>`.
+///
+/// ```
+/// use lira::audit::audit_headings;
+///
+/// let report = audit_headings("<h1>Shop</h1>\n<h3>Tea</h3>\n<h1>Contact</h1>");
+///
+/// assert_eq!(
+///     "<h3> at 2:1 (byte 14): heading level jumps from h1 to h3\n\
+///      <h1> at 3:1 (byte 27): more than one h1\n",
+///     report.to_string()
+/// );
+/// ```
+pub fn audit_headings(html: &str) -> Report {
+    let mut report = audit(html);
+    report.warnings.retain(|warning| {
+        matches!(
+            warning.issue,
+            Issue::HeadingLevelJump { .. } | Issue::MultipleH1
+        )
+    });
+    report
+}
+
+/// Audits already rendered HTML, see [`RenderWithReport`].
+pub fn audit(html: &str) -> Report {
+    let nodes = tree::parse(html);
+
+    let mut labelled = HashSet::new();
+    collect_label_targets(&nodes, &mut labelled);
+
+    let mut audit = Audit {
+        html,
+        labelled,
+        last_heading: None,
+        seen_h1: false,
+        warnings: Vec::new(),
+    };
+    audit.walk(&nodes, false);
+
+    Report {
+        warnings: audit.warnings,
+    }
+}
+
+struct Audit<'a> {
+    html: &'a str,
+    // ids referenced by <label for="...">
+    labelled: HashSet<String>,
+    last_heading: Option<u8>,
+    seen_h1: bool,
+    warnings: Vec<Warning>,
+}
+
+impl Audit<'_> {
+    fn walk(&mut self, nodes: &[TreeNode], in_label: bool) {
+        for node in nodes {
+            if let TreeNode::Element(element) = node {
+                self.check(element, in_label);
+                self.walk(&element.children, in_label || element.name == "label");
+            }
+        }
+    }
+
+    fn check(&mut self, element: &Element, in_label: bool) {
+        match element.name.as_str() {
+            "img" if element.attr("alt").is_none() => self.warn(element, Issue::MissingAlt),
+            "input" | "select" | "textarea"
+                if needs_label(element) && !in_label && !self.is_labelled(element) =>
+            {
+                self.warn(element, Issue::UnlabeledControl)
+            }
+            "button" if !has_accessible_name(element) => self.warn(element, Issue::UnnamedButton),
+            name => {
+                if let Some(level) = heading_level(name) {
+                    if let Some(from) = self.last_heading.filter(|from| level > from + 1) {
+                        self.warn(element, Issue::HeadingLevelJump { from, to: level });
+                    }
+                    if level == 1 && std::mem::replace(&mut self.seen_h1, true) {
+                        self.warn(element, Issue::MultipleH1);
+                    }
+                    self.last_heading = Some(level);
+                }
+            }
+        }
+
+        if let Some(index) = element
+            .attr("tabindex")
+            .and_then(|index| index.trim().parse::<i32>().ok())
+            .filter(|index| *index > 0)
+        {
+            self.warn(element, Issue::PositiveTabindex(index));
+        }
+    }
+
+    fn is_labelled(&self, element: &Element) -> bool {
+        has_aria_name(element)
+            || element
+                .attr("id")
+                .is_some_and(|id| self.labelled.contains(id))
+    }
+
+    fn warn(&mut self, element: &Element, issue: Issue) {
+        let offset = element.offset();
+        let before = &self.html[..offset.min(self.html.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        self.warnings.push(Warning {
+            issue,
+            element: element.name.clone(),
+            offset,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        });
+    }
+}
+
+fn collect_label_targets(nodes: &[TreeNode], out: &mut HashSet<String>) {
+    for node in nodes {
+        if let TreeNode::Element(element) = node {
+            if element.name == "label"
+                && let Some(id) = element.attr("for")
+            {
+                out.insert(id.to_string());
+            }
+            collect_label_targets(&element.children, out);
+        }
+    }
+}
+
+fn needs_label(element: &Element) -> bool {
+    element.name != "input"
+        || !matches!(
+            element.attr("type"),
+            Some("hidden" | "submit" | "reset" | "button" | "image")
+        )
+}
+
+fn has_aria_name(element: &Element) -> bool {
+    ["aria-label", "aria-labelledby", "title"]
+        .iter()
+        .any(|name| element.attr(name).is_some_and(|v| !v.trim().is_empty()))
+}
+
+fn has_accessible_name(element: &Element) -> bool {
+    fn has_img_alt(nodes: &[TreeNode]) -> bool {
+        nodes.iter().any(|node| match node {
+            TreeNode::Element(element) => {
+                (element.name == "img"
+                    && element
+                        .attr("alt")
+                        .is_some_and(|alt| !alt.trim().is_empty()))
+                    || has_img_alt(&element.children)
+            }
+            _ => false,
+        })
+    }
+
+    has_aria_name(element)
+        || !element.text_content().trim().is_empty()
+        || has_img_alt(&element.children)
+}
+
+fn heading_level(name: &str) -> Option<u8> {
+    match name.as_bytes() {
+        [b'h', level @ b'1'..=b'6'] => Some(level - b'0'),
+        _ => None,
+    }
+}
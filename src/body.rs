@@ -0,0 +1,100 @@
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http_body::{Body, Frame};
+use http_body_util::Full;
+
+use crate::core::{Content, Fragment, Node, Open, Renderable, Void};
+
+/// Renders into a response body for hyper and tower services. The rendered buffer becomes the body as it is,
+/// without being copied.
+///
+/// Finished nodes and fragments convert into a body directly:
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::Full;
+/// use lira::prelude::*;
+///
+/// let body: Full<Bytes> = p().text("Hello").into();
+/// let body = div().child(p().text("Hello")).render_body();
+/// ```
+pub trait RenderBody: Renderable + Sized {
+    fn render_body(self) -> Full<Bytes> {
+        Full::new(Bytes::from(self.render()))
+    }
+}
+
+impl<R> RenderBody for R where R: Renderable {}
+
+macro_rules! into_body {
+    ($($ty:ty $(, $tag:ident)?;)*) => {
+        $(
+            impl$(<$tag>)? From<$ty> for Bytes {
+                fn from(value: $ty) -> Self {
+                    Bytes::from(value.render())
+                }
+            }
+
+            impl$(<$tag>)? From<$ty> for Full<Bytes> {
+                fn from(value: $ty) -> Self {
+                    value.render_body()
+                }
+            }
+        )*
+    };
+}
+
+into_body! {
+    Node<Tag, Open>, Tag;
+    Node<Tag, Content>, Tag;
+    Node<Tag, Void>, Tag;
+    Fragment;
+}
+
+/// A body streaming the items of an iterator, see [`chunked_body`].
+pub struct ChunkedBody<I> {
+    chunks: I,
+}
+
+/// Streams a page in chunks, each item is rendered into its own frame when the body is polled. That lets the
+/// browser start on the head while the rest of a large page is still being rendered.
+///
+/// Rendering happens while the server polls the body, which is outside of the scope of any
+/// [`RenderContext`](crate::context::RenderContext) of the handler. Options like a CSP nonce have to be set within
+/// the items, e.g. by rendering them in a scope of their own.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let rows = vec!["a", "b", "c"];
+/// let body = chunked_body(rows.into_iter().map(|row| li().text(row)));
+/// ```
+pub fn chunked_body<It, R>(chunks: It) -> ChunkedBody<It::IntoIter>
+where
+    It: IntoIterator<Item = R>,
+    R: Renderable,
+{
+    ChunkedBody {
+        chunks: chunks.into_iter(),
+    }
+}
+
+impl<I, R> Body for ChunkedBody<I>
+where
+    I: Iterator<Item = R> + Unpin,
+    R: Renderable,
+{
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        let chunk = self.get_mut().chunks.next();
+        Poll::Ready(chunk.map(|chunk| Ok(Frame::data(Bytes::from(chunk.render())))))
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::core::{HasAttributes, Node, Void};
+use crate::html5::{HasInputType, Input, InputType, input};
+
+/// An sRGB color, written the way `<input type="color">` requires its value: `#` and six lowercase hex digits.
+///
+/// Parses `#rgb` and `#rrggbb` in any case, with or without the `#`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let brand: Color = "#FA0".parse().unwrap();
+///
+/// assert_eq!(Color::rgb(255, 170, 0), brand);
+/// assert_eq!("#ffaa00", brand.to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The color of a `0xRRGGBB` number, the upper byte is ignored.
+    pub const fn from_hex(value: u32) -> Self {
+        Self::rgb((value >> 16) as u8, (value >> 8) as u8, value as u8)
+    }
+
+    pub const fn to_hex(self) -> u32 {
+        (self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let error = || ColorError {
+            value: s.to_string(),
+        };
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).map_err(|_| error());
+        let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| error());
+        match hex.len() {
+            3 => Ok(Color::rgb(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+            6 => Ok(Color::rgb(pair(0)?, pair(2)?, pair(4)?)),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color::rgb(r, g, b)
+    }
+}
+
+impl From<[u8; 3]> for Color {
+    fn from([r, g, b]: [u8; 3]) -> Self {
+        Color::rgb(r, g, b)
+    }
+}
+
+/// Error of parsing a [`Color`] from something other than `#rgb` or `#rrggbb`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorError {
+    value: String,
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid color: {:?} isn't #rgb or #rrggbb", self.value)
+    }
+}
+
+impl std::error::Error for ColorError {}
+
+/// Renders `<input type="color">` holding `value`, which is always a valid color since browsers reset anything
+/// else to black.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<input type=\"color\" value=\"#336699\" name=\"accent\" />",
+///     color_input(Color::from_hex(0x336699)).name("accent").render()
+/// );
+/// ```
+pub fn color_input(value: impl Into<Color>) -> Node<Input, Void> {
+    input()
+        .input_type(InputType::Color)
+        .attr("value", value.into().to_string())
+}
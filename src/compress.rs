@@ -0,0 +1,242 @@
+use crate::core::Renderable;
+
+/// Renders into a gzip stream, for handlers serving `Content-Encoding: gzip` themselves:
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let body = div().children(0..100, |i| p().text(i.to_string())).render_gzip();
+///
+/// assert_eq!([0x1f, 0x8b], body[..2]);
+/// ```
+///
+/// The compressor favors speed over ratio, it finds repetitions in a single pass and encodes them with the fixed
+/// codes of DEFLATE, which suits the repetitive markup of large pages.
+///
+/// The page is compressed once it's finished, so the render context applies like it does to `render()`: minifying,
+/// sorting attributes and the render hooks see the page before it's compressed. That's a pass over the whole page
+/// on top of rendering it, the compressor needs the finished output anyway.
+///
+/// There's no brotli counterpart, a brotli encoder is much bigger than this one and would be lira's first required
+/// dependency. Compress the output of `render()` with a brotli crate instead.
+pub trait RenderCompressed: Renderable + Sized {
+    fn render_gzip(self) -> Vec<u8> {
+        gzip(self.render().as_bytes())
+    }
+}
+
+impl<R> RenderCompressed for R where R: Renderable {}
+
+/// Compresses `data` into a gzip stream.
+pub fn gzip(data: &[u8]) -> Vec<u8> {
+    // magic, deflate, no flags, no mtime, no extra flags, unknown OS
+    let mut out = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 255];
+    out.reserve(data.len() / 3);
+
+    let mut bits = BitWriter {
+        out,
+        acc: 0,
+        len: 0,
+    };
+    deflate(data, &mut bits);
+    let mut out = bits.finish();
+
+    out.extend_from_slice(&crc32(data).to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out
+}
+
+// DEFLATE, see RFC 1951. A single block with the fixed Huffman codes, matches are found through hash chains over
+// the last 32 KiB
+
+const WINDOW: usize = 1 << 15;
+const HASH_BITS: u32 = 15;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const MAX_CHAIN: usize = 64;
+const NO_POS: u32 = u32::MAX;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+fn deflate(data: &[u8], bits: &mut BitWriter) {
+    // final block, fixed codes
+    bits.write(1, 1);
+    bits.write(1, 2);
+
+    let mut head = vec![NO_POS; 1 << HASH_BITS];
+    let mut prev = vec![NO_POS; WINDOW];
+
+    let mut pos = 0;
+    while pos < data.len() {
+        let (length, distance) = longest_match(data, pos, &head, &prev);
+
+        if length >= MIN_MATCH {
+            write_match(bits, length, distance);
+            for p in pos..pos + length {
+                insert(data, p, &mut head, &mut prev);
+            }
+            pos += length;
+        } else {
+            write_literal(bits, data[pos] as usize);
+            insert(data, pos, &mut head, &mut prev);
+            pos += 1;
+        }
+    }
+
+    write_literal(bits, 256);
+}
+
+fn hash(data: &[u8], pos: usize) -> usize {
+    let value =
+        u32::from(data[pos]) << 16 | u32::from(data[pos + 1]) << 8 | u32::from(data[pos + 2]);
+    (value.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+}
+
+fn insert(data: &[u8], pos: usize, head: &mut [u32], prev: &mut [u32]) {
+    if pos + MIN_MATCH > data.len() {
+        return;
+    }
+    let h = hash(data, pos);
+    prev[pos % WINDOW] = head[h];
+    head[h] = pos as u32;
+}
+
+fn longest_match(data: &[u8], pos: usize, head: &[u32], prev: &[u32]) -> (usize, usize) {
+    if pos + MIN_MATCH > data.len() {
+        return (0, 0);
+    }
+
+    let max = (data.len() - pos).min(MAX_MATCH);
+    let mut best = (0, 0);
+    let mut candidate = head[hash(data, pos)];
+
+    for _ in 0..MAX_CHAIN {
+        if candidate == NO_POS {
+            break;
+        }
+        let start = candidate as usize;
+        let distance = pos - start;
+        if distance > WINDOW {
+            break;
+        }
+
+        let length = data[start..]
+            .iter()
+            .zip(&data[pos..pos + max])
+            .take_while(|(a, b)| a == b)
+            .count();
+        if length > best.0 {
+            best = (length, distance);
+            if length == max {
+                break;
+            }
+        }
+
+        let next = prev[start % WINDOW];
+        // older entries of the slot were overwritten, the chain would continue at a newer position
+        if next != NO_POS && next as usize >= start {
+            break;
+        }
+        candidate = next;
+    }
+
+    best
+}
+
+fn write_literal(bits: &mut BitWriter, symbol: usize) {
+    match symbol {
+        0..=143 => bits.write_code(0x30 + symbol as u32, 8),
+        144..=255 => bits.write_code(0x190 + (symbol - 144) as u32, 9),
+        256..=279 => bits.write_code((symbol - 256) as u32, 7),
+        _ => bits.write_code(0xc0 + (symbol - 280) as u32, 8),
+    }
+}
+
+fn write_match(bits: &mut BitWriter, length: usize, distance: usize) {
+    let code = LENGTH_BASE.partition_point(|&base| base as usize <= length) - 1;
+    write_literal(bits, 257 + code);
+    bits.write(
+        (length - LENGTH_BASE[code] as usize) as u32,
+        LENGTH_EXTRA[code],
+    );
+
+    let code = DIST_BASE.partition_point(|&base| base as usize <= distance) - 1;
+    bits.write_code(code as u32, 5);
+    bits.write(
+        (distance - DIST_BASE[code] as usize) as u32,
+        DIST_EXTRA[code],
+    );
+}
+
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u64,
+    len: u8,
+}
+
+impl BitWriter {
+    // values are packed starting at the least significant bit
+    fn write(&mut self, value: u32, len: u8) {
+        self.acc |= u64::from(value) << self.len;
+        self.len += len;
+        while self.len >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    // Huffman codes are packed starting at their most significant bit
+    fn write_code(&mut self, code: u32, len: u8) {
+        self.write(code.reverse_bits() >> (32 - len), len);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+// CRC-32 as used by gzip, see RFC 1952
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
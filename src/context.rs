@@ -0,0 +1,675 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::assets::Assets;
+use crate::document::HeadRequirements;
+use crate::forms::FieldErrors;
+#[cfg(feature = "fluent")]
+use crate::i18n::Bundle;
+use crate::ids::{ControlIds, Slugs};
+use crate::memo::FragmentCache;
+use crate::stats::RenderStats;
+
+/// Source of CSP nonces, queried once when a [`RenderContext`] is created from it.
+pub trait NonceProvider {
+    fn nonce(&self) -> String;
+}
+
+impl NonceProvider for str {
+    fn nonce(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl NonceProvider for String {
+    fn nonce(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<F> NonceProvider for F
+where
+    F: Fn() -> String,
+{
+    fn nonce(&self) -> String {
+        self()
+    }
+}
+
+/// Hook returning the inline style for elements with the given tag name.
+pub type StyleInliner = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// Hook receiving the [`RenderStats`] of every finished render.
+pub type StatsHook = dyn Fn(&RenderStats) + Send + Sync;
+
+/// How void elements like `<img>` are closed, see [`RenderContext::void_style`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum VoidStyle {
+    /// `<img src="a.png" />`, readable by XML parsers as well.
+    #[default]
+    SelfClosing,
+
+    /// `<img src="a.png">`, as serialized by browsers.
+    Html5,
+}
+
+/// How the characters escaped in text and attribute values are written, see [`RenderContext::entity_style`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum EntityStyle {
+    /// `&amp;`, `&lt;`, `&gt;` and `&quot;`, `'` is always written as `&#39;`.
+    #[default]
+    Named,
+
+    /// `&#38;`, `&#60;`, `&#62;`, `&#34;` and `&#39;`, for consumers that only decode numeric references.
+    Numeric,
+}
+
+/// Initial sizes of the buffers elements are written into, see [`RenderContext::buffer_capacities`].
+///
+/// Buffers grow as needed, so these only decide how often they are reallocated on the way. Pages with large text
+/// nodes or long tables get away with fewer reallocations by starting bigger, small fragments rendered in bulk
+/// waste less memory by starting smaller.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BufferCapacities {
+    /// Bytes reserved for most elements and fragments, 128 by default.
+    pub node: usize,
+
+    /// Bytes reserved for elements that usually only hold a bit of text like `<td>` or `<li>`, 32 by default.
+    pub small: usize,
+
+    /// Bytes reserved for `<html>` and the body of a [`Document`](crate::document::Document), which hold the
+    /// whole page, 2048 by default.
+    pub page: usize,
+}
+
+impl BufferCapacities {
+    pub const DEFAULT: BufferCapacities = BufferCapacities {
+        node: 128,
+        small: 32,
+        page: 2048,
+    };
+}
+
+impl Default for BufferCapacities {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Per-render settings that elements pick up while they are being built.
+///
+/// Since nodes are written into their buffers as they are constructed, a context has to be active while the tree
+/// is built, which is what [`RenderContext::scope`] does:
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = RenderContext::new()
+///     .nonce("r4nd0m")
+///     .scope(|| div().child(script().raw("init()")).render());
+///
+/// assert_eq!("<div><script nonce=\"r4nd0m\">init()</script></div>", res);
+/// ```
+#[derive(Clone, Default)]
+pub struct RenderContext {
+    nonce: Option<String>,
+    assets: Option<Arc<Assets>>,
+    #[cfg(feature = "fluent")]
+    bundle: Option<Arc<Bundle>>,
+    escape_non_ascii: bool,
+    mode: RenderMode,
+    // inverted so the derived default keeps validation on
+    unchecked_attribute_names: bool,
+    entity_style: EntityStyle,
+    minify: bool,
+    sorted_attributes: bool,
+    void_style: VoidStyle,
+    buffer_capacities: BufferCapacities,
+    base_path: Option<String>,
+    style_inliner: Option<Arc<StyleInliner>>,
+    key_attribute: Option<String>,
+    stats_hook: Option<Arc<StatsHook>>,
+    csrf_token: Option<String>,
+    csrf_field_name: Option<String>,
+    field_errors: Option<Arc<FieldErrors>>,
+    head_requirements: Option<Arc<Mutex<HeadRequirements>>>,
+    fragment_cache: Option<Arc<dyn FragmentCache>>,
+    // fresh for every `scope`, shared between the copies `enter` activates, e.g. on the threads of `children_par`
+    id_counter: Arc<AtomicUsize>,
+    // shared the same way, for heading ids that are unique within the page
+    slugs: Arc<Mutex<Slugs>>,
+    // and for form control ids, apart from the headings so the error summary can tell the ids up front
+    controls: Arc<Mutex<ControlIds>>,
+}
+
+impl RenderContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// CSP nonce stamped onto every `<script>` and `<style>` element.
+    pub fn nonce(mut self, value: impl AsRef<str>) -> Self {
+        self.nonce = Some(value.as_ref().to_string());
+        self
+    }
+
+    /// Like [`RenderContext::nonce`] but asks the provider for the value.
+    pub fn nonce_from(self, provider: &(impl NonceProvider + ?Sized)) -> Self {
+        self.nonce(provider.nonce())
+    }
+
+    /// Asset manifest used to resolve logical asset names to their hashed paths.
+    pub fn assets(mut self, assets: impl Into<Arc<Assets>>) -> Self {
+        self.assets = Some(assets.into());
+        self
+    }
+
+    /// Fluent bundle used by [`t`](crate::i18n::t) and the `.t()` text helpers.
+    #[cfg(feature = "fluent")]
+    pub fn bundle(mut self, bundle: impl Into<Arc<Bundle>>) -> Self {
+        self.bundle = Some(bundle.into());
+        self
+    }
+
+    /// Escapes every non-ASCII character in text and attribute values as a numeric character reference, for
+    /// email clients and legacy systems that mangle UTF-8.
+    pub fn escape_non_ascii(mut self, value: bool) -> Self {
+        self.escape_non_ascii = value;
+        self
+    }
+
+    /// How much is checked while building, see [`RenderMode`].
+    pub fn mode(mut self, mode: RenderMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Whether attribute and flag names are cleaned up, on by default. Characters that can't be part of a name,
+    /// whitespace, quotes, `<`, `>`, `/` and `=`, are left out and names with nothing left are skipped, so a name
+    /// taken from user input can't add attributes of its own like `flag("onload=alert(1)")` would.
+    ///
+    /// Turning it off writes names as they are, for benchmarks and code that only uses literal names. In
+    /// [`RenderMode::Strict`] names are always validated.
+    pub fn validate_attribute_names(mut self, value: bool) -> Self {
+        self.unchecked_attribute_names = !value;
+        self
+    }
+
+    /// Whether escaped characters are written as named or numeric references, named by default.
+    ///
+    /// Combine it with [`escape_non_ascii`](RenderContext::escape_non_ascii) for output that's pure ASCII and only
+    /// uses numeric references.
+    pub fn entity_style(mut self, style: EntityStyle) -> Self {
+        self.entity_style = style;
+        self
+    }
+
+    /// Minifies the output of every `render()`, see [`minify`](crate::minify::minify).
+    ///
+    /// Minifying means parsing the output again, so use it for output that contains indented markup from `raw()`,
+    /// lira's own markup has no whitespace to remove.
+    pub fn minify(mut self, value: bool) -> Self {
+        self.minify = value;
+        self
+    }
+
+    /// Writes the attributes of every element sorted by name, so the output doesn't depend on the order the
+    /// attributes were set in, e.g. for cache keys and snapshot tests that should survive refactors.
+    ///
+    /// Like [`minify`](RenderContext::minify) this parses the output of every `render()` again.
+    pub fn sorted_attributes(mut self, value: bool) -> Self {
+        self.sorted_attributes = value;
+        self
+    }
+
+    /// How void elements are closed, [`VoidStyle::SelfClosing`] by default.
+    pub fn void_style(mut self, style: VoidStyle) -> Self {
+        self.void_style = style;
+        self
+    }
+
+    /// Initial sizes of the buffers of the elements built in this context.
+    ///
+    /// ```
+    /// use lira::prelude::*;
+    ///
+    /// let capacities = BufferCapacities { page: 64 * 1024, ..BufferCapacities::DEFAULT };
+    ///
+    /// let res = RenderContext::new()
+    ///     .buffer_capacities(capacities)
+    ///     .scope(|| html().child(body().child(p().text("Large page"))).render());
+    ///
+    /// assert_eq!("<!DOCTYPE html><html><body><p>Large page</p></body></html>", res);
+    /// ```
+    pub fn buffer_capacities(mut self, capacities: BufferCapacities) -> Self {
+        self.buffer_capacities = capacities;
+        self
+    }
+
+    /// Path the app is mounted under, prefixed to every root-relative URL written to `href`, `src`, `srcset`,
+    /// `action`, `formaction`, `poster` and `cite`, so `/app.css` becomes `/myapp/app.css`.
+    ///
+    /// Protocol-relative URLs (`//cdn.example.com`) and markup inserted with `raw()` are left alone.
+    pub fn base_path(mut self, path: impl AsRef<str>) -> Self {
+        let path = path.as_ref().trim_matches('/');
+        self.base_path = (!path.is_empty()).then(|| format!("/{}", path));
+        self
+    }
+
+    /// Hook adding a `style` attribute to every element it returns a style for, used to inline CSS into emails.
+    ///
+    /// The inlined style is written as the first attribute, so don't set another `style` on the same element.
+    pub fn inline_styles(
+        mut self,
+        inliner: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.style_inliner = Some(Arc::new(inliner));
+        self
+    }
+
+    /// Attribute written by `.key()` and `children_keyed()`, `id` by default since that's what idiomorph and
+    /// morphdom match elements by. Use e.g. `data-key` if the ids are needed for something else.
+    pub fn key_attribute(mut self, name: impl AsRef<str>) -> Self {
+        self.key_attribute = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// CSRF token written by [`csrf`](crate::forms::csrf), set it once per request in the middleware that creates
+    /// the token.
+    pub fn csrf_token(mut self, token: impl AsRef<str>) -> Self {
+        self.csrf_token = Some(token.as_ref().to_string());
+        self
+    }
+
+    /// Name of the hidden CSRF input, `csrf_token` by default.
+    pub fn csrf_field_name(mut self, name: impl AsRef<str>) -> Self {
+        self.csrf_field_name = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// Validation errors the form helpers render next to their fields, see [`FieldErrors`].
+    pub fn field_errors(mut self, errors: impl Into<Arc<FieldErrors>>) -> Self {
+        self.field_errors = Some(errors.into());
+        self
+    }
+
+    /// Cache [`memo`](crate::memo::memo) keeps rendered partials in, shared between requests.
+    pub fn fragment_cache<C>(mut self, cache: Arc<C>) -> Self
+    where
+        C: FragmentCache + 'static,
+    {
+        self.fragment_cache = Some(cache);
+        self
+    }
+
+    /// Hook called with the stats of every `render()` in this scope, e.g. to feed page weight into metrics.
+    ///
+    /// Measuring means parsing the output again, so this roughly doubles the cost of a render.
+    pub fn on_render(mut self, hook: impl Fn(&RenderStats) + Send + Sync + 'static) -> Self {
+        self.stats_hook = Some(Arc::new(hook));
+        self
+    }
+
+    pub(crate) fn head_requirements(mut self, requirements: Arc<Mutex<HeadRequirements>>) -> Self {
+        self.head_requirements = Some(requirements);
+        self
+    }
+
+    /// The settings of this context as names and readable values, for the [`debug_panel`](crate::debug::debug_panel).
+    /// Tokens and hooks are only reported as set, their values don't belong on a page.
+    #[cfg(feature = "debug")]
+    pub(crate) fn debug_values(&self) -> Vec<(&'static str, String)> {
+        let set = |value: bool| if value { "set" } else { "unset" }.to_string();
+        let capacities = self.buffer_capacities;
+
+        let values = vec![
+            ("mode", format!("{:?}", self.mode)),
+            (
+                "validate_attribute_names",
+                (!self.unchecked_attribute_names).to_string(),
+            ),
+            ("nonce", set(self.nonce.is_some())),
+            (
+                "base_path",
+                self.base_path.clone().unwrap_or_else(|| set(false)),
+            ),
+            ("escape_non_ascii", self.escape_non_ascii.to_string()),
+            ("entity_style", format!("{:?}", self.entity_style)),
+            ("void_style", format!("{:?}", self.void_style)),
+            ("minify", self.minify.to_string()),
+            ("sorted_attributes", self.sorted_attributes.to_string()),
+            (
+                "buffer_capacities",
+                format!(
+                    "node {}, small {}, page {}",
+                    capacities.node, capacities.small, capacities.page
+                ),
+            ),
+            (
+                "key_attribute",
+                self.key_attribute
+                    .clone()
+                    .unwrap_or_else(|| "id".to_string()),
+            ),
+            ("assets", set(self.assets.is_some())),
+            ("csrf_token", set(self.csrf_token.is_some())),
+            (
+                "field_errors",
+                match &self.field_errors {
+                    Some(errors) => format!("{} fields", errors.iter().count()),
+                    None => set(false),
+                },
+            ),
+            ("inline_styles", set(self.style_inliner.is_some())),
+            ("on_render", set(self.stats_hook.is_some())),
+            ("fragment_cache", set(self.fragment_cache.is_some())),
+        ];
+        #[cfg(feature = "fluent")]
+        let values = {
+            let mut values = values;
+            values.push(("bundle", set(self.bundle.is_some())));
+            values
+        };
+        values
+    }
+
+    /// Makes this context the active one while `fun` runs, nested scopes shadow outer ones.
+    ///
+    /// Generated ids, heading slugs and control ids are counted per scope, so rendering twice with the same context
+    /// produces the same ids both times.
+    pub fn scope<R>(mut self, fun: impl FnOnce() -> R) -> R {
+        self.id_counter = Arc::default();
+        self.slugs = Arc::default();
+        self.controls = Arc::default();
+        self.enter(fun)
+    }
+
+    /// Like [`scope`](Self::scope) but continues the ids and slugs of the context it was copied from, for the
+    /// copies rendering parts of the same page.
+    pub(crate) fn enter<R>(self, fun: impl FnOnce() -> R) -> R {
+        let flags = Flags {
+            escape_non_ascii: self.escape_non_ascii,
+            validate_attribute_names: match self.mode {
+                RenderMode::Lenient => false,
+                RenderMode::Standard => !self.unchecked_attribute_names,
+                RenderMode::Strict => true,
+            },
+            strict: self.mode == RenderMode::Strict,
+            entity_style: self.entity_style,
+            minify: self.minify,
+            sorted_attributes: self.sorted_attributes,
+            void_style: self.void_style,
+            buffer_capacities: self.buffer_capacities,
+            base_path: self.base_path.is_some(),
+            inline_styles: self.style_inliner.is_some(),
+            render_stats: self.stats_hook.is_some(),
+        };
+
+        CONTEXT.with(|stack| stack.borrow_mut().push(self));
+        let _guard = ScopeGuard {
+            flags: FLAGS.with(|cell| cell.replace(flags)),
+        };
+        fun()
+    }
+}
+
+/// How much lira checks while building elements, set with [`RenderContext::mode`].
+///
+/// The `strict` feature makes [`Strict`](RenderMode::Strict) the default, for deployments that want the checks
+/// everywhere without setting up a context for every render.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let link = |url: &str| a().href(url).text("Profile").render();
+///
+/// assert_eq!(
+///     "<a href=\"about:invalid\">Profile</a>",
+///     RenderContext::new()
+///         .mode(RenderMode::Strict)
+///         .scope(|| link("javascript:alert(1)"))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Writes everything as it is, the fastest path for benchmarks and trusted, literal markup.
+    Lenient,
+    /// Validates attribute names, the default.
+    Standard,
+    /// Additionally replaces `javascript:`, `vbscript:` and scriptable `data:` URLs in `href`, `src` and the other
+    /// URL attributes with `about:invalid`, and panics when a child would be moved out of its parent by the
+    /// browser's parser, like a `<div>` in a `<p>` or a link in a link. Checking children re-reads their markup,
+    /// which makes building deep trees slower.
+    Strict,
+}
+
+impl RenderMode {
+    const DEFAULT: RenderMode = if cfg!(feature = "strict") {
+        RenderMode::Strict
+    } else {
+        RenderMode::Standard
+    };
+}
+
+impl Default for RenderMode {
+    fn default() -> Self {
+        RenderMode::DEFAULT
+    }
+}
+
+/// Settings checked on hot paths, kept outside of the context stack so checking them is cheap.
+#[derive(Clone, Copy)]
+pub(crate) struct Flags {
+    pub(crate) escape_non_ascii: bool,
+    pub(crate) validate_attribute_names: bool,
+    pub(crate) strict: bool,
+    pub(crate) entity_style: EntityStyle,
+    pub(crate) minify: bool,
+    pub(crate) sorted_attributes: bool,
+    pub(crate) void_style: VoidStyle,
+    pub(crate) buffer_capacities: BufferCapacities,
+    pub(crate) base_path: bool,
+    pub(crate) inline_styles: bool,
+    pub(crate) render_stats: bool,
+}
+
+impl Flags {
+    const NONE: Flags = Flags {
+        escape_non_ascii: false,
+        validate_attribute_names: !matches!(RenderMode::DEFAULT, RenderMode::Lenient),
+        strict: matches!(RenderMode::DEFAULT, RenderMode::Strict),
+        entity_style: EntityStyle::Named,
+        minify: false,
+        sorted_attributes: false,
+        void_style: VoidStyle::SelfClosing,
+        buffer_capacities: BufferCapacities::DEFAULT,
+        base_path: false,
+        inline_styles: false,
+        render_stats: false,
+    };
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Vec<RenderContext>> = const { RefCell::new(Vec::new()) };
+    static FLAGS: Cell<Flags> = const { Cell::new(Flags::NONE) };
+    // renders in progress that others are part of, see `nested`
+    static NESTED: Cell<usize> = const { Cell::new(0) };
+}
+
+struct ScopeGuard {
+    flags: Flags,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        CONTEXT.with(|stack| stack.borrow_mut().pop());
+        FLAGS.with(|cell| cell.set(self.flags));
+    }
+}
+
+struct NestedGuard;
+
+impl Drop for NestedGuard {
+    fn drop(&mut self) {
+        NESTED.with(|cell| cell.set(cell.get() - 1));
+    }
+}
+
+/// Runs `fun` as part of a larger render, renders within it don't report stats since the page they end up in does.
+pub(crate) fn nested<R>(fun: impl FnOnce() -> R) -> R {
+    NESTED.with(|cell| cell.set(cell.get() + 1));
+    let _guard = NestedGuard;
+    fun()
+}
+
+pub(crate) fn flags() -> Flags {
+    FLAGS.with(Cell::get)
+}
+
+/// A copy of the active context, to activate it on another thread.
+#[cfg(feature = "rayon")]
+pub(crate) fn current() -> Option<RenderContext> {
+    with_current(|ctx| ctx.cloned())
+}
+
+/// Calls `fun` with the currently active context, if any.
+pub(crate) fn with_current<R>(fun: impl FnOnce(Option<&RenderContext>) -> R) -> R {
+    CONTEXT.with(|stack| fun(stack.borrow().last()))
+}
+
+/// The CSP nonce of the active context.
+pub fn current_nonce() -> Option<String> {
+    with_current(|ctx| ctx.and_then(|ctx| ctx.nonce.clone()))
+}
+
+/// The base path of the active context, without a trailing slash.
+pub fn current_base_path() -> Option<String> {
+    with_current(|ctx| ctx.and_then(|ctx| ctx.base_path.clone()))
+}
+
+/// The asset manifest of the active context.
+pub fn current_assets() -> Option<Arc<Assets>> {
+    with_current(|ctx| ctx.and_then(|ctx| ctx.assets.clone()))
+}
+
+/// The Fluent bundle of the active context.
+#[cfg(feature = "fluent")]
+pub fn current_bundle() -> Option<Arc<Bundle>> {
+    with_current(|ctx| ctx.and_then(|ctx| ctx.bundle.clone()))
+}
+
+/// Inline style for elements with the tag name `tag`, according to the active context.
+pub fn current_inline_style(tag: &str) -> Option<String> {
+    let inliner = with_current(|ctx| ctx.and_then(|ctx| ctx.style_inliner.clone()))?;
+    inliner(tag)
+}
+
+/// The attribute used for element keys by the active context.
+pub fn current_key_attribute() -> Cow<'static, str> {
+    with_current(|ctx| {
+        ctx.and_then(|ctx| ctx.key_attribute.clone())
+            .map_or(Cow::Borrowed("id"), Cow::Owned)
+    })
+}
+
+/// The CSRF token of the active context.
+pub fn current_csrf_token() -> Option<String> {
+    with_current(|ctx| ctx.and_then(|ctx| ctx.csrf_token.clone()))
+}
+
+/// The name of the hidden CSRF input according to the active context.
+pub fn current_csrf_field_name() -> Cow<'static, str> {
+    with_current(|ctx| {
+        ctx.and_then(|ctx| ctx.csrf_field_name.clone())
+            .map_or(Cow::Borrowed("csrf_token"), Cow::Owned)
+    })
+}
+
+/// The validation errors of the active context.
+pub fn current_field_errors() -> Option<Arc<FieldErrors>> {
+    with_current(|ctx| ctx.and_then(|ctx| ctx.field_errors.clone()))
+}
+
+/// The fragment cache of the active context.
+pub fn current_fragment_cache() -> Option<Arc<dyn FragmentCache>> {
+    with_current(|ctx| ctx.and_then(|ctx| ctx.fragment_cache.clone()))
+}
+
+/// Head requirements of the [`Document`](crate::document::Document) being rendered.
+pub(crate) fn current_head_requirements() -> Option<Arc<Mutex<HeadRequirements>>> {
+    with_current(|ctx| ctx.and_then(|ctx| ctx.head_requirements.clone()))
+}
+
+/// Next number for generated ids, counting per context so every render produces the same ids.
+pub(crate) fn next_id_number() -> Option<usize> {
+    with_current(|ctx| ctx.map(|ctx| ctx.id_counter.fetch_add(1, Ordering::Relaxed) + 1))
+}
+
+/// How many ids, slugs and control ids the active context handed out so far.
+pub(crate) fn handed_out_ids() -> usize {
+    with_current(|ctx| {
+        ctx.map_or(0, |ctx| {
+            let slugs = ctx
+                .slugs
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .len();
+            let controls = ctx
+                .controls
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .len();
+            ctx.id_counter.load(Ordering::Relaxed) + slugs + controls
+        })
+    })
+}
+
+/// A slug of `text` that no other heading or control of the active context got, `None` outside of a context.
+pub(crate) fn unique_slug(text: &str, fallback: &str) -> Option<String> {
+    with_current(|ctx| {
+        ctx.map(|ctx| {
+            let controls = ctx.controls.lock().unwrap_or_else(|err| err.into_inner());
+            ctx.slugs
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .unique_besides(text, fallback, &controls)
+        })
+    })
+}
+
+/// `id` numbered like `id-2` if the active context handed it out to a control already, `None` outside of a
+/// context.
+pub(crate) fn unique_control_id(id: &str) -> Option<String> {
+    with_current(|ctx| {
+        ctx.map(|ctx| {
+            ctx.controls
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .number(id)
+        })
+    })
+}
+
+/// The id the first control with the base `id` got or is going to get, `None` outside of a context.
+pub(crate) fn first_control_id(id: &str) -> Option<String> {
+    with_current(|ctx| {
+        ctx.map(|ctx| {
+            ctx.controls
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .first(id)
+        })
+    })
+}
+
+/// Calls the stats hook of the active context with the stats of `html`, unless it's part of a larger render.
+pub(crate) fn report_stats(html: &str) {
+    if NESTED.with(Cell::get) > 0 {
+        return;
+    }
+    if let Some(hook) = with_current(|ctx| ctx.and_then(|ctx| ctx.stats_hook.clone())) {
+        hook(&RenderStats::of(html));
+    }
+}
@@ -1,6 +1,67 @@
+use std::borrow::Cow;
+use std::fmt;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
-const DEFAULT_BUFFER_CAPACITY: usize = 128;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::context::{self, EntityStyle, VoidStyle};
+use crate::pool;
+use crate::tokenizer;
+
+// capacity of the buffers of most nodes, according to the active context
+fn node_capacity() -> usize {
+    context::flags().buffer_capacities.node
+}
+
+// for leaves like `<td>1x1`, they grow like any other buffer if that's not enough
+fn small_capacity() -> usize {
+    context::flags().buffer_capacities.small
+}
+
+// the settings of the render context that apply while building a node, read once when it's started instead of on
+// every attribute and text
+#[derive(Clone, Copy)]
+struct NodeFlags {
+    strict: bool,
+    base_path: bool,
+    inline_styles: bool,
+    validate_attribute_names: bool,
+    escape: Escape,
+}
+
+impl NodeFlags {
+    fn current() -> Self {
+        let flags = context::flags();
+        NodeFlags {
+            strict: flags.strict,
+            base_path: flags.base_path,
+            inline_styles: flags.inline_styles,
+            validate_attribute_names: flags.validate_attribute_names,
+            escape: Escape::of(flags),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Escape {
+    non_ascii: bool,
+    numeric: bool,
+}
+
+impl Escape {
+    fn of(flags: context::Flags) -> Self {
+        Escape {
+            non_ascii: flags.escape_non_ascii,
+            numeric: flags.entity_style == EntityStyle::Numeric,
+        }
+    }
+
+    fn current() -> Self {
+        Self::of(context::flags())
+    }
+}
 
 pub trait CanAddAttributes {}
 
@@ -17,14 +78,44 @@ pub struct Void;
 
 impl CanAddAttributes for Void {}
 
-pub trait HasAttributes {
+pub trait HasAttributes: Sized {
     /// Add a custom attribute to the element
     fn attr(self, k: impl AsRef<str>, v: impl AsRef<str>) -> Self;
 
     /// Add a boolean attribute to the element
     fn flag(self, k: impl AsRef<str>) -> Self;
+
+    /// Add an integer attribute to the element
+    fn attr_int(self, k: impl AsRef<str>, v: impl Integer) -> Self {
+        self.attr(k, v.to_string())
+    }
+
+    /// Add a floating point attribute to the element
+    fn attr_float(self, k: impl AsRef<str>, v: impl Float) -> Self {
+        self.attr(k, v.to_string())
+    }
+
+    /// Add an attribute with a formatted value: `attr_fmt("href", format_args!("/users/{}", id))`
+    fn attr_fmt(self, k: impl AsRef<str>, v: fmt::Arguments) -> Self {
+        self.attr(k, v.to_string())
+    }
+}
+
+/// Integer types accepted by [`HasAttributes::attr_int`].
+pub trait Integer: fmt::Display + Copy {}
+
+/// Floating point types accepted by [`HasAttributes::attr_float`].
+pub trait Float: fmt::Display + Copy {}
+
+macro_rules! numbers {
+    ($trait:ident: $($ty:ty),*) => {
+        $(impl $trait for $ty {})*
+    };
 }
 
+numbers!(Integer: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+numbers!(Float: f32, f64);
+
 pub trait CanAddChildren {}
 
 pub trait CanAddText {}
@@ -32,26 +123,45 @@ pub trait CanAddText {}
 pub struct Node<Tag, State = Open> {
     tag: &'static [u8],
     buf: Vec<u8>,
+    flags: NodeFlags,
+    // start tags of the children as bits of `NESTING_TAGS`, only kept in strict mode
+    descendants: u64,
     _state: PhantomData<State>,
     _tag: PhantomData<Tag>,
 }
 
 impl<Tag> Node<Tag, Open> {
     pub fn new(tag: &'static str) -> Self {
-        let buf = Vec::with_capacity(DEFAULT_BUFFER_CAPACITY);
-        Self::with_buffer(tag, buf)
+        let buf = pool::buffer(node_capacity());
+        Self::in_buffer(tag, buf)
+    }
+
+    /// Like [`new`](Self::new) but starts with a smaller buffer, for elements that usually only hold a bit of text.
+    pub fn new_small(tag: &'static str) -> Self {
+        let buf = pool::buffer(small_capacity());
+        Self::in_buffer(tag, buf)
     }
 
-    pub fn with_buffer(tag: &'static str, mut buf: Vec<u8>) -> Self {
+    /// Starts the element at the end of `buf`, which has to hold valid UTF-8.
+    pub fn with_buffer(tag: &'static str, buf: Vec<u8>) -> Self {
+        check_buffer(&buf);
+        Self::in_buffer(tag, buf)
+    }
+
+    // like `with_buffer` for buffers of this crate, which are valid UTF-8 already
+    pub(crate) fn in_buffer(tag: &'static str, mut buf: Vec<u8>) -> Self {
         buf.push(b'<');
         buf.extend_from_slice(tag.as_bytes());
 
         Node {
             tag: tag.as_bytes(),
             buf,
+            flags: NodeFlags::current(),
+            descendants: 0,
             _state: PhantomData,
             _tag: PhantomData,
         }
+        .with_inlined_style()
     }
 
     pub fn close(mut self) -> Node<Tag, Content> {
@@ -59,6 +169,8 @@ impl<Tag> Node<Tag, Open> {
         Node {
             tag: self.tag,
             buf: self.buf,
+            flags: self.flags,
+            descendants: self.descendants,
             _state: PhantomData,
             _tag: PhantomData,
         }
@@ -79,6 +191,18 @@ impl<State, Tag> Node<Tag, State> {
     {
         if condition { fun(self) } else { self }
     }
+
+    // the element and its descendants as bits of `NESTING_TAGS`
+    fn nested(&self) -> u64 {
+        let tag = std::str::from_utf8(self.tag).unwrap_or_default();
+        tag_bit(tag) | self.descendants
+    }
+
+    #[cfg(feature = "maud")]
+    pub(crate) fn tag_name(&self) -> &'static str {
+        // always written from a `&'static str`
+        std::str::from_utf8(self.tag).unwrap_or_default()
+    }
 }
 
 impl<Tag, State> HasAttributes for Node<Tag, State>
@@ -88,12 +212,48 @@ where
     fn attr(mut self, k: impl AsRef<str>, v: impl AsRef<str>) -> Self {
         let k = k.as_ref();
         let v = v.as_ref();
+        let flags = self.flags;
+        let prefixed;
+        let v = if flags.strict && is_url_attribute(k) && !is_safe_url(v) {
+            BLOCKED_URL
+        } else if flags.base_path && is_url_attribute(k) {
+            prefixed = with_base_path(k, v);
+            prefixed.as_ref()
+        } else {
+            v
+        };
         self.buf.reserve(k.len() + v.len() + 8);
 
-        self.buf.push(b' ');
-        write_normalized(&mut self.buf, k);
+        if !write_name(&mut self.buf, k, flags.validate_attribute_names) {
+            return self;
+        }
+        self.buf.extend_from_slice(b"=\"");
+        escape_into(&mut self.buf, v, flags.escape);
+        self.buf.push(b'"');
+        self
+    }
+
+    fn attr_int(self, k: impl AsRef<str>, v: impl Integer) -> Self {
+        self.attr_display(k.as_ref(), v)
+    }
+
+    fn attr_float(self, k: impl AsRef<str>, v: impl Float) -> Self {
+        self.attr_display(k.as_ref(), v)
+    }
+
+    fn attr_fmt(mut self, k: impl AsRef<str>, v: fmt::Arguments) -> Self {
+        let k = k.as_ref();
+        let flags = self.flags;
+        if (flags.strict || flags.base_path) && is_url_attribute(k) {
+            return self.attr(k, v.to_string());
+        }
+        self.buf.reserve(k.len() + 8);
+
+        if !write_name(&mut self.buf, k, flags.validate_attribute_names) {
+            return self;
+        }
         self.buf.extend_from_slice(b"=\"");
-        write_escaped(&mut self.buf, v);
+        escape_fmt_into(&mut self.buf, v, flags.escape);
         self.buf.push(b'"');
         self
     }
@@ -102,8 +262,7 @@ where
         let k = k.as_ref();
         self.buf.reserve(k.len() + 8);
 
-        self.buf.push(b' ');
-        write_normalized(&mut self.buf, k);
+        write_name(&mut self.buf, k, self.flags.validate_attribute_names);
         self
     }
 }
@@ -113,7 +272,7 @@ where
     Tag: CanAddChildren,
 {
     pub fn child(mut self, child: impl Renderable) -> Node<Tag, Content> {
-        child.render_into(&mut self.buf);
+        self.push_child(child);
         self
     }
 
@@ -125,20 +284,85 @@ where
     {
         for item in iter {
             let elem = fun(item);
-            elem.render_into(&mut self.buf);
+            self.push_child(elem);
         }
 
         self
     }
 
+    /// Adds `n` children built by `fun` from their index, like [`repeat`].
+    pub fn repeat_child<Fn, R>(self, n: usize, fun: Fn) -> Self
+    where
+        Fn: FnMut(usize) -> R,
+        R: Renderable,
+    {
+        self.child(repeat(n, fun))
+    }
+
     pub fn child_when<Fn, T>(mut self, condition: bool, f: Fn) -> Self
     where
         Fn: FnOnce() -> Node<T, Content>,
     {
         if condition {
             let child = f();
-            child.render_into(&mut self.buf);
+            self.push_child(child);
+        }
+        self
+    }
+
+    /// Builds children directly in this node's buffer instead of rendering each into its own buffer first.
+    ///
+    /// ```
+    /// use lira::prelude::*;
+    ///
+    /// let res = ul()
+    ///     .child_with(|w| {
+    ///         for i in 1..=2 {
+    ///             w.li(|li| li.text(i.to_string()));
+    ///         }
+    ///     })
+    ///     .render();
+    ///
+    /// assert_eq!("<ul><li>1</li><li>2</li></ul>", res);
+    /// ```
+    pub fn child_with<Fn>(mut self, fun: Fn) -> Self
+    where
+        Fn: FnOnce(&mut ElementWriter),
+    {
+        let mut writer = ElementWriter {
+            buf: std::mem::take(&mut self.buf),
+        };
+        fun(&mut writer);
+        self.buf = writer.buf;
+        self
+    }
+
+    /// Like [`children`](Self::children) but keys every child with the value of `key_fn`, so DOM morphing
+    /// libraries can tell which element is which when the list is reordered.
+    ///
+    /// The key is added to the first element each view renders.
+    pub fn children_keyed<It, KeyFn, K, Fn, T, R>(
+        mut self,
+        iter: It,
+        mut key_fn: KeyFn,
+        mut fun: Fn,
+    ) -> Self
+    where
+        It: IntoIterator<Item = T>,
+        KeyFn: FnMut(&T) -> K,
+        K: AsRef<str>,
+        Fn: FnMut(T) -> R,
+        R: Renderable,
+    {
+        let key_attr = context::current_key_attribute();
+
+        for item in iter {
+            let key = key_fn(&item);
+            let start = self.buf.len();
+            self.push_child(fun(item));
+            insert_attr(&mut self.buf, start, &key_attr, key.as_ref());
         }
+
         self
     }
 }
@@ -160,12 +384,96 @@ where
         self.close().children(iter, fun)
     }
 
+    pub fn repeat_child<Fn, R>(self, n: usize, fun: Fn) -> Node<Tag, Content>
+    where
+        Fn: FnMut(usize) -> R,
+        R: Renderable,
+    {
+        self.close().repeat_child(n, fun)
+    }
+
     pub fn child_when<Fn, T>(self, condition: bool, f: Fn) -> Node<Tag, Content>
     where
         Fn: FnOnce() -> Node<T, Content>,
     {
         self.close().child_when(condition, f)
     }
+
+    pub fn child_with<Fn>(self, fun: Fn) -> Node<Tag, Content>
+    where
+        Fn: FnOnce(&mut ElementWriter),
+    {
+        self.close().child_with(fun)
+    }
+
+    pub fn children_keyed<It, KeyFn, K, Fn, T, R>(
+        self,
+        iter: It,
+        key_fn: KeyFn,
+        fun: Fn,
+    ) -> Node<Tag, Content>
+    where
+        It: IntoIterator<Item = T>,
+        KeyFn: FnMut(&T) -> K,
+        K: AsRef<str>,
+        Fn: FnMut(T) -> R,
+        R: Renderable,
+    {
+        self.close().children_keyed(iter, key_fn, fun)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<Tag> Node<Tag, Content>
+where
+    Tag: CanAddChildren,
+{
+    /// Like [`children`](Self::children) but renders the children on the rayon thread pool, in order.
+    ///
+    /// Every child is rendered into its own buffer with the active [`RenderContext`](context::RenderContext), so
+    /// this only pays off for large collections of children that are expensive to build.
+    pub fn children_par<It, F, R>(mut self, iter: It, fun: F) -> Self
+    where
+        It: IntoParallelIterator,
+        F: Fn(It::Item) -> R + Send + Sync,
+        R: Renderable,
+    {
+        let ctx = context::current();
+        let render = |item| {
+            let mut buf = Vec::with_capacity(node_capacity());
+            context::nested(|| render_child(fun(item), &mut buf));
+            buf
+        };
+
+        let parts: Vec<Vec<u8>> = iter
+            .into_par_iter()
+            .map(|item| match &ctx {
+                Some(ctx) => ctx.clone().enter(|| render(item)),
+                None => render(item),
+            })
+            .collect();
+
+        self.buf.reserve(parts.iter().map(Vec::len).sum());
+        for part in parts {
+            self.buf.extend_from_slice(&part);
+        }
+        self
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<Tag> Node<Tag, Open>
+where
+    Tag: CanAddChildren,
+{
+    pub fn children_par<It, F, R>(self, iter: It, fun: F) -> Node<Tag, Content>
+    where
+        It: IntoParallelIterator,
+        F: Fn(It::Item) -> R + Send + Sync,
+        R: Renderable,
+    {
+        self.close().children_par(iter, fun)
+    }
 }
 
 impl<Tag> Node<Tag, Open>
@@ -179,6 +487,10 @@ where
     pub fn raw(self, text: impl AsRef<str>) -> Node<Tag, Content> {
         self.close().raw(text.as_ref())
     }
+
+    pub fn text_fmt(self, args: fmt::Arguments) -> Node<Tag, Content> {
+        self.close().text_fmt(args)
+    }
 }
 
 impl<Tag> Node<Tag, Content>
@@ -186,7 +498,7 @@ where
     Tag: CanAddText,
 {
     pub fn text(mut self, text: impl AsRef<str>) -> Self {
-        write_escaped(&mut self.buf, text.as_ref());
+        escape_into(&mut self.buf, text.as_ref(), self.flags.escape);
         self
     }
 
@@ -194,141 +506,1253 @@ where
         self.buf.extend_from_slice(text.as_ref().as_bytes());
         self
     }
+
+    /// Escapes formatted text straight into the buffer: `td().text_fmt(format_args!("{}x{}", row, col))`.
+    pub fn text_fmt(mut self, args: fmt::Arguments) -> Self {
+        escape_fmt_into(&mut self.buf, args, self.flags.escape);
+        self
+    }
 }
 
 impl<Tag> Node<Tag, Void> {
     pub fn new_self_closing(tag: &'static str) -> Self {
-        let mut buf = Vec::with_capacity(DEFAULT_BUFFER_CAPACITY);
+        let buf = pool::buffer(node_capacity());
+        Self::in_buffer_self_closing(tag, buf)
+    }
+
+    /// Like [`new_self_closing`](Self::new_self_closing) but starts with a smaller buffer.
+    pub fn new_small_self_closing(tag: &'static str) -> Self {
+        let buf = pool::buffer(small_capacity());
+        Self::in_buffer_self_closing(tag, buf)
+    }
+
+    /// Starts the element at the end of `buf`, which has to hold valid UTF-8.
+    pub fn with_buffer_self_closing(tag: &'static str, buf: Vec<u8>) -> Self {
+        check_buffer(&buf);
+        Self::in_buffer_self_closing(tag, buf)
+    }
+
+    pub(crate) fn in_buffer_self_closing(tag: &'static str, mut buf: Vec<u8>) -> Self {
         buf.push(b'<');
         buf.extend_from_slice(tag.as_bytes());
 
         Node {
             tag: tag.as_bytes(),
             buf,
+            flags: NodeFlags::current(),
+            descendants: 0,
             _state: PhantomData,
             _tag: PhantomData,
         }
+        .with_inlined_style()
     }
 }
 
-pub trait Renderable {
-    fn render_into(self, buf: &mut Vec<u8>);
-
-    fn render(self) -> String;
-}
+impl<Tag, State> Node<Tag, State>
+where
+    State: CanAddAttributes,
+{
+    // numbers never need escaping, so they can be formatted straight into the buffer
+    fn attr_display(mut self, k: &str, v: impl fmt::Display) -> Self {
+        use std::io::Write;
 
-impl<Tag> Renderable for Node<Tag, Open> {
-    fn render_into(self, buf: &mut Vec<u8>) {
-        self.close().render_into(buf);
+        self.buf.reserve(k.len() + 24);
+        if !write_name(&mut self.buf, k, self.flags.validate_attribute_names) {
+            return self;
+        }
+        self.buf.extend_from_slice(b"=\"");
+        let _ = write!(self.buf, "{}", v);
+        self.buf.push(b'"');
+        self
     }
 
-    fn render(self) -> String {
-        self.close().render()
+    fn with_inlined_style(self) -> Self {
+        if !self.flags.inline_styles {
+            return self;
+        }
+
+        let tag = std::str::from_utf8(self.tag).expect("Internal Error: Invalid UTF-8");
+        match context::current_inline_style(tag) {
+            Some(style) => self.attr("style", style),
+            None => self,
+        }
     }
 }
 
-impl<Tag> Renderable for Node<Tag, Content> {
-    fn render_into(self, buf: &mut Vec<u8>) {
-        buf.extend_from_slice(&self.buf);
+/// Elements that can be built directly in the buffer of their parent, see [`ElementWriter`].
+pub trait Element: Sized {
+    type State;
 
-        // close tag
-        buf.extend_from_slice(b"</");
-        buf.extend_from_slice(self.tag);
-        buf.push(b'>');
+    /// Starts the element at the end of `buf`.
+    fn new_in(buf: Vec<u8>) -> Node<Self, Self::State>;
+}
+
+/// Nodes that can be finished in place, handing back the buffer they were written to.
+///
+/// Only the nodes of this crate implement it, the buffer they return is what keeps rendered output valid UTF-8.
+pub trait IntoBuffer: sealed::Sealed {
+    fn into_buffer(self) -> Vec<u8>;
+}
+
+impl<Tag, State> sealed::Sealed for Node<Tag, State> {}
+
+impl<Tag> IntoBuffer for Node<Tag, Open> {
+    fn into_buffer(self) -> Vec<u8> {
+        self.close().into_buffer()
     }
+}
 
-    fn render(mut self) -> String {
-        // close tag
+impl<Tag> IntoBuffer for Node<Tag, Content> {
+    fn into_buffer(mut self) -> Vec<u8> {
         self.buf.extend_from_slice(b"</");
         self.buf.extend_from_slice(self.tag);
         self.buf.push(b'>');
+        self.buf
+    }
+}
 
-        String::from_utf8(self.buf).expect("Internal Error: Invalid UTF-8")
+impl<Tag> IntoBuffer for Node<Tag, Void> {
+    fn into_buffer(mut self) -> Vec<u8> {
+        self.buf.extend_from_slice(void_end());
+        self.buf
     }
 }
 
-impl<Tag> Renderable for Node<Tag, Void> {
-    fn render_into(self, buf: &mut Vec<u8>) {
-        buf.extend_from_slice(&self.buf);
-        buf.extend_from_slice(b" />");
+impl<Tag> Node<Tag, Content> {
+    // in strict mode children that browsers would move out of this element are a bug, see `RenderMode::Strict`
+    fn push_child(&mut self, child: impl Renderable) {
+        if !self.flags.strict {
+            render_child(child, &mut self.buf);
+            return;
+        }
+
+        let start = self.buf.len();
+        let tags = child.nested_tags(sealed::Trusted);
+        render_child(child, &mut self.buf);
+        // elements know what they contain, anything else is scanned once here
+        let tags = tags.unwrap_or_else(|| scan_tags(&self.buf[start..]));
+        check_nesting(self.tag, tags);
+        self.descendants |= tags;
     }
 
-    fn render(mut self) -> String {
-        self.buf.extend_from_slice(b" />");
+    /// Length of the rendered element in bytes, including the end tag that's only added when it's rendered.
+    pub fn len(&self) -> usize {
+        self.buf.len() + self.tag.len() + 3
+    }
+
+    /// Always `false`, an element renders at least its tags.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
 
-        String::from_utf8(self.buf).expect("Internal Error: Invalid UTF-8")
+    /// The markup written so far, which is everything but the end tag.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// The rendered element, without the post-processing `render()` applies for the render context.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_buffer()
     }
 }
 
-impl<Tag> Into<String> for Node<Tag, Open> {
-    fn into(self) -> String {
-        self.render()
+impl<Tag> Node<Tag, Void> {
+    /// Length of the rendered element in bytes, including the end of the tag that's only added when it's
+    /// rendered.
+    pub fn len(&self) -> usize {
+        self.buf.len() + void_end().len()
+    }
+
+    /// Always `false`, an element renders at least its tag.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The markup written so far, which is everything but the `/>` or `>` closing the tag.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// The rendered element, without the post-processing `render()` applies for the render context.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_buffer()
     }
 }
 
-impl<Tag> Into<String> for Node<Tag, Content> {
-    fn into(self) -> String {
-        self.render()
+// finished nodes compare by the markup they render, so tests and caches don't need to convert them to strings
+
+/// Whether `other` is `buf` followed by the parts of `end`.
+fn eq_rendered(buf: &[u8], end: &[&[u8]], other: &[u8]) -> bool {
+    let Some(mut rest) = other.strip_prefix(buf) else {
+        return false;
+    };
+    for part in end {
+        match rest.strip_prefix(*part) {
+            Some(remaining) => rest = remaining,
+            None => return false,
+        }
     }
+    rest.is_empty()
 }
 
-impl<Tag> Into<String> for Node<Tag, Void> {
-    fn into(self) -> String {
-        self.render()
+impl<A, B> PartialEq<Node<B, Content>> for Node<A, Content> {
+    fn eq(&self, other: &Node<B, Content>) -> bool {
+        self.tag == other.tag && self.buf == other.buf
     }
 }
 
-#[inline(always)]
-fn write_normalized(dest: &mut Vec<u8>, k: &str) {
-    let bytes = k.as_bytes();
+impl<Tag> Eq for Node<Tag, Content> {}
 
-    if !bytes.iter().any(|&b| b == b'_' || (b >= b'A' && b <= b'Z')) {
-        dest.extend_from_slice(bytes);
-        return;
+impl<Tag> PartialEq<str> for Node<Tag, Content> {
+    fn eq(&self, other: &str) -> bool {
+        eq_rendered(&self.buf, &[b"</", self.tag, b">"], other.as_bytes())
+    }
+}
+
+impl<Tag> PartialEq<&str> for Node<Tag, Content> {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
     }
+}
 
-    dest.reserve(bytes.len());
+impl<Tag> fmt::Debug for Node<Tag, Content> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Node").field(&self.to_string()).finish()
+    }
+}
 
-    for &b in bytes {
-        dest.push(match b {
-            b'A'..=b'Z' => b + 32,
-            b'_' => b'-',
-            _ => b,
-        });
+/// Writes the rendered element, without the post-processing `render()` applies for the render context.
+impl<Tag> fmt::Display for Node<Tag, Content> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.buf))?;
+        f.write_str("</")?;
+        f.write_str(&String::from_utf8_lossy(self.tag))?;
+        f.write_str(">")
     }
 }
 
-#[inline(always)]
-pub fn write_escaped(dest: &mut Vec<u8>, src: &str) {
-    let bytes = src.as_bytes();
-    let len = bytes.len();
+impl<A, B> PartialEq<Node<B, Void>> for Node<A, Void> {
+    fn eq(&self, other: &Node<B, Void>) -> bool {
+        self.buf == other.buf
+    }
+}
 
-    if !bytes
-        .iter()
-        .any(|&b| matches!(b, b'&' | b'<' | b'>' | b'"' | b'\''))
+impl<Tag> Eq for Node<Tag, Void> {}
+
+impl<Tag> PartialEq<str> for Node<Tag, Void> {
+    fn eq(&self, other: &str) -> bool {
+        eq_rendered(&self.buf, &[void_end()], other.as_bytes())
+    }
+}
+
+impl<Tag> PartialEq<&str> for Node<Tag, Void> {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl<Tag> fmt::Debug for Node<Tag, Void> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Node").field(&self.to_string()).finish()
+    }
+}
+
+/// Writes the rendered element, without the post-processing `render()` applies for the render context.
+impl<Tag> fmt::Display for Node<Tag, Void> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.buf))?;
+        f.write_str(&String::from_utf8_lossy(void_end()))
+    }
+}
+
+/// Writes children into the buffer of the parent passed to [`child_with`](Node::child_with).
+///
+/// Every HTML element has a method of the same name, `w.td(|td| td.text("..."))`, which takes over the shared buffer
+/// while the element is being built, so a tree built this way needs a single allocation no matter how deep it is.
+pub struct ElementWriter {
+    buf: Vec<u8>,
+}
+
+impl ElementWriter {
+    /// Builds an element of type `E` in place, `fun` gets the opened element and returns it when done.
+    ///
+    /// The element methods like [`td`](ElementWriter::td) are shorthands for this, `E` is inferred from the closure:
+    /// `w.element(|td: Node<Td, Open>| td.text("..."))`.
+    pub fn element<E, Fn, R>(&mut self, fun: Fn) -> &mut Self
+    where
+        E: Element,
+        Fn: FnOnce(Node<E, E::State>) -> R,
+        R: IntoBuffer,
     {
-        dest.extend_from_slice(bytes);
-        return;
+        let buf = std::mem::take(&mut self.buf);
+        self.buf = fun(E::new_in(buf)).into_buffer();
+        self
     }
 
-    dest.reserve(len / 10);
+    /// Adds an already built child.
+    pub fn child(&mut self, child: impl Renderable) -> &mut Self {
+        render_child(child, &mut self.buf);
+        self
+    }
+}
 
-    let mut start = 0;
-    for (i, &b) in bytes.iter().enumerate() {
-        let entity = match b {
-            b'&' => Some(&b"&amp;"[..]),
-            b'<' => Some(&b"&lt;"[..]),
-            b'>' => Some(&b"&gt;"[..]),
-            b'"' => Some(&b"&quot;"[..]),
-            b'\'' => Some(&b"&#39;"[..]),
-            _ => None,
-        };
+/// Sibling nodes without a wrapping element.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = ul().child(fragment().child(li().text("One")).child(li().text("Two"))).render();
+///
+/// assert_eq!("<ul><li>One</li><li>Two</li></ul>", res);
+/// ```
+pub struct Fragment {
+    buf: Vec<u8>,
+}
 
-        if let Some(ent) = entity {
-            dest.extend_from_slice(&bytes[start..i]);
-            dest.extend_from_slice(ent);
-            start = i + 1;
-        }
+/// Creates an empty [`Fragment`].
+pub fn fragment() -> Fragment {
+    Fragment {
+        buf: pool::buffer(node_capacity()),
+    }
+}
+
+impl Fragment {
+    pub fn child(mut self, child: impl Renderable) -> Self {
+        render_child(child, &mut self.buf);
+        self
+    }
+
+    pub fn children<It, Fn, T, R>(mut self, iter: It, mut fun: Fn) -> Self
+    where
+        It: IntoIterator<Item = T>,
+        Fn: FnMut(T) -> R,
+        R: Renderable,
+    {
+        for item in iter {
+            render_child(fun(item), &mut self.buf);
+        }
+        self
+    }
+
+    pub fn repeat_child<Fn, R>(self, n: usize, fun: Fn) -> Self
+    where
+        Fn: FnMut(usize) -> R,
+        R: Renderable,
+    {
+        self.child(repeat(n, fun))
+    }
+
+    pub fn text(mut self, text: impl AsRef<str>) -> Self {
+        write_escaped(&mut self.buf, text.as_ref());
+        self
+    }
+
+    pub fn raw(mut self, text: impl AsRef<str>) -> Self {
+        self.buf.extend_from_slice(text.as_ref().as_bytes());
+        self
+    }
+
+    /// Escapes formatted text straight into the buffer: `td().text_fmt(format_args!("{}x{}", row, col))`.
+    pub fn text_fmt(mut self, args: fmt::Arguments) -> Self {
+        write_escaped_fmt(&mut self.buf, args);
+        self
+    }
+
+    /// Length of the rendered fragment in bytes.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// The rendered fragment, always valid UTF-8.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// The rendered fragment, without the post-processing `render()` applies for the render context.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+impl PartialEq for Fragment {
+    fn eq(&self, other: &Fragment) -> bool {
+        self.buf == other.buf
+    }
+}
+
+impl Eq for Fragment {}
+
+impl PartialEq<str> for Fragment {
+    fn eq(&self, other: &str) -> bool {
+        self.buf == other.as_bytes()
+    }
+}
+
+impl PartialEq<&str> for Fragment {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl fmt::Debug for Fragment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Fragment").field(&self.to_string()).finish()
+    }
+}
+
+/// Writes the rendered fragment, without the post-processing `render()` applies for the render context.
+impl fmt::Display for Fragment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.buf))
+    }
+}
+
+pub trait Renderable {
+    fn render_into(self, buf: &mut Vec<u8>);
+
+    fn render(self) -> String;
+
+    // the types of this crate only ever write valid UTF-8, letting `finish` skip the check. Can't be overridden
+    // outside of the crate since `Trusted` can't be named there
+    #[doc(hidden)]
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        false
+    }
+
+    // the start tags of the output as bits of `NESTING_TAGS` for the strict nesting check, `None` if they have to be
+    // scanned from the output
+    #[doc(hidden)]
+    fn nested_tags(&self, _: sealed::Trusted) -> Option<u64> {
+        None
+    }
+}
+
+pub(crate) mod sealed {
+    pub struct Trusted;
+
+    pub trait Sealed {}
+}
+
+/// Renders into a [`fmt::Write`] sink, e.g. to build markup inside a `Display` impl or a template engine that only
+/// hands out a formatter.
+///
+/// ```
+/// use std::fmt;
+/// use lira::prelude::*;
+///
+/// struct Badge(u32);
+///
+/// impl fmt::Display for Badge {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         span().class("badge").text_fmt(format_args!("{}", self.0)).render_fmt(f)
+///     }
+/// }
+///
+/// assert_eq!("<span class=\"badge\">3</span>", Badge(3).to_string());
+/// ```
+pub trait RenderFmt: Renderable + Sized {
+    fn render_fmt<W>(self, out: &mut W) -> fmt::Result
+    where
+        W: fmt::Write + ?Sized,
+    {
+        // the markup usually ends up inside a larger page, which reports the stats
+        out.write_str(&context::nested(|| self.render()))
+    }
+}
+
+impl<R> RenderFmt for R where R: Renderable {}
+
+/// Renders `child` at the end of `buf`.
+///
+/// Output of renderables from outside of this crate is checked for valid UTF-8 before it's added, this is what
+/// keeps `buf` valid UTF-8 at all times.
+pub(crate) fn render_child(child: impl Renderable, buf: &mut Vec<u8>) {
+    if child.is_trusted(sealed::Trusted) {
+        child.render_into(buf);
+        return;
+    }
+
+    let mut scratch = pool::buffer(node_capacity());
+    child.render_into(&mut scratch);
+    std::str::from_utf8(&scratch).expect("Renderable wrote invalid UTF-8");
+    buf.extend_from_slice(&scratch);
+    pool::recycle(scratch);
+}
+
+impl<Tag> Renderable for Node<Tag, Open> {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        self.close().render_into(buf);
+    }
+
+    fn render(self) -> String {
+        self.close().render()
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+
+    fn nested_tags(&self, _: sealed::Trusted) -> Option<u64> {
+        Some(self.nested())
+    }
+}
+
+impl<Tag> Renderable for Node<Tag, Content> {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.buf);
+
+        // close tag
+        buf.extend_from_slice(b"</");
+        buf.extend_from_slice(self.tag);
+        buf.push(b'>');
+
+        pool::recycle(self.buf);
+    }
+
+    fn render(mut self) -> String {
+        // close tag
+        self.buf.extend_from_slice(b"</");
+        self.buf.extend_from_slice(self.tag);
+        self.buf.push(b'>');
+
+        finish(self.buf)
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+
+    fn nested_tags(&self, _: sealed::Trusted) -> Option<u64> {
+        Some(self.nested())
+    }
+}
+
+impl<Tag> Node<Tag, Void> {
+    /// Value of the attribute `name` written so far, for helpers finishing an element built by the caller.
+    pub(crate) fn written_attr(&self, name: &str) -> Option<String> {
+        let src = std::str::from_utf8(&self.buf).ok()?;
+        // the buffer can start with other markup, values are escaped so the last `<` starts this element
+        let tag = format!("{}>", &src[src.rfind('<')?..]);
+        match tokenizer::tokenize(&tag).into_iter().next()? {
+            (_, tokenizer::Token::StartTag { attrs, .. }) => attrs
+                .into_iter()
+                .find(|attr| attr.name == name)
+                .and_then(|attr| attr.value),
+            _ => None,
+        }
+    }
+}
+
+impl<Tag> Renderable for Node<Tag, Void> {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.buf);
+        buf.extend_from_slice(void_end());
+
+        pool::recycle(self.buf);
+    }
+
+    fn render(mut self) -> String {
+        self.buf.extend_from_slice(void_end());
+
+        finish(self.buf)
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+
+    fn nested_tags(&self, _: sealed::Trusted) -> Option<u64> {
+        Some(self.nested())
+    }
+}
+
+impl Renderable for Fragment {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.buf);
+        pool::recycle(self.buf);
+    }
+
+    fn render(self) -> String {
+        finish(self.buf)
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// A renderable that writes nothing, see [`empty`].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Empty;
+
+/// Renders nothing, for the branch of a conditional that has nothing to show.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let unread = 0;
+/// let badge = if unread > 0 {
+///     span().class("badge").text(unread.to_string()).into_any()
+/// } else {
+///     empty().into_any()
+/// };
+///
+/// assert_eq!("<a href=\"/inbox\">Inbox</a>", a().href("/inbox").text("Inbox").child(badge).render());
+/// ```
+pub fn empty() -> Empty {
+    Empty
+}
+
+impl Renderable for Empty {
+    fn render_into(self, _: &mut Vec<u8>) {}
+
+    fn render(self) -> String {
+        String::new()
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// One of two renderables, for branches that build different elements, see [`either`].
+#[derive(Clone, Copy, Debug)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+/// Calls `left` if `condition` holds and `right` otherwise, without copying the result like
+/// [`into_any`](IntoAny::into_any) does.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let logged_in = false;
+/// let res = nav()
+///     .child(either(
+///         logged_in,
+///         || a().href("/logout").text("Log out"),
+///         || button().text("Log in"),
+///     ))
+///     .render();
+///
+/// assert_eq!("<nav><button>Log in</button></nav>", res);
+/// ```
+pub fn either<A, B>(
+    condition: bool,
+    left: impl FnOnce() -> A,
+    right: impl FnOnce() -> B,
+) -> Either<A, B> {
+    if condition {
+        Either::Left(left())
+    } else {
+        Either::Right(right())
+    }
+}
+
+impl<A, B> Renderable for Either<A, B>
+where
+    A: Renderable,
+    B: Renderable,
+{
+    fn render_into(self, buf: &mut Vec<u8>) {
+        match self {
+            Either::Left(left) => render_child(left, buf),
+            Either::Right(right) => render_child(right, buf),
+        }
+    }
+
+    fn render(self) -> String {
+        match self {
+            Either::Left(left) => left.render(),
+            Either::Right(right) => right.render(),
+        }
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+
+    fn nested_tags(&self, trusted: sealed::Trusted) -> Option<u64> {
+        match self {
+            Either::Left(left) => left.nested_tags(trusted),
+            Either::Right(right) => right.nested_tags(trusted),
+        }
+    }
+}
+
+/// Renders a child `n` times, see [`repeat`].
+#[derive(Clone, Copy, Debug)]
+pub struct Repeat<F> {
+    n: usize,
+    fun: F,
+}
+
+/// Calls `fun` with the indices `0..n` and renders what it returns, for skeleton loaders, star ratings and grid
+/// placeholders.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let rating = 3;
+/// let res = div()
+///     .class("stars")
+///     .child(repeat(5, |i| span().class(if i < rating { "star on" } else { "star" })))
+///     .render();
+///
+/// assert_eq!(
+///     "<div class=\"stars\"><span class=\"star on\"></span><span class=\"star on\"></span>\
+///      <span class=\"star on\"></span><span class=\"star\"></span><span class=\"star\"></span></div>",
+///     res
+/// );
+/// ```
+pub fn repeat<F, R>(n: usize, fun: F) -> Repeat<F>
+where
+    F: FnMut(usize) -> R,
+    R: Renderable,
+{
+    Repeat { n, fun }
+}
+
+impl<F, R> Renderable for Repeat<F>
+where
+    F: FnMut(usize) -> R,
+    R: Renderable,
+{
+    fn render_into(mut self, buf: &mut Vec<u8>) {
+        for i in 0..self.n {
+            render_child((self.fun)(i), buf);
+        }
+    }
+
+    fn render(self) -> String {
+        let mut buf = pool::buffer(node_capacity());
+        self.render_into(&mut buf);
+        finish(buf)
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// Erases the type of a renderable, so branches rendering different elements can be returned from one expression.
+pub trait IntoAny: Renderable + Sized {
+    fn into_any(self) -> Fragment {
+        let mut buf = pool::buffer(node_capacity());
+        render_child(self, &mut buf);
+        Fragment { buf }
+    }
+}
+
+impl<R> IntoAny for R where R: Renderable {}
+
+/// Text escaped once when it's created, for strings shown many times on a page like the name of the author next
+/// to each of their comments. Inserting it copies the escaped bytes, clones share them.
+///
+/// It's escaped with the settings of the render context active at creation, e.g.
+/// [`escape_non_ascii`](crate::context::RenderContext::escape_non_ascii).
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let author = EscapedText::new("Tom & Jerry");
+/// let res = ul()
+///     .children(["First", "Second"], |comment| li().text(comment).child(&author))
+///     .render();
+///
+/// assert_eq!(
+///     "<ul><li>FirstTom &amp; Jerry</li><li>SecondTom &amp; Jerry</li></ul>",
+///     res
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscapedText {
+    html: Arc<str>,
+}
+
+impl EscapedText {
+    pub fn new(text: impl AsRef<str>) -> Self {
+        let mut buf = Vec::with_capacity(text.as_ref().len());
+        write_escaped(&mut buf, text.as_ref());
+        // SAFETY: escaping only replaces ASCII characters of a `&str` with ASCII entities
+        let html = unsafe { String::from_utf8_unchecked(buf) };
+        Self { html: html.into() }
+    }
+
+    /// The escaped text.
+    pub fn as_str(&self) -> &str {
+        &self.html
+    }
+}
+
+impl Renderable for &EscapedText {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.html.as_bytes());
+    }
+
+    fn render(self) -> String {
+        finish(self.html.as_bytes().to_vec())
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+}
+
+impl Renderable for EscapedText {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        (&self).render_into(buf);
+    }
+
+    fn render(self) -> String {
+        (&self).render()
+    }
+
+    fn is_trusted(&self, _: sealed::Trusted) -> bool {
+        true
+    }
+}
+
+// buffers handed in from outside are checked once, everything the crate writes to them afterwards is valid UTF-8
+fn check_buffer(buf: &[u8]) {
+    if let Err(err) = std::str::from_utf8(buf) {
+        panic!("buffer handed to a node isn't valid UTF-8: {}", err);
+    }
+}
+
+/// Turns a finished buffer into the rendered string, reporting stats if the render context asks for them.
+///
+/// `buf` has to be built by this crate, which only writes `&str`s and ASCII, checks buffers passed to
+/// [`Node::with_buffer`] and checks the output of other renderables in [`render_child`].
+pub(crate) fn finish(buf: Vec<u8>) -> String {
+    debug_assert!(std::str::from_utf8(&buf).is_ok());
+    // SAFETY: see above, re-validating would scan the whole page again
+    let html = unsafe { String::from_utf8_unchecked(buf) };
+    let flags = context::flags();
+    let html = if flags.sorted_attributes {
+        crate::tree::sort_attributes(&html)
+    } else {
+        html
+    };
+    let html = if flags.minify {
+        crate::minify::minify(&html)
+    } else {
+        html
+    };
+    if flags.render_stats {
+        context::report_stats(&html);
+    }
+    html
+}
+
+impl<Tag> From<Node<Tag, Open>> for String {
+    fn from(node: Node<Tag, Open>) -> Self {
+        node.render()
+    }
+}
+
+impl<Tag> From<Node<Tag, Content>> for String {
+    fn from(node: Node<Tag, Content>) -> Self {
+        node.render()
+    }
+}
+
+impl<Tag> From<Node<Tag, Void>> for String {
+    fn from(node: Node<Tag, Void>) -> Self {
+        node.render()
+    }
+}
+
+impl From<Fragment> for String {
+    fn from(fragment: Fragment) -> Self {
+        fragment.render()
+    }
+}
+
+/// End of a void element's start tag according to the render context.
+pub(crate) fn void_end() -> &'static [u8] {
+    match context::flags().void_style {
+        VoidStyle::SelfClosing => b" />",
+        VoidStyle::Html5 => b">",
+    }
+}
+
+// what unsafe URLs are replaced with in strict mode, a URL that does nothing
+const BLOCKED_URL: &str = "about:invalid";
+
+// URLs that can't run script when followed or loaded: no `javascript:` or `vbscript:`, and `data:` only for
+// images other than SVG, fonts and media
+fn is_safe_url(url: &str) -> bool {
+    // browsers ignore leading whitespace and controls, and tabs and newlines anywhere
+    let url: String = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .take(32)
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    let Some(colon) = url.find(':') else {
+        return true;
+    };
+    // a `/`, `?` or `#` before the colon makes it a relative URL
+    if url[..colon].contains(['/', '?', '#']) {
+        return true;
+    }
+
+    match &url[..colon] {
+        "javascript" | "vbscript" => false,
+        "data" => {
+            let media = &url[colon + 1..];
+            ["image/", "font/", "audio/", "video/"]
+                .iter()
+                .any(|prefix| media.starts_with(prefix))
+                && !media.starts_with("image/svg")
+        }
+        _ => true,
+    }
+}
+
+// elements that browsers move out of `parent` when parsing, so the page wouldn't have the structure it was built
+// with: each of them ends a `<p>`, links and forms can't be nested and buttons can't hold other controls
+fn is_misnested(parent: &str, child: &str) -> bool {
+    const ENDS_P: &[&str] = &[
+        "address",
+        "article",
+        "aside",
+        "blockquote",
+        "details",
+        "dialog",
+        "div",
+        "dl",
+        "fieldset",
+        "figcaption",
+        "figure",
+        "footer",
+        "form",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "header",
+        "hgroup",
+        "hr",
+        "main",
+        "menu",
+        "nav",
+        "ol",
+        "p",
+        "pre",
+        "section",
+        "table",
+        "ul",
+    ];
+    const INTERACTIVE: &[&str] = &[
+        "a", "button", "details", "embed", "iframe", "input", "label", "select", "textarea",
+    ];
+
+    match parent {
+        "p" => ENDS_P.contains(&child),
+        "a" => child == "a",
+        "form" => child == "form",
+        "button" => INTERACTIVE.contains(&child),
+        _ => false,
+    }
+}
+
+// the elements `is_misnested` checks for, strict mode tracks which of them an element contains as bits of a `u64`
+const NESTING_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+    "a",
+    "button",
+    "embed",
+    "iframe",
+    "input",
+    "label",
+    "select",
+    "textarea",
+];
+
+fn tag_bit(name: &str) -> u64 {
+    NESTING_TAGS
+        .iter()
+        .position(|tag| *tag == name)
+        .map_or(0, |i| 1 << i)
+}
+
+// the tags of markup that isn't an element of this crate, like fragments and `raw` strings
+fn scan_tags(html: &[u8]) -> u64 {
+    let Ok(html) = std::str::from_utf8(html) else {
+        return 0;
+    };
+    tokenizer::tokenize(html)
+        .into_iter()
+        .fold(0, |tags, (_, token)| match token {
+            tokenizer::Token::StartTag { name, .. } => tags | tag_bit(&name),
+            _ => tags,
+        })
+}
+
+// panics for descendants of `parent` that the parser would move out of it
+fn check_nesting(parent: &[u8], tags: u64) {
+    let parent = std::str::from_utf8(parent).unwrap_or_default();
+    if tags == 0 || !matches!(parent, "p" | "a" | "form" | "button") {
+        return;
+    }
+
+    for (i, tag) in NESTING_TAGS.iter().enumerate() {
+        assert!(
+            tags & (1 << i) == 0 || !is_misnested(parent, tag),
+            "<{}> can't contain <{}>, browsers would move it out of the element",
+            parent,
+            tag
+        );
+    }
+}
+
+fn is_url_attribute(k: &str) -> bool {
+    [
+        "href",
+        "src",
+        "srcset",
+        "action",
+        "formaction",
+        "poster",
+        "cite",
+    ]
+    .iter()
+    .any(|name| k.eq_ignore_ascii_case(name))
+}
+
+// prefixes root-relative URLs with the base path of the render context, for srcset every candidate
+fn with_base_path<'a>(k: &str, v: &'a str) -> Cow<'a, str> {
+    let Some(base) = context::current_base_path() else {
+        return Cow::Borrowed(v);
+    };
+    let is_root_relative = |url: &str| url.starts_with('/') && !url.starts_with("//");
+
+    if !k.eq_ignore_ascii_case("srcset") {
+        return if is_root_relative(v) {
+            Cow::Owned(format!("{}{}", base, v))
+        } else {
+            Cow::Borrowed(v)
+        };
+    }
+
+    let candidates: Vec<String> = v
+        .split(',')
+        .map(|candidate| {
+            let trimmed = candidate.trim_start();
+            if is_root_relative(trimmed) {
+                format!("{}{}", base, trimmed)
+            } else {
+                trimmed.to_string()
+            }
+        })
+        .collect();
+    Cow::Owned(candidates.join(", "))
+}
+
+/// Inserts an attribute into the first start tag found at or after `from`.
+fn insert_attr(buf: &mut Vec<u8>, from: usize, k: &str, v: &str) {
+    let Some(tag_start) = buf[from..]
+        .windows(2)
+        .position(|w| w[0] == b'<' && w[1].is_ascii_alphabetic())
+    else {
+        return;
+    };
+
+    let name_start = from + tag_start + 1;
+    let name_end = buf[name_start..]
+        .iter()
+        .position(|&b| b.is_ascii_whitespace() || b == b'>' || b == b'/')
+        .map_or(buf.len(), |i| name_start + i);
+
+    let mut attr = Vec::with_capacity(k.len() + v.len() + 4);
+    if !write_name(&mut attr, k, context::flags().validate_attribute_names) {
+        return;
+    }
+    attr.extend_from_slice(b"=\"");
+    write_escaped(&mut attr, v);
+    attr.push(b'"');
+
+    buf.splice(name_end..name_end, attr);
+}
+
+/// Writes a space and the attribute name, lowercased and with `_` as `-`. Unless the context turned validation
+/// off, characters that would end the name early, like spaces, quotes, `=` and `>`, are dropped and `false` is
+/// returned for a name with nothing left, which the caller skips.
+#[inline(always)]
+fn write_name(dest: &mut Vec<u8>, k: &str, validate: bool) -> bool {
+    let bytes = k.as_bytes();
+
+    let needs_changes = (validate && bytes.is_empty())
+        || bytes
+            .iter()
+            .any(|&b| b == b'_' || b.is_ascii_uppercase() || (validate && !is_name_byte(b)));
+    if !needs_changes {
+        dest.push(b' ');
+        dest.extend_from_slice(bytes);
+        return true;
+    }
+
+    let start = dest.len();
+    dest.reserve(bytes.len() + 1);
+    dest.push(b' ');
+
+    for &b in bytes {
+        if validate && !is_name_byte(b) {
+            continue;
+        }
+        dest.push(match b {
+            b'A'..=b'Z' => b + 32,
+            b'_' => b'-',
+            _ => b,
+        });
+    }
+
+    if dest.len() == start + 1 {
+        dest.truncate(start);
+        return false;
+    }
+    true
+}
+
+// everything but controls and the characters ending an attribute name, bytes of non-ASCII characters are fine
+#[inline(always)]
+pub(crate) fn is_name_byte(b: u8) -> bool {
+    b > b' ' && !matches!(b, b'"' | b'\'' | b'<' | b'>' | b'/' | b'=' | 0x7f)
+}
+
+#[inline(always)]
+pub fn write_escaped(dest: &mut Vec<u8>, src: &str) {
+    escape_into(dest, src, Escape::current());
+}
+
+// `write_escaped` with the settings of a node
+#[inline(always)]
+fn escape_into(dest: &mut Vec<u8>, src: &str, escape: Escape) {
+    if escape.non_ascii {
+        escape_ascii_into(dest, src, escape.numeric);
+        return;
+    }
+    let numeric = escape.numeric;
+
+    let bytes = src.as_bytes();
+    let len = bytes.len();
+
+    if !bytes
+        .iter()
+        .any(|&b| matches!(b, b'&' | b'<' | b'>' | b'"' | b'\''))
+    {
+        dest.extend_from_slice(bytes);
+        return;
+    }
+
+    dest.reserve(len / 10);
+
+    let mut start = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        if let Some(ent) = entity(b, numeric) {
+            dest.extend_from_slice(&bytes[start..i]);
+            dest.extend_from_slice(ent);
+            start = i + 1;
+        }
+    }
+    dest.extend_from_slice(&bytes[start..]);
+}
+
+/// Like [`write_escaped`] but for formatted text, without formatting it into a `String` first.
+pub fn write_escaped_fmt(dest: &mut Vec<u8>, args: fmt::Arguments) {
+    escape_fmt_into(dest, args, Escape::current());
+}
+
+fn escape_fmt_into(dest: &mut Vec<u8>, args: fmt::Arguments, escape: Escape) {
+    struct Escaping<'a>(&'a mut Vec<u8>, Escape);
+
+    impl fmt::Write for Escaping<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            escape_into(self.0, s, self.1);
+            Ok(())
+        }
+    }
+
+    match args.as_str() {
+        Some(s) => escape_into(dest, s, escape),
+        None => {
+            let _ = fmt::Write::write_fmt(&mut Escaping(dest, escape), args);
+        }
+    }
+}
+
+#[inline(always)]
+fn entity(b: u8, numeric: bool) -> Option<&'static [u8]> {
+    let (named, number): (&[u8], &[u8]) = match b {
+        b'&' => (b"&amp;", b"&#38;"),
+        b'<' => (b"&lt;", b"&#60;"),
+        b'>' => (b"&gt;", b"&#62;"),
+        b'"' => (b"&quot;", b"&#34;"),
+        b'\'' => (b"&#39;", b"&#39;"),
+        _ => return None,
+    };
+    Some(if numeric { number } else { named })
+}
+
+/// Like [`write_escaped`] but also writes every non-ASCII character as a numeric character reference.
+pub fn write_escaped_ascii(dest: &mut Vec<u8>, src: &str) {
+    escape_ascii_into(dest, src, Escape::current().numeric);
+}
+
+fn escape_ascii_into(dest: &mut Vec<u8>, src: &str, numeric: bool) {
+    use std::io::Write;
+
+    for c in src.chars() {
+        match c {
+            c if c.is_ascii() => match entity(c as u8, numeric) {
+                Some(ent) => dest.extend_from_slice(ent),
+                None => dest.push(c as u8),
+            },
+            c => {
+                let _ = write!(dest, "&#{};", c as u32);
+            }
+        }
     }
-    dest.extend_from_slice(&bytes[start..]);
 }
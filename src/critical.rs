@@ -0,0 +1,292 @@
+use crate::tree::Tree;
+
+/// Picks the rules of a stylesheet that the rendered page needs for its first paint, see
+/// [`Document::critical_css`](crate::document::Document::critical_css).
+///
+/// Closures taking the CSS and the rendered body are extractors too, e.g. to call out to a dedicated tool.
+pub trait CriticalCss: Send + Sync {
+    /// The part of `css` to inline for a page with the body `html`.
+    fn extract(&self, css: &str, html: &str) -> String;
+}
+
+impl<F> CriticalCss for F
+where
+    F: Fn(&str, &str) -> String + Send + Sync,
+{
+    fn extract(&self, css: &str, html: &str) -> String {
+        self(css, html)
+    }
+}
+
+/// Keeps the rules with a selector whose classes, ids and tag names all appear in the markup.
+///
+/// The structure of selectors isn't checked, `.nav a` is kept for any page with a `nav` class and a link, and so are
+/// `@font-face` and other at-rules without selectors. `@media`, `@supports` and `@layer` blocks are filtered like
+/// the stylesheet itself.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let css = ".card{padding:1rem} .modal{display:none} @media (min-width:40rem){.card{padding:2rem}}";
+///
+/// assert_eq!(
+///     ".card{padding:1rem}@media (min-width:40rem){.card{padding:2rem}}",
+///     UsedRules.extract(css, "<div class=\"card\"></div>")
+/// );
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct UsedRules;
+
+impl CriticalCss for UsedRules {
+    fn extract(&self, css: &str, html: &str) -> String {
+        let used = Used::of(html);
+        let mut out = String::with_capacity(css.len() / 4);
+        filter_rules(&strip_comments(css), &used, &mut out);
+        out
+    }
+}
+
+// classes, ids and tag names of a page
+#[derive(Default)]
+struct Used {
+    classes: Vec<String>,
+    ids: Vec<String>,
+    tags: Vec<String>,
+}
+
+impl Used {
+    fn of(html: &str) -> Self {
+        let mut used = Used::default();
+        Tree::parse(html).walk(|element| {
+            push_unique(&mut used.tags, &element.name);
+            if let Some(id) = element.attr("id") {
+                push_unique(&mut used.ids, id);
+            }
+            for class in element
+                .attr("class")
+                .unwrap_or_default()
+                .split_ascii_whitespace()
+            {
+                push_unique(&mut used.classes, class);
+            }
+        });
+        used
+    }
+
+    fn matches(&self, selector: &str) -> bool {
+        let required = requirements(selector);
+        required
+            .classes
+            .iter()
+            .all(|class| self.classes.contains(class))
+            && required.ids.iter().all(|id| self.ids.contains(id))
+            && required.tags.iter().all(|tag| {
+                // the tree always has these, even if the body didn't write them
+                matches!(tag.as_str(), "html" | "body" | "*") || self.tags.contains(tag)
+            })
+    }
+}
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|existing| existing == value) {
+        list.push(value.to_string());
+    }
+}
+
+fn filter_rules(css: &str, used: &Used, out: &mut String) {
+    let mut rest = css;
+    loop {
+        let trimmed = rest.trim_start();
+        if trimmed.is_empty() {
+            return;
+        }
+
+        let Some(open) = find_top_level(trimmed, '{') else {
+            return;
+        };
+        // statements like `@import url(a.css);` before the next block
+        if let Some(end) = find_top_level(&trimmed[..open], ';') {
+            out.push_str(trimmed[..=end].trim());
+            rest = &trimmed[end + 1..];
+            continue;
+        }
+
+        let prelude = trimmed[..open].trim();
+        let close = matching_brace(trimmed, open);
+        let block = &trimmed[open + 1..close];
+        rest = trimmed.get(close + 1..).unwrap_or_default();
+
+        if ["@media", "@supports", "@layer", "@container"]
+            .iter()
+            .any(|at| prelude.starts_with(at))
+        {
+            let mut inner = String::new();
+            filter_rules(block, used, &mut inner);
+            if !inner.is_empty() {
+                out.push_str(prelude);
+                out.push('{');
+                out.push_str(&inner);
+                out.push('}');
+            }
+        } else if prelude.starts_with('@') {
+            out.push_str(prelude);
+            out.push('{');
+            out.push_str(block.trim());
+            out.push('}');
+        } else {
+            let selectors: Vec<&str> = split_top_level(prelude, ',')
+                .into_iter()
+                .map(str::trim)
+                .filter(|selector| used.matches(selector))
+                .collect();
+            if !selectors.is_empty() {
+                out.push_str(&selectors.join(","));
+                out.push('{');
+                out.push_str(block.trim());
+                out.push('}');
+            }
+        }
+    }
+}
+
+// what a selector needs to be able to match, requirements within `:not(...)` and friends are ignored
+fn requirements(selector: &str) -> Used {
+    let mut required = Used::default();
+    let chars: Vec<char> = selector.chars().collect();
+    let mut i = 0;
+    let mut depth = 0;
+    // a tag name is only possible at the start of a compound selector
+    let mut compound_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            _ if depth > 0 => {}
+            '.' | '#' => {
+                let (name, end) = read_name(&chars, i + 1);
+                if !name.is_empty() {
+                    let list = if c == '.' {
+                        &mut required.classes
+                    } else {
+                        &mut required.ids
+                    };
+                    list.push(name);
+                }
+                i = end;
+                compound_start = false;
+                continue;
+            }
+            ':' => {
+                // the name of a pseudo class or element
+                let start = if chars.get(i + 1) == Some(&':') {
+                    i + 2
+                } else {
+                    i + 1
+                };
+                let (_, end) = read_name(&chars, start);
+                i = end;
+                compound_start = false;
+                continue;
+            }
+            ' ' | '>' | '+' | '~' => compound_start = true,
+            '*' => compound_start = false,
+            c if compound_start && (c.is_alphabetic() || c == '-' || c == '_') => {
+                let (name, end) = read_name(&chars, i);
+                required.tags.push(name.to_ascii_lowercase());
+                i = end;
+                compound_start = false;
+                continue;
+            }
+            _ => compound_start = false,
+        }
+        i += 1;
+    }
+
+    required
+}
+
+// an identifier starting at `start`, resolving escapes like `md\:p-4`
+fn read_name(chars: &[char], start: usize) -> (String, usize) {
+    let mut name = String::new();
+    let mut i = start;
+    while let Some(&c) = chars.get(i) {
+        if c == '\\' {
+            if let Some(&escaped) = chars.get(i + 1) {
+                name.push(escaped);
+            }
+            i += 2;
+        } else if c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+            name.push(c);
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    (name, i)
+}
+
+fn find_top_level(css: &str, needle: char) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut quote = None;
+    for (i, c) in css.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            (None, c) if c == needle && depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn split_top_level(css: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = css;
+    while let Some(i) = find_top_level(rest, separator) {
+        parts.push(&rest[..i]);
+        rest = &rest[i + 1..];
+    }
+    parts.push(rest);
+    parts
+}
+
+// position of the `}` closing the block opened at `open`, or the end of the stylesheet
+fn matching_brace(css: &str, open: usize) -> usize {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in css[open..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{') => depth += 1,
+            (None, '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + i;
+                }
+            }
+            _ => {}
+        }
+    }
+    css.len()
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,303 @@
+use crate::core::{self, Renderable};
+
+/// Fails compilation when a `.class("...")` literal in the annotated item uses a class that none of the
+/// stylesheets define, catching typos before they reach production.
+///
+/// Paths are relative to the crate's `Cargo.toml`. Classes that are only used by scripts or generated at runtime
+/// can be allowed by name, a trailing `*` allows a prefix. Only string literals are checked, classes built at
+/// runtime are left alone.
+///
+/// ```
+/// use lira::css::check_classes;
+/// use lira::prelude::*;
+///
+/// #[check_classes("tests/fixtures/app.css", allow = ["js-*"])]
+/// fn card(title: &str) -> impl Renderable {
+///     div()
+///         .class("card card--wide js-collapsible")
+///         .child(h2().class("card__title").text(title))
+/// }
+///
+/// assert_eq!(
+///     "<div class=\"card card--wide js-collapsible\"><h2 class=\"card__title\">Tea</h2></div>",
+///     card("Tea").render()
+/// );
+/// ```
+///
+/// A typo like `.class("crad")` is reported at the literal as ``unknown class `crad` ``.
+#[cfg(feature = "derive")]
+pub use lira_derive::check_classes;
+
+/// CSS declarations for the `style` attribute, built with [`css`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InlineStyle {
+    buf: String,
+}
+
+/// Starts an empty list of declarations for [`style`](crate::html5::HasGlobalAttributes::style).
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = div()
+///     .style(css().display("flex").gap("1rem").prop("grid_area", "main"))
+///     .render();
+///
+/// assert_eq!("<div style=\"display:flex;gap:1rem;grid-area:main\"></div>", res);
+/// ```
+pub fn css() -> InlineStyle {
+    InlineStyle::default()
+}
+
+macro_rules! properties {
+    () => {
+        properties! {
+            display => "display", position => "position", top => "top", right => "right", bottom => "bottom",
+            left => "left", width => "width", height => "height", min_width => "min-width",
+            max_width => "max-width", min_height => "min-height", max_height => "max-height", margin => "margin",
+            padding => "padding", gap => "gap", flex => "flex", flex_direction => "flex-direction",
+            flex_wrap => "flex-wrap", justify_content => "justify-content", align_items => "align-items",
+            grid_template_columns => "grid-template-columns", color => "color", background => "background",
+            background_color => "background-color", border => "border", border_radius => "border-radius",
+            font_family => "font-family", font_size => "font-size", font_weight => "font-weight",
+            line_height => "line-height", text_align => "text-align", text_decoration => "text-decoration",
+            overflow => "overflow", opacity => "opacity", z_index => "z-index", cursor => "cursor",
+            view_transition_name => "view-transition-name",
+        }
+    };
+    ($($method:ident => $name:literal),* $(,)?) => {
+        $(
+            #[doc = concat!("Sets `", $name, "`.")]
+            pub fn $method(self, value: impl AsRef<str>) -> Self {
+                self.prop($name, value)
+            }
+        )*
+    };
+}
+
+impl InlineStyle {
+    /// Adds a declaration, underscores in the property name are turned into dashes.
+    pub fn prop(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        if !self.buf.is_empty() {
+            self.buf.push(';');
+        }
+        write_declaration(&mut self.buf, name.as_ref(), value.as_ref());
+        self
+    }
+
+    /// Adds a declaration only if `value` is `Some`.
+    pub fn prop_opt(self, name: impl AsRef<str>, value: Option<impl AsRef<str>>) -> Self {
+        match value {
+            Some(value) => self.prop(name, value),
+            None => self,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    properties!();
+}
+
+impl AsRef<str> for InlineStyle {
+    fn as_ref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl std::fmt::Display for InlineStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+/// Collects `(property, value)` pairs.
+impl<K, V> FromIterator<(K, V)> for InlineStyle
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn from_iter<It: IntoIterator<Item = (K, V)>>(iter: It) -> Self {
+        iter.into_iter()
+            .fold(css(), |style, (name, value)| style.prop(name, value))
+    }
+}
+
+/// A style rule built with [`rule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    selector: String,
+    declarations: InlineStyle,
+}
+
+/// Starts a style rule for [`stylesheet`], the property methods are the same as for [`css`].
+pub fn rule(selector: impl AsRef<str>) -> Rule {
+    Rule {
+        selector: selector.as_ref().to_string(),
+        declarations: css(),
+    }
+}
+
+impl Rule {
+    /// Adds a declaration, underscores in the property name are turned into dashes.
+    pub fn prop(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.declarations = self.declarations.prop(name, value);
+        self
+    }
+
+    /// Adds all declarations of `style`.
+    pub fn declarations(mut self, style: InlineStyle) -> Self {
+        if !self.declarations.is_empty() && !style.is_empty() {
+            self.declarations.buf.push(';');
+        }
+        self.declarations.buf.push_str(&style.buf);
+        self
+    }
+
+    properties!();
+
+    fn write(&self, out: &mut String) {
+        write_value(out, self.selector.trim());
+        out.push('{');
+        out.push_str(&self.declarations.buf);
+        out.push('}');
+    }
+}
+
+/// CSS rules and media queries, rendered as the content of a `<style>` element.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let sheet = stylesheet()
+///     .rule(rule("body").margin("0").font_family("sans-serif"))
+///     .media(media("(max-width: 600px)").rule(rule(".column").width("100%")));
+///
+/// assert_eq!(
+///     "<style>body{margin:0;font-family:sans-serif}@media (max-width: 600px){.column{width:100%}}</style>",
+///     style().child(sheet).render()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stylesheet {
+    buf: String,
+}
+
+/// Starts an empty [`Stylesheet`].
+pub fn stylesheet() -> Stylesheet {
+    Stylesheet::default()
+}
+
+/// An `@media` block built with [`media`], can be nested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Media {
+    query: String,
+    body: Stylesheet,
+}
+
+/// Starts an `@media` block for the given media query.
+pub fn media(query: impl AsRef<str>) -> Media {
+    Media {
+        query: query.as_ref().to_string(),
+        body: stylesheet(),
+    }
+}
+
+impl Stylesheet {
+    pub fn rule(mut self, rule: Rule) -> Self {
+        rule.write(&mut self.buf);
+        self
+    }
+
+    pub fn rules(self, rules: impl IntoIterator<Item = Rule>) -> Self {
+        rules.into_iter().fold(self, Stylesheet::rule)
+    }
+
+    pub fn media(mut self, media: Media) -> Self {
+        media.write(&mut self.buf);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+impl Media {
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.body = self.body.rule(rule);
+        self
+    }
+
+    pub fn rules(mut self, rules: impl IntoIterator<Item = Rule>) -> Self {
+        self.body = self.body.rules(rules);
+        self
+    }
+
+    pub fn media(mut self, media: Media) -> Self {
+        self.body = self.body.media(media);
+        self
+    }
+
+    fn write(&self, out: &mut String) {
+        out.push_str("@media ");
+        write_value(out, self.query.trim());
+        out.push('{');
+        out.push_str(&self.body.buf);
+        out.push('}');
+    }
+}
+
+impl AsRef<str> for Stylesheet {
+    fn as_ref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl std::fmt::Display for Stylesheet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+impl Renderable for Stylesheet {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.buf.as_bytes());
+    }
+
+    fn render(self) -> String {
+        core::finish(self.buf.into_bytes())
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+fn write_declaration(out: &mut String, name: &str, value: &str) {
+    for c in name.trim().chars() {
+        match c {
+            '_' => out.push('-'),
+            c if c.is_ascii_alphanumeric() || c == '-' => out.push(c.to_ascii_lowercase()),
+            _ => {}
+        }
+    }
+    out.push(':');
+    write_value(out, value.trim());
+}
+
+// backslash escapes keep a value from ending its declaration or block early, `red;background:url(x)` stays a single
+// (invalid) value and `</style>` can't close the element it's in
+fn write_value(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            ';' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '<' => out.push_str("\\3c "),
+            c => out.push(c),
+        }
+    }
+}
@@ -0,0 +1,211 @@
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+use std::fmt::Write;
+
+/// A value that can be written in the HTML date and time microsyntax, as used by `datetime`, and `min`/`max`/`value`
+/// of date inputs.
+///
+/// Strings are passed through as is, date and time types of `chrono`, `time` and `jiff` are supported with their
+/// respective features.
+pub trait DateTimeValue {
+    fn to_datetime_string(&self) -> String;
+}
+
+impl DateTimeValue for str {
+    fn to_datetime_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl DateTimeValue for String {
+    fn to_datetime_string(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T> DateTimeValue for &T
+where
+    T: DateTimeValue + ?Sized,
+{
+    fn to_datetime_string(&self) -> String {
+        (**self).to_datetime_string()
+    }
+}
+
+// yyyy-mm-dd
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn write_date(out: &mut String, year: i32, month: u8, day: u8) {
+    let _ = write!(out, "{:04}-{:02}-{:02}", year, month, day);
+}
+
+// hh:mm:ss, with milliseconds if there are any
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn write_time(out: &mut String, hour: u8, minute: u8, second: u8, millisecond: u16) {
+    let _ = write!(out, "{:02}:{:02}:{:02}", hour, minute, second);
+    if millisecond > 0 {
+        let _ = write!(out, ".{:03}", millisecond);
+    }
+}
+
+// Z or +hh:mm
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn write_offset(out: &mut String, seconds: i32) {
+    if seconds == 0 {
+        out.push('Z');
+        return;
+    }
+
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.unsigned_abs() / 60;
+    let _ = write!(out, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60);
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+    use chrono::{Datelike, Offset, TimeZone, Timelike};
+
+    fn millis(nanos: u32) -> u16 {
+        // leap seconds are represented as nanoseconds >= 1_000_000_000
+        ((nanos % 1_000_000_000) / 1_000_000) as u16
+    }
+
+    impl DateTimeValue for chrono::NaiveDate {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::with_capacity(10);
+            write_date(&mut out, self.year(), self.month() as u8, self.day() as u8);
+            out
+        }
+    }
+
+    impl DateTimeValue for chrono::NaiveTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::with_capacity(12);
+            write_time(
+                &mut out,
+                self.hour() as u8,
+                self.minute() as u8,
+                self.second() as u8,
+                millis(self.nanosecond()),
+            );
+            out
+        }
+    }
+
+    impl DateTimeValue for chrono::NaiveDateTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.date().to_datetime_string();
+            out.push('T');
+            out.push_str(&self.time().to_datetime_string());
+            out
+        }
+    }
+
+    impl<Tz: TimeZone> DateTimeValue for chrono::DateTime<Tz> {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.naive_local().to_datetime_string();
+            write_offset(&mut out, self.offset().fix().local_minus_utc());
+            out
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::*;
+
+    impl DateTimeValue for time::Date {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::with_capacity(10);
+            write_date(&mut out, self.year(), self.month() as u8, self.day());
+            out
+        }
+    }
+
+    impl DateTimeValue for time::Time {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::with_capacity(12);
+            write_time(
+                &mut out,
+                self.hour(),
+                self.minute(),
+                self.second(),
+                self.millisecond(),
+            );
+            out
+        }
+    }
+
+    impl DateTimeValue for time::PrimitiveDateTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.date().to_datetime_string();
+            out.push('T');
+            out.push_str(&self.time().to_datetime_string());
+            out
+        }
+    }
+
+    impl DateTimeValue for time::OffsetDateTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.date().to_datetime_string();
+            out.push('T');
+            out.push_str(&self.time().to_datetime_string());
+            write_offset(&mut out, self.offset().whole_seconds());
+            out
+        }
+    }
+}
+
+#[cfg(feature = "jiff")]
+mod jiff_impls {
+    use super::*;
+
+    impl DateTimeValue for jiff::civil::Date {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::with_capacity(10);
+            write_date(
+                &mut out,
+                self.year() as i32,
+                self.month() as u8,
+                self.day() as u8,
+            );
+            out
+        }
+    }
+
+    impl DateTimeValue for jiff::civil::Time {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::with_capacity(12);
+            write_time(
+                &mut out,
+                self.hour() as u8,
+                self.minute() as u8,
+                self.second() as u8,
+                self.millisecond() as u16,
+            );
+            out
+        }
+    }
+
+    impl DateTimeValue for jiff::civil::DateTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.date().to_datetime_string();
+            out.push('T');
+            out.push_str(&self.time().to_datetime_string());
+            out
+        }
+    }
+
+    impl DateTimeValue for jiff::Timestamp {
+        fn to_datetime_string(&self) -> String {
+            self.to_zoned(jiff::tz::TimeZone::UTC).to_datetime_string()
+        }
+    }
+
+    impl DateTimeValue for jiff::Zoned {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.datetime().to_datetime_string();
+            write_offset(&mut out, self.offset().seconds());
+            out
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::context::RenderContext;
+use crate::core::{self, Content, Node, Renderable};
+use crate::css::{Stylesheet, rule, stylesheet};
+use crate::html5::{
+    HasGlobalAttributes, Table, caption, details, style, summary, table, td, th, tr,
+};
+
+/// Class of the panel's `<details>` element.
+pub const DEBUG_PANEL: &str = "lira-debug";
+
+/// A collapsible overlay in the corner of the page showing how it was rendered, built by [`debug_panel`].
+///
+/// It lists the settings of the render context, timings and whatever values the app adds, a development aid that
+/// saves adding `dbg!` to handlers. The panel brings its own `<style>`, which gets the CSP nonce of the active
+/// context like any other.
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use lira::prelude::*;
+///
+/// let started = Instant::now();
+/// let ctx = RenderContext::new().base_path("/admin");
+///
+/// let res = body()
+///     .child(h1().text("Users"))
+///     .child(
+///         debug_panel(&ctx)
+///             .started(started)
+///             .timing("db", Duration::from_millis(12))
+///             .value("user", "ada"),
+///     )
+///     .render();
+///
+/// assert!(res.contains("<tr><th>base_path</th><td>/admin</td></tr>"));
+/// assert!(res.contains("<tr><th>db</th><td>12.00ms</td></tr>"));
+/// ```
+pub struct DebugPanel {
+    context: Vec<(&'static str, String)>,
+    started: Option<Instant>,
+    timings: Vec<(String, Duration)>,
+    values: Vec<(String, String)>,
+    open: bool,
+}
+
+/// Starts a [`DebugPanel`] listing the settings of `ctx`, usually the context the page is rendered in.
+pub fn debug_panel(ctx: &RenderContext) -> DebugPanel {
+    DebugPanel {
+        context: ctx.debug_values(),
+        started: None,
+        timings: Vec::new(),
+        values: Vec::new(),
+        open: false,
+    }
+}
+
+impl DebugPanel {
+    /// When handling the request started, the time from there until the panel is rendered is shown as `total`.
+    pub fn started(mut self, at: Instant) -> Self {
+        self.started = Some(at);
+        self
+    }
+
+    /// A measured step, like a database query or a call to another service.
+    pub fn timing(mut self, label: impl AsRef<str>, duration: Duration) -> Self {
+        self.timings.push((label.as_ref().to_string(), duration));
+        self
+    }
+
+    /// Any value worth seeing while developing, shown in the order they were added.
+    pub fn value(mut self, key: impl AsRef<str>, value: impl fmt::Display) -> Self {
+        self.values
+            .push((key.as_ref().to_string(), value.to_string()));
+        self
+    }
+
+    /// Whether the panel starts expanded, collapsed by default.
+    pub fn open(mut self, value: bool) -> Self {
+        self.open = value;
+        self
+    }
+}
+
+impl Renderable for DebugPanel {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        let mut timings: Vec<(String, String)> = self
+            .timings
+            .into_iter()
+            .map(|(label, duration)| (label, format!("{:.2?}", duration)))
+            .collect();
+        if let Some(started) = self.started {
+            timings.push(("total".to_string(), format!("{:.2?}", started.elapsed())));
+        }
+
+        let mut panel = details().class(DEBUG_PANEL);
+        if self.open {
+            panel = panel.open();
+        }
+
+        panel
+            .child(summary().text("Debug"))
+            .child(style().child(styles()))
+            .child(section("Render context", self.context))
+            .child_when(!timings.is_empty(), || section("Timing", timings))
+            .child_when(!self.values.is_empty(), || section("Values", self.values))
+            .render_into(buf);
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+fn section<K, V>(title: &str, rows: Vec<(K, V)>) -> Node<Table, Content>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    table()
+        .child(caption().text(title))
+        .children(rows, |(key, value)| {
+            tr().child(th().text(key)).child(td().text(value))
+        })
+}
+
+fn styles() -> Stylesheet {
+    stylesheet()
+        .rule(
+            rule(".lira-debug")
+                .position("fixed")
+                .right("0.5rem")
+                .bottom("0.5rem")
+                .z_index("2147483647")
+                .max_width("min(32rem, calc(100vw - 1rem))")
+                .max_height("60vh")
+                .overflow("auto")
+                .padding("0.25rem 0.5rem")
+                .border_radius("0.25rem")
+                .font_family("ui-monospace, monospace")
+                .font_size("12px")
+                .line_height("1.4")
+                .color("#e6edf3")
+                .background("rgba(13, 17, 23, 0.92)"),
+        )
+        .rule(rule(".lira-debug summary").cursor("pointer"))
+        .rule(
+            rule(".lira-debug table")
+                .width("100%")
+                .margin("0.5rem 0")
+                .prop("border-collapse", "collapse"),
+        )
+        .rule(
+            rule(".lira-debug caption")
+                .text_align("left")
+                .font_weight("700"),
+        )
+        .rule(
+            rule(".lira-debug th, .lira-debug td")
+                .padding("0 0.5rem 0 0")
+                .text_align("left")
+                .font_weight("400")
+                .prop("vertical-align", "top"),
+        )
+        .rule(rule(".lira-debug th").opacity("0.7"))
+}
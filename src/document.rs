@@ -0,0 +1,387 @@
+use std::sync::{Arc, Mutex};
+
+use crate::context;
+use crate::core::{self, Content, Fragment, HasAttributes, Node, Renderable, fragment};
+use crate::critical::CriticalCss;
+use crate::html5::{
+    HasHref, HasRel, HasSrc, Html, Rel, body, head, html, link, meta, noscript, script, style,
+    title,
+};
+
+/// A complete page whose `<head>` is assembled after the body, built with [`document`].
+///
+/// Since elements are written as they are built, the head is usually finished before the components that need
+/// something in it are even called. Inside [`render`](Document::render) components can register what they need
+/// with [`require_stylesheet`], [`require_script`] and friends instead, which ends up in the head once the body is
+/// done. Requirements registered more than once are only written once.
+///
+/// The title, description, canonical URL and Open Graph tags are set by key instead, so a page can replace the
+/// defaults of its layout with [`set_title`] and friends without both ending up in the head:
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = document()
+///     .title("Shop")
+///     .description("Everything you need")
+///     .render(|| {
+///         set_title("Tea | Shop");
+///         set_og("og:title", "Tea");
+///         body().child(h1().text("Tea"))
+///     });
+///
+/// assert_eq!(
+///     "<!DOCTYPE html><html><head><title>Tea | Shop</title>\
+///      <meta name=\"description\" content=\"Everything you need\" />\
+///      <meta property=\"og:title\" content=\"Tea\" /></head><body><h1>Tea</h1></body></html>",
+///     res
+/// );
+/// ```
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// fn map_widget() -> impl Renderable {
+///     require_stylesheet("/css/map.css");
+///     require_script("/js/map.js");
+///     div().class("map")
+/// }
+///
+/// let res = document()
+///     .lang("en")
+///     .head(title().text("Stores"))
+///     .render(|| body().child(map_widget()).child(map_widget()));
+///
+/// assert_eq!(
+///     "<!DOCTYPE html><html lang=\"en\"><head><title>Stores</title>\
+///      <link rel=\"stylesheet\" href=\"/css/map.css\" /><script src=\"/js/map.js\" defer></script></head>\
+///      <body><div class=\"map\"></div><div class=\"map\"></div></body></html>",
+///     res
+/// );
+/// ```
+#[derive(Default)]
+pub struct Document {
+    lang: Option<String>,
+    head: Vec<u8>,
+    defaults: HeadRequirements,
+    // (href, content)
+    stylesheets: Vec<(String, String)>,
+    critical_css: Option<Box<dyn CriticalCss>>,
+}
+
+/// Starts an empty [`Document`].
+pub fn document() -> Document {
+    Document::default()
+}
+
+impl Document {
+    /// Language of the page, e.g. `"en"`.
+    pub fn lang(mut self, value: impl AsRef<str>) -> Self {
+        self.lang = Some(value.as_ref().to_string());
+        self
+    }
+
+    /// Adds content at the start of the head, before everything registered while rendering the body, e.g. the
+    /// charset and other defaults of the layout.
+    pub fn head(mut self, content: impl Renderable) -> Self {
+        core::render_child(content, &mut self.head);
+        self
+    }
+
+    /// Default `<title>`, replaced by [`set_title`].
+    pub fn title(mut self, text: impl AsRef<str>) -> Self {
+        self.defaults.set("title", title().text(text));
+        self
+    }
+
+    /// Default `<meta name="description">`, replaced by [`set_description`].
+    pub fn description(mut self, text: impl AsRef<str>) -> Self {
+        self.defaults.set("description", description_meta(text));
+        self
+    }
+
+    /// Default canonical URL, replaced by [`set_canonical`].
+    pub fn canonical(mut self, url: impl AsRef<str>) -> Self {
+        self.defaults.set("canonical", canonical_link(url));
+        self
+    }
+
+    /// Default Open Graph tag, e.g. `og("og:image", url)`, replaced by [`set_og`] with the same property.
+    pub fn og(mut self, property: impl AsRef<str>, content: impl AsRef<str>) -> Self {
+        let property = property.as_ref();
+        self.defaults.set(property, og_meta(property, content));
+        self
+    }
+
+    /// Stylesheet the page loads from `href`, `css` is its content for the [`critical_css`](Self::critical_css)
+    /// extractor. Without an extractor this is a plain `<link rel="stylesheet">`.
+    pub fn stylesheet(mut self, href: impl AsRef<str>, css: impl AsRef<str>) -> Self {
+        self.stylesheets
+            .push((href.as_ref().to_string(), css.as_ref().to_string()));
+        self
+    }
+
+    /// Inlines what `extractor` picks from the [`stylesheet`](Self::stylesheet)s for the rendered body into a
+    /// `<style>` in the head, and loads the stylesheets themselves without blocking the first paint.
+    ///
+    /// The stylesheets are loaded with `media="print"` and switched to all media once they are loaded, which needs
+    /// a Content Security Policy that allows inline event handlers. A `<noscript>` fallback loads them as usual.
+    ///
+    /// ```
+    /// use lira::prelude::*;
+    ///
+    /// let css = ".hero{height:80vh} .footer{color:gray}";
+    ///
+    /// let res = document()
+    ///     .stylesheet("/app.css", css)
+    ///     .critical_css(UsedRules)
+    ///     .render(|| body().child(div().class("hero")));
+    ///
+    /// assert_eq!(
+    ///     "<!DOCTYPE html><html><head><style>.hero{height:80vh}</style>\
+    ///      <link rel=\"stylesheet\" href=\"/app.css\" media=\"print\" onload=\"this.media=&#39;all&#39;\" />\
+    ///      <noscript><link rel=\"stylesheet\" href=\"/app.css\" /></noscript></head>\
+    ///      <body><div class=\"hero\"></div></body></html>",
+    ///     res
+    /// );
+    /// ```
+    pub fn critical_css(mut self, extractor: impl CriticalCss + 'static) -> Self {
+        self.critical_css = Some(Box::new(extractor));
+        self
+    }
+
+    /// Renders the whole document, `fun` builds the `<body>`.
+    ///
+    /// The body is built in a copy of the active [`RenderContext`](crate::context::RenderContext), so settings
+    /// like the nonce still apply.
+    pub fn render<Fn, R>(self, fun: Fn) -> String
+    where
+        Fn: FnOnce() -> R,
+        R: Renderable,
+    {
+        let requirements = Arc::new(Mutex::new(self.defaults));
+
+        let mut body = Vec::with_capacity(context::flags().buffer_capacities.page);
+        context::with_current(|ctx| ctx.cloned())
+            .unwrap_or_default()
+            .head_requirements(requirements.clone())
+            .enter(|| context::nested(|| core::render_child(fun(), &mut body)));
+
+        let mut head_buf = self.head;
+        let requirements = requirements.lock().unwrap_or_else(|err| err.into_inner());
+        for (_, part) in &requirements.keyed {
+            head_buf.extend_from_slice(part);
+        }
+        if !self.stylesheets.is_empty() {
+            let html = std::str::from_utf8(&body).expect("render_child writes valid UTF-8");
+            let links = stylesheet_links(&self.stylesheets, self.critical_css.as_deref(), html);
+            core::render_child(links, &mut head_buf);
+        }
+        for part in &requirements.parts {
+            head_buf.extend_from_slice(part);
+        }
+
+        html()
+            .map_when(self.lang.is_some(), |node| {
+                node.attr("lang", self.lang.as_deref().unwrap_or_default())
+            })
+            .child(head().child(Markup(head_buf)))
+            .child(Markup(body))
+            .render()
+    }
+}
+
+fn stylesheet_links(
+    stylesheets: &[(String, String)],
+    critical_css: Option<&dyn CriticalCss>,
+    html: &str,
+) -> Fragment {
+    let Some(extractor) = critical_css else {
+        return fragment().children(stylesheets, |(href, _)| {
+            link().rel(Rel::Stylesheet).href(href)
+        });
+    };
+
+    let critical: String = stylesheets
+        .iter()
+        .map(|(_, css)| extractor.extract(css, html))
+        .collect();
+
+    let inlined = if critical.is_empty() {
+        fragment()
+    } else {
+        fragment().child(style().raw(&critical))
+    };
+    inlined
+        .children(stylesheets, |(href, _)| {
+            link()
+                .rel(Rel::Stylesheet)
+                .href(href)
+                .attr("media", "print")
+                .attr("onload", "this.media='all'")
+        })
+        .child(noscript().children(stylesheets, |(href, _)| {
+            link().rel(Rel::Stylesheet).href(href)
+        }))
+}
+
+/// A whole page in one call, for small apps and admin pages that don't need a layout.
+///
+/// The head starts with the UTF-8 charset, a responsive viewport and the title, followed by `head_extra`. Use
+/// [`empty`](crate::core::empty) if there's nothing to add. `body_content` is placed inside the `<body>`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = page("Admin", empty(), h1().text("Users")).render();
+///
+/// assert_eq!(
+///     "<!DOCTYPE html><html><head><meta charset=\"utf-8\" />\
+///      <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />\
+///      <title>Admin</title></head><body><h1>Users</h1></body></html>",
+///     res
+/// );
+/// ```
+pub fn page(
+    title_text: impl AsRef<str>,
+    head_extra: impl Renderable,
+    body_content: impl Renderable,
+) -> Node<Html, Content> {
+    html()
+        .child(
+            head()
+                .child(meta().charset("utf-8"))
+                .child(
+                    meta()
+                        .name("viewport")
+                        .content("width=device-width, initial-scale=1"),
+                )
+                .child(title().text(title_text))
+                .child(head_extra),
+        )
+        .child(body().child(body_content))
+}
+
+/// What the components rendered in a [`Document`] registered for its head.
+#[derive(Default)]
+pub(crate) struct HeadRequirements {
+    // written first, in the order the keys were first set
+    keyed: Vec<(String, Vec<u8>)>,
+    parts: Vec<Vec<u8>>,
+}
+
+impl HeadRequirements {
+    fn push(&mut self, part: Vec<u8>) {
+        if !self.parts.contains(&part) {
+            self.parts.push(part);
+        }
+    }
+
+    fn set(&mut self, key: &str, content: impl Renderable) {
+        let mut part = Vec::new();
+        core::render_child(content, &mut part);
+
+        match self.keyed.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) => *existing = part,
+            None => self.keyed.push((key.to_string(), part)),
+        }
+    }
+}
+
+// already rendered markup, valid UTF-8 since it was written by `render_child`
+struct Markup(Vec<u8>);
+
+impl Renderable for Markup {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.0);
+    }
+
+    fn render(self) -> String {
+        core::finish(self.0)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// Adds `content` to the head of the [`Document`] being rendered, unless the same markup was added before.
+///
+/// Outside of a document there is no head to add to and `content` is dropped, e.g. when a component is rendered
+/// on its own as an htmx partial into a page that already loaded what it needs.
+pub fn require_head(content: impl Renderable) {
+    let Some(requirements) = context::current_head_requirements() else {
+        return;
+    };
+
+    let mut part = Vec::new();
+    core::render_child(content, &mut part);
+    requirements
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(part);
+}
+
+/// Adds a stylesheet to the head of the [`Document`] being rendered, see [`require_head`].
+pub fn require_stylesheet(href: impl AsRef<str>) {
+    require_head(link().rel(Rel::Stylesheet).href(href));
+}
+
+/// Adds a deferred script to the head of the [`Document`] being rendered, see [`require_head`].
+pub fn require_script(src: impl AsRef<str>) {
+    require_head(script().src(src).defer());
+}
+
+/// Adds an inline script to the head of the [`Document`] being rendered, see [`require_head`].
+pub fn require_inline_script(code: impl AsRef<str>) {
+    require_head(script().raw(code));
+}
+
+/// Adds a `<meta>` tag to the head of the [`Document`] being rendered, see [`require_head`].
+pub fn require_meta(name: impl AsRef<str>, content: impl AsRef<str>) {
+    require_head(meta().name(name).content(content));
+}
+
+/// Sets the head entry `key` of the [`Document`] being rendered to `content`, replacing what was set for the same
+/// key before, e.g. by the layout. Dropped outside of a document, like [`require_head`].
+pub fn set_head(key: impl AsRef<str>, content: impl Renderable) {
+    if let Some(requirements) = context::current_head_requirements() {
+        requirements
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .set(key.as_ref(), content);
+    }
+}
+
+/// Replaces the `<title>` of the [`Document`] being rendered.
+pub fn set_title(text: impl AsRef<str>) {
+    set_head("title", title().text(text));
+}
+
+/// Replaces the `<meta name="description">` of the [`Document`] being rendered.
+pub fn set_description(text: impl AsRef<str>) {
+    set_head("description", description_meta(text));
+}
+
+/// Replaces the canonical URL of the [`Document`] being rendered.
+pub fn set_canonical(url: impl AsRef<str>) {
+    set_head("canonical", canonical_link(url));
+}
+
+/// Replaces the Open Graph tag `property`, e.g. `"og:image"`, of the [`Document`] being rendered.
+pub fn set_og(property: impl AsRef<str>, content: impl AsRef<str>) {
+    let property = property.as_ref();
+    set_head(property, og_meta(property, content));
+}
+
+fn description_meta(text: impl AsRef<str>) -> impl Renderable {
+    meta().name("description").content(text)
+}
+
+fn canonical_link(url: impl AsRef<str>) -> impl Renderable {
+    link().rel(Rel::Canonical).href(url)
+}
+
+fn og_meta(property: &str, content: impl AsRef<str>) -> impl Renderable {
+    meta().attr("property", property).content(content)
+}
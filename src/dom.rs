@@ -0,0 +1,79 @@
+use wasm_bindgen::JsValue;
+use web_sys::{Document, DocumentFragment, Node};
+
+use crate::core::Renderable;
+use crate::tokenizer::{self, Token};
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Builds real DOM nodes from a lira tree, so the same view code can render to a string on the server and to the
+/// DOM in the browser.
+///
+/// Elements are created with `createElement`/`createElementNS`, attributes set with `setAttribute` and text added
+/// as text nodes, nothing goes through `innerHTML`. Names get the case an HTML parser would give them, so SVG
+/// elements and attributes like `linearGradient` and `viewBox` work although lira writes them in lowercase.
+pub fn render_dom(node: impl Renderable, document: &Document) -> Result<DocumentFragment, JsValue> {
+    let html = node.render();
+    let fragment = document.create_document_fragment();
+
+    // parent nodes and whether they are in the SVG namespace
+    let mut stack: Vec<(String, Node, bool)> = Vec::new();
+
+    for (_, token) in tokenizer::tokenize(&html) {
+        let (parent, in_svg) = match stack.last() {
+            Some((_, parent, in_svg)) => (parent.clone(), *in_svg),
+            None => (fragment.clone().into(), false),
+        };
+
+        match token {
+            // the document already has a doctype
+            Token::Doctype(_) => {}
+            Token::StartTag {
+                name,
+                attrs,
+                self_closing,
+                ..
+            } => {
+                let svg = in_svg || name == "svg";
+                let element = if svg {
+                    document.create_element_ns(Some(SVG_NAMESPACE), &name)?
+                } else {
+                    document.create_element(&name)?
+                };
+
+                for attr in attrs {
+                    element.set_attribute(&attr.name, attr.value.as_deref().unwrap_or(""))?;
+                }
+
+                parent.append_child(&element)?;
+
+                if !self_closing && !tokenizer::is_void(&name) {
+                    stack.push((name, element.into(), svg));
+                }
+            }
+            Token::EndTag { name } => {
+                if let Some(i) = stack.iter().rposition(|(open, _, _)| *open == name) {
+                    stack.truncate(i);
+                }
+            }
+            Token::Text(text) => {
+                parent.append_child(&document.create_text_node(&text))?;
+            }
+            Token::Comment(comment) => {
+                parent.append_child(&document.create_comment(comment))?;
+            }
+        }
+    }
+
+    Ok(fragment)
+}
+
+/// Replaces the children of `parent` with the DOM nodes built from `node`.
+pub fn mount(node: impl Renderable, parent: &web_sys::Element) -> Result<(), JsValue> {
+    let document = parent
+        .owner_document()
+        .ok_or_else(|| JsValue::from_str("element is not attached to a document"))?;
+    let fragment = render_dom(node, &document)?;
+    parent.replace_children_with_node_1(&fragment);
+    Ok(())
+}
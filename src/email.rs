@@ -0,0 +1,70 @@
+use crate::core::{self, HasAttributes, Node, Open, Renderable};
+use crate::html5::{Table, Td, Tr, table, td, tr};
+
+/// Content wrapped in a conditional comment, see [`mso`] and [`not_mso`].
+pub struct Conditional {
+    buf: Vec<u8>,
+}
+
+impl Renderable for Conditional {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.buf);
+    }
+
+    fn render(self) -> String {
+        core::finish(self.buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// Content only rendered by Outlook's Word based engine, e.g. VML buttons or ghost tables:
+/// `<!--[if mso]>...<![endif]-->`.
+pub fn mso(content: impl Renderable) -> Conditional {
+    let mut buf = Vec::with_capacity(64);
+    buf.extend_from_slice(b"<!--[if mso]>");
+    core::render_child(content, &mut buf);
+    buf.extend_from_slice(b"<![endif]-->");
+    Conditional { buf }
+}
+
+/// Content hidden from Outlook's Word based engine but rendered by every other client:
+/// `<!--[if !mso]><!-->...<!--<![endif]-->`.
+pub fn not_mso(content: impl Renderable) -> Conditional {
+    let mut buf = Vec::with_capacity(64);
+    buf.extend_from_slice(b"<!--[if !mso]><!-->");
+    core::render_child(content, &mut buf);
+    buf.extend_from_slice(b"<!--<![endif]-->");
+    Conditional { buf }
+}
+
+/// A layout table without spacing or borders that screen readers announce as presentation only.
+pub fn layout_table() -> Node<Table, Open> {
+    table()
+        .attr("role", "presentation")
+        .attr("cellpadding", "0")
+        .attr("cellspacing", "0")
+        .attr("border", "0")
+}
+
+/// Centered fixed width container, the outermost building block of a table based email layout.
+///
+/// Shrinks on small screens through `max-width` for clients that support it.
+pub fn container(width: u32) -> Node<Table, Open> {
+    layout_table()
+        .attr("align", "center")
+        .attr_int("width", width)
+        .attr("style", format!("width:100%;max-width:{}px", width))
+}
+
+/// Row of a layout table.
+pub fn row() -> Node<Tr, Open> {
+    tr()
+}
+
+/// Cell of a layout table with a fixed width, use multiple of them in a [`row`] for columns.
+pub fn column(width: u32) -> Node<Td, Open> {
+    td().attr_int("width", width).attr("valign", "top")
+}
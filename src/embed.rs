@@ -0,0 +1,184 @@
+use std::fmt;
+
+use crate::core::{self, HasAttributes, Renderable};
+use crate::html5::div;
+use crate::tokenizer::{self, Token};
+
+/// Markup rendered by another framework, e.g. a leptos `to_html()` or `dioxus_ssr::render` island, placed into a
+/// lira layout as it is.
+///
+/// Unlike `raw()` the markup is a value of its own, which makes the places trusting foreign output easy to find.
+/// [`checked`](EmbedHtml::checked) additionally makes sure it can't close elements of the surrounding layout.
+///
+/// There are no conversions from the types of those frameworks, their server renderers return a `String`, which
+/// is what [`new`](EmbedHtml::new) and [`checked`](EmbedHtml::checked) take.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// // what the other framework rendered on the server
+/// let counter = String::from("<button data-hk=\"0-0\">Count: 0</button>");
+///
+/// let res = main()
+///     .child(h1().text("Dashboard"))
+///     .child(EmbedHtml::checked(counter).unwrap().island("counter"))
+///     .render();
+///
+/// assert_eq!(
+///     "<main><h1>Dashboard</h1><div data-island=\"counter\"><button data-hk=\"0-0\">Count: 0</button></div></main>",
+///     res
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbedHtml {
+    html: String,
+    island: Option<String>,
+}
+
+impl EmbedHtml {
+    /// Embeds `html` without looking at it, it has to come from a renderer that escapes its output.
+    pub fn new(html: impl Into<String>) -> Self {
+        Self {
+            html: html.into(),
+            island: None,
+        }
+    }
+
+    /// Like [`new`](Self::new), but fails for markup with end tags of elements it didn't open or with elements
+    /// left open, either would change the structure of the page around it. Unfinished markup like an unterminated
+    /// comment, tag or attribute value fails as well, it would swallow the layout after it.
+    pub fn checked(html: impl Into<String>) -> Result<Self, EmbedError> {
+        let html = html.into();
+        check_balanced(&html)?;
+        Ok(Self::new(html))
+    }
+
+    /// Wraps the markup in `<div data-island="name">`, so scripts hydrating it have an element to mount to.
+    pub fn island(mut self, name: impl AsRef<str>) -> Self {
+        self.island = Some(name.as_ref().to_string());
+        self
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.html
+    }
+}
+
+impl Renderable for EmbedHtml {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        match self.island {
+            Some(name) => div()
+                .attr("data-island", name)
+                .raw(self.html)
+                .render_into(buf),
+            None => buf.extend_from_slice(self.html.as_bytes()),
+        }
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// Why [`EmbedHtml::checked`] rejected markup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbedError {
+    /// Byte offset of the offending tag.
+    pub offset: usize,
+    message: String,
+}
+
+impl fmt::Display for EmbedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "can't embed html: {} at byte {}",
+            self.message, self.offset
+        )
+    }
+}
+
+impl std::error::Error for EmbedError {}
+
+fn check_balanced(html: &str) -> Result<(), EmbedError> {
+    let mut open: Vec<(usize, String)> = Vec::new();
+    // the text of these elements is theirs, a `<` in it doesn't start markup
+    let mut in_raw_text = false;
+
+    let tokens = tokenizer::tokenize(html);
+    // the tokens cover all of `html`, each ends where the next one starts
+    let ends: Vec<usize> = tokens
+        .iter()
+        .skip(1)
+        .map(|(offset, _)| *offset)
+        .chain([html.len()])
+        .collect();
+
+    for ((offset, token), end) in tokens.into_iter().zip(ends) {
+        let raw_text = std::mem::take(&mut in_raw_text);
+        match token {
+            Token::StartTag {
+                name, self_closing, ..
+            } if !self_closing && !tokenizer::is_void(&name) => {
+                in_raw_text = matches!(name.as_str(), "script" | "style" | "textarea" | "title");
+                open.push((offset, name));
+            }
+            Token::EndTag { name } => match open.pop() {
+                Some((_, opened)) if opened == name => {}
+                Some((_, opened)) => {
+                    return Err(EmbedError {
+                        offset,
+                        message: format!("</{}> closes <{}>", name, opened),
+                    });
+                }
+                None => {
+                    return Err(EmbedError {
+                        offset,
+                        message: format!("</{}> closes an element outside of the markup", name),
+                    });
+                }
+            },
+            Token::Comment(comment) if !html[offset + 4 + comment.len()..].starts_with("-->") => {
+                return Err(EmbedError {
+                    offset,
+                    message: "the comment is never closed".to_string(),
+                });
+            }
+            // the tokenizer keeps markup it can't finish as text
+            Token::Text(_) if !raw_text => {
+                if let Some(i) = unfinished_markup(&html[offset..end]) {
+                    return Err(EmbedError {
+                        offset: offset + i,
+                        message: "the tag is never finished".to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match open.pop() {
+        Some((offset, name)) => Err(EmbedError {
+            offset,
+            message: format!("<{}> is never closed", name),
+        }),
+        None => Ok(()),
+    }
+}
+
+// offset of a `<` in text that starts markup, or would together with the layout after it
+fn unfinished_markup(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    (0..bytes.len()).find(|&i| {
+        bytes[i] == b'<'
+            && bytes
+                .get(i + 1)
+                .is_none_or(|b| b.is_ascii_alphabetic() || matches!(b, b'/' | b'!' | b'?'))
+    })
+}
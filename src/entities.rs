@@ -0,0 +1,77 @@
+use crate::core::{self, Renderable};
+
+/// A named character reference like `&nbsp;`, written as it is, see [`nbsp`] and friends.
+///
+/// Only the helpers of this module create entities, which makes them a way to insert invisible or ambiguous
+/// characters without reaching for `raw()`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = p()
+///     .text("Home")
+///     .child(nbsp())
+///     .child(raquo())
+///     .child(nbsp())
+///     .text("Blog")
+///     .render();
+///
+/// assert_eq!("<p>Home&nbsp;&raquo;&nbsp;Blog</p>", res);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Entity(&'static str);
+
+impl Entity {
+    /// The reference including `&` and `;`.
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl Renderable for Entity {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.0.as_bytes());
+    }
+
+    fn render(self) -> String {
+        self.0.to_string()
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+macro_rules! entities {
+    ($($(#[$doc:meta])* $name:ident => $entity:literal;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $name() -> Entity {
+                Entity($entity)
+            }
+        )*
+    };
+}
+
+entities! {
+    /// Non-breaking space, keeps the words around it on one line.
+    nbsp => "&nbsp;";
+    /// Thin space, e.g. between a number and its unit.
+    thinsp => "&thinsp;";
+    /// Em dash `—`.
+    mdash => "&mdash;";
+    /// En dash `–`, e.g. for ranges.
+    ndash => "&ndash;";
+    /// Horizontal ellipsis `…`.
+    hellip => "&hellip;";
+    /// Multiplication sign `×`, e.g. for dimensions or close buttons.
+    times => "&times;";
+    /// Middle dot `·`, a common separator.
+    middot => "&middot;";
+    /// Right-pointing double angle quotation mark `»`.
+    raquo => "&raquo;";
+    /// Left-pointing double angle quotation mark `«`.
+    laquo => "&laquo;";
+    /// Copyright sign `©`.
+    copy => "&copy;";
+}
@@ -0,0 +1,238 @@
+use crate::core::{self, Fragment, Renderable, fragment};
+use crate::css::{Stylesheet, media, rule, stylesheet};
+use crate::html5::{
+    HasGlobalAttributes, HasHref, a, body, details, div, h1, head, html, main, meta, p, style,
+    summary, title,
+};
+
+/// Class of the `<main>` element of an [`ErrorPage`], for styles replacing the defaults.
+pub const ERROR_PAGE: &str = "error-page";
+
+/// A complete fallback page for a failed request, built by [`error_page`] or one of the presets like
+/// [`not_found`].
+///
+/// The page has its own small stylesheet so it looks decent even when the app's assets are the thing that's
+/// broken. [`styles`](ErrorPage::styles) replaces it with one that matches the app.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let page = not_found()
+///     .message("There is no order #42.")
+///     .link("/orders", "All orders");
+///
+/// assert_eq!(404, page.status());
+/// assert!(page.render().contains("<h1>Not Found</h1><p>There is no order #42.</p>"));
+/// ```
+pub struct ErrorPage {
+    status: u16,
+    title: Option<String>,
+    message: String,
+    lang: Option<String>,
+    link: Option<(String, String)>,
+    details: Option<(String, Fragment)>,
+    styles: Stylesheet,
+}
+
+/// Starts an error page for `status`, titled with its reason phrase, like `Not Found` for 404.
+pub fn error_page(status: u16, message: impl AsRef<str>) -> ErrorPage {
+    ErrorPage {
+        status,
+        title: None,
+        message: message.as_ref().to_string(),
+        lang: None,
+        link: None,
+        details: None,
+        styles: default_styles(),
+    }
+}
+
+/// `404 Not Found`, with a link back to the home page.
+pub fn not_found() -> ErrorPage {
+    error_page(
+        404,
+        "The page you are looking for doesn't exist or has been moved.",
+    )
+    .link("/", "Home")
+}
+
+/// `500 Internal Server Error`, for failures the visitor can't do anything about.
+pub fn internal_error() -> ErrorPage {
+    error_page(
+        500,
+        "Something went wrong on our side. Please try again later.",
+    )
+}
+
+/// `503 Service Unavailable`, for maintenance windows and overloaded backends.
+pub fn unavailable() -> ErrorPage {
+    error_page(
+        503,
+        "The service is temporarily unavailable. Please try again in a few minutes.",
+    )
+}
+
+impl ErrorPage {
+    /// Heading and title of the page, instead of the reason phrase of the status.
+    pub fn title(mut self, value: impl AsRef<str>) -> Self {
+        self.title = Some(value.as_ref().to_string());
+        self
+    }
+
+    pub fn message(mut self, value: impl AsRef<str>) -> Self {
+        self.message = value.as_ref().to_string();
+        self
+    }
+
+    pub fn lang(mut self, value: impl AsRef<str>) -> Self {
+        self.lang = Some(value.as_ref().to_string());
+        self
+    }
+
+    /// A link below the message, e.g. back to the home page or to a status page.
+    pub fn link(mut self, href: impl AsRef<str>, label: impl AsRef<str>) -> Self {
+        self.link = Some((href.as_ref().to_string(), label.as_ref().to_string()));
+        self
+    }
+
+    pub fn no_link(mut self) -> Self {
+        self.link = None;
+        self
+    }
+
+    /// Extra content in a collapsed `<details>` element labeled `summary`, like a request id to quote to support
+    /// or, in development, the error itself.
+    pub fn details(mut self, summary: impl AsRef<str>, content: impl Renderable) -> Self {
+        self.details = Some((summary.as_ref().to_string(), fragment().child(content)));
+        self
+    }
+
+    /// Replaces the default styles, the page's `<main>` has the class [`ERROR_PAGE`].
+    pub fn styles(mut self, styles: Stylesheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// The status the page was built for, to set on the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+}
+
+impl Renderable for ErrorPage {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        let heading = self
+            .title
+            .unwrap_or_else(|| reason_phrase(self.status).to_string());
+
+        let mut root = html();
+        if let Some(lang) = self.lang {
+            root = root.lang(lang);
+        }
+
+        let mut content = main()
+            .class(ERROR_PAGE)
+            .child(
+                p().class("error-page__status")
+                    .text(self.status.to_string()),
+            )
+            .child(h1().text(&heading))
+            .child(p().text(self.message));
+        if let Some((href, label)) = self.link {
+            content = content.child(p().child(a().href(href).text(label)));
+        }
+        if let Some((label, details_content)) = self.details {
+            content = content.child(
+                details()
+                    .class("error-page__details")
+                    .child(summary().text(label))
+                    .child(div().child(details_content)),
+            );
+        }
+
+        let mut head_content = head()
+            .child(meta().charset("utf-8"))
+            .child(
+                meta()
+                    .name("viewport")
+                    .content("width=device-width, initial-scale=1"),
+            )
+            .child(title().text(format!("{} {}", self.status, heading)));
+        if !self.styles.is_empty() {
+            head_content = head_content.child(style().child(self.styles));
+        }
+
+        root.child(head_content)
+            .child(body().child(content))
+            .render_into(buf);
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+fn default_styles() -> Stylesheet {
+    stylesheet()
+        .rule(
+            rule("body")
+                .margin("0")
+                .font_family("system-ui, sans-serif")
+                .line_height("1.5")
+                .color("#1f2328")
+                .background("#f6f8fa"),
+        )
+        .rule(
+            rule(".error-page")
+                .max_width("36rem")
+                .margin("15vh auto 0")
+                .padding("0 1.5rem"),
+        )
+        .rule(
+            rule(".error-page__status")
+                .margin("0")
+                .font_size("4rem")
+                .font_weight("700")
+                .opacity("0.3"),
+        )
+        .rule(rule(".error-page h1").margin("0 0 1rem"))
+        .rule(rule(".error-page a").color("inherit"))
+        .rule(
+            rule(".error-page__details")
+                .margin("2rem 0 0")
+                .font_size("0.875rem"),
+        )
+        .rule(rule(".error-page__details summary").cursor("pointer"))
+        .media(
+            media("(prefers-color-scheme: dark)")
+                .rule(rule("body").color("#e6edf3").background("#0d1117")),
+        )
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Error",
+    }
+}
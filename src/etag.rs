@@ -0,0 +1,58 @@
+use std::fmt;
+
+use crate::core::Renderable;
+use crate::sri;
+
+/// SHA-256 of rendered HTML, see [`RenderHashed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentHash([u8; 32]);
+
+impl ContentHash {
+    /// Hashes already rendered HTML.
+    pub fn of(html: &str) -> Self {
+        ContentHash(sri::sha256(html.as_bytes()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// The hash as a strong `ETag` header value, the lowercase hex digest in quotes.
+    pub fn etag(&self) -> String {
+        format!("\"{}\"", self)
+    }
+}
+
+/// Lowercase hex digest.
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders together with a hash of the output, for `ETag` headers and cache keys:
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let (html, hash) = p().text("Hello").render_hashed();
+///
+/// assert_eq!("<p>Hello</p>", html);
+/// assert_eq!(ContentHash::of("<p>Hello</p>"), hash);
+/// assert_eq!(66, hash.etag().len());
+/// ```
+///
+/// This is a hash of the finished page, taken after rendering in a pass over the output of its own. It can't be
+/// computed while the nodes write, the render context may still minify the page or sort its attributes at the end.
+pub trait RenderHashed: Renderable + Sized {
+    fn render_hashed(self) -> (String, ContentHash) {
+        let html = self.render();
+        let hash = ContentHash::of(&html);
+        (html, hash)
+    }
+}
+
+impl<R> RenderHashed for R where R: Renderable {}
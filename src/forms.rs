@@ -0,0 +1,818 @@
+use std::fmt;
+
+use crate::a11y::alert;
+use crate::context;
+use crate::core::{Fragment, HasAttributes, Node, Renderable, Void, fragment};
+use crate::html5::{
+    HasGlobalAttributes, HasHref, HasInputType, Input, InputType, a, h2, input, label, li, option,
+    output, p, select, textarea, ul,
+};
+use crate::ids::unique_id;
+
+#[cfg(feature = "derive")]
+pub use lira_derive::Form;
+
+#[cfg(feature = "derive")]
+pub use lira_derive::SelectOptions;
+
+/// Adds a constant with the name of every field to a struct the handler deserializes, so forms written by hand
+/// use the names serde expects. Renaming a field renames its constant, which turns a silently broken form into
+/// a compile error.
+///
+/// The names follow `#[serde(rename = "...")]` and `#[serde(rename_all = "...")]`, the constants are the field
+/// names in upper case:
+///
+/// ```
+/// use lira::forms::FieldNames;
+/// use lira::prelude::*;
+///
+/// #[derive(FieldNames, serde::Deserialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Login {
+///     email_address: String,
+///     #[serde(rename = "pw")]
+///     password: String,
+/// }
+///
+/// let res = form()
+///     .child(input().name(Login::EMAIL_ADDRESS))
+///     .child(input().name(Login::PASSWORD))
+///     .render();
+///
+/// assert_eq!("<form><input name=\"emailAddress\" /><input name=\"pw\" /></form>", res);
+/// ```
+#[cfg(feature = "derive")]
+pub use lira_derive::FieldNames;
+
+/// A struct that renders as a set of labeled form controls, usually derived:
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use lira::forms::Form;
+/// use lira::prelude::*;
+///
+/// #[derive(Form)]
+/// struct Signup {
+///     #[form(type = "email", required)]
+///     email: String,
+///     newsletter: bool,
+/// }
+///
+/// let res = Signup::empty_fields().render();
+///
+/// assert_eq!(
+///     "<label for=\"email\">Email</label><input type=\"email\" id=\"email\" name=\"email\" required />\
+///      <input type=\"checkbox\" id=\"newsletter\" name=\"newsletter\" value=\"true\" />\
+///      <label for=\"newsletter\">Newsletter</label>",
+///     res
+/// );
+/// # }
+/// ```
+///
+/// Field names follow `#[serde(rename)]` and `#[serde(rename_all)]`, so the submitted form deserializes into the
+/// same struct. Fields can be configured with `#[form(...)]`:
+///
+/// - `rename = "..."` overrides the name
+/// - `label = "..."` overrides the label, which defaults to the humanized field name
+/// - `type = "..."` overrides the input type, e.g. `email` or `password`
+/// - `placeholder = "..."` and `required` are passed on to the control
+/// - `textarea` renders a `<textarea>` instead of an `<input>`
+/// - `skip` leaves the field out
+///
+/// Deriving `Form` on an enum with unit variants implements [`FormField`] for it instead, so it can be used as the
+/// type of a field and renders as a `<select>`.
+pub trait Form {
+    /// Controls for all fields, filled with the current values.
+    fn fields(&self) -> Fragment;
+
+    /// Controls for all fields without values.
+    fn empty_fields() -> Fragment
+    where
+        Self: Sized;
+}
+
+/// Settings for rendering a single form field.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Field<'a> {
+    /// Name the value is submitted as, also used as the control's id, numbered like `name-2` when it's taken.
+    pub name: &'a str,
+    pub label: &'a str,
+    /// Input type overriding the one picked for the value type.
+    pub input_type: Option<&'a str>,
+    pub placeholder: Option<&'a str>,
+    pub required: bool,
+    pub textarea: bool,
+}
+
+/// Types that can be rendered as the control of a [`Field`].
+pub trait FormField {
+    /// Renders the label and control, holding `value` if there is one.
+    fn render_field(field: &Field<'_>, value: Option<&Self>) -> Fragment;
+}
+
+impl FormField for str {
+    fn render_field(field: &Field<'_>, value: Option<&Self>) -> Fragment {
+        if field.textarea {
+            let id = field_id(field.name);
+            let errors = error_ids(field, &id);
+            let control = control_attrs(with_name(textarea(), field, &id), field, &errors)
+                .text(value.unwrap_or_default());
+            return labeled(field, &id, &errors, control);
+        }
+
+        text_input(field, field.input_type.unwrap_or("text"), value)
+    }
+}
+
+impl FormField for String {
+    fn render_field(field: &Field<'_>, value: Option<&Self>) -> Fragment {
+        str::render_field(field, value.map(String::as_str))
+    }
+}
+
+impl<T> FormField for &T
+where
+    T: FormField + ?Sized,
+{
+    fn render_field(field: &Field<'_>, value: Option<&Self>) -> Fragment {
+        T::render_field(field, value.copied())
+    }
+}
+
+impl<T> FormField for Option<T>
+where
+    T: FormField,
+{
+    fn render_field(field: &Field<'_>, value: Option<&Self>) -> Fragment {
+        T::render_field(field, value.and_then(Option::as_ref))
+    }
+}
+
+impl FormField for bool {
+    fn render_field(field: &Field<'_>, value: Option<&Self>) -> Fragment {
+        let id = field_id(field.name);
+        let errors = error_ids(field, &id);
+        let control = input()
+            .input_type(InputType::Checkbox)
+            .map(|node| with_name(node, field, &id))
+            .map(|node| control_attrs(node, field, &errors))
+            .value("true")
+            .map_when(value.copied().unwrap_or_default(), |node| node.checked());
+
+        fragment()
+            .child(control)
+            .child(label().for_(&id).text(field.label))
+            .child(error_messages(field, &errors))
+    }
+}
+
+macro_rules! number_fields {
+    ($step:literal, $($ty:ty),*) => {
+        $(
+            impl FormField for $ty {
+                fn render_field(field: &Field<'_>, value: Option<&Self>) -> Fragment {
+                    let value = value.map(ToString::to_string);
+                    let id = field_id(field.name);
+                    let errors = error_ids(field, &id);
+                    let control = input()
+                        .attr("type", field.input_type.unwrap_or("number"))
+                        .map(|node| with_name(node, field, &id))
+                        .map(|node| control_attrs(node, field, &errors))
+                        .map_when(!$step.is_empty(), |node| node.attr("step", $step))
+                        .map_when(value.is_some(), |node| node.value(value.unwrap_or_default()));
+                    labeled(field, &id, &errors, control)
+                }
+            }
+        )*
+    };
+}
+
+number_fields!(
+    "", i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+number_fields!("any", f32, f64);
+
+/// Renders `<option>` children for a `<select>`, marking the one with the value `selected` as selected.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let sizes = [("s", "Small"), ("m", "Medium")];
+/// let res = select()
+///     .name("size")
+///     .child(select_options(sizes, |(value, label)| (value, label), Some("m")))
+///     .render();
+///
+/// assert_eq!(
+///     "<select name=\"size\"><option value=\"s\">Small</option><option value=\"m\" selected>Medium</option></select>",
+///     res
+/// );
+/// ```
+pub fn select_options<It, T, Fn, V, L>(iter: It, mut fun: Fn, selected: Option<&str>) -> Fragment
+where
+    It: IntoIterator<Item = T>,
+    Fn: FnMut(T) -> (V, L),
+    V: AsRef<str>,
+    L: AsRef<str>,
+{
+    fragment().children(iter, |item| {
+        let (value, label) = fun(item);
+        let value = value.as_ref();
+        option()
+            .value(value)
+            .map_when(selected == Some(value), |node| node.selected())
+            .text(label)
+    })
+}
+
+/// An enum offered as the options of a `<select>`, usually derived for enums with unit variants:
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use lira::forms::SelectOptions;
+/// use lira::prelude::*;
+///
+/// #[derive(SelectOptions)]
+/// enum Priority {
+///     Low,
+///     #[form(label = "Normal priority")]
+///     Normal,
+///     High,
+/// }
+///
+/// let res = select().name("priority").child(Priority::Normal.selected()).render();
+///
+/// assert_eq!(
+///     "<select name=\"priority\"><option value=\"Low\">Low</option>\
+///      <option value=\"Normal\" selected>Normal priority</option><option value=\"High\">High</option></select>",
+///     res
+/// );
+/// # }
+/// ```
+///
+/// Values are the variant names and follow `#[serde(rename)]` and `#[serde(rename_all)]`, so the submitted value
+/// deserializes into the same enum. Variants take the `rename`, `label` and `skip` options of [`Form`], skipped
+/// variants aren't offered but still have a value.
+pub trait SelectOptions {
+    /// `(value, label)` of every offered variant, in declaration order.
+    const OPTIONS: &'static [(&'static str, &'static str)];
+
+    /// The value this variant is submitted as.
+    fn value(&self) -> &'static str;
+
+    /// All options without a selection, for a `<select>` starting out empty.
+    fn options() -> Fragment
+    where
+        Self: Sized,
+    {
+        select_options(Self::OPTIONS, |option| *option, None)
+    }
+
+    /// All options with this variant selected.
+    fn selected(&self) -> Fragment {
+        select_options(Self::OPTIONS, |option| *option, Some(self.value()))
+    }
+}
+
+/// Numbers that can be written into the `min`, `max`, `step` and `value` attributes of numeric inputs.
+pub trait InputNumber: Copy + PartialOrd + fmt::Display {
+    #[doc(hidden)]
+    fn write_attr<N: HasAttributes>(self, node: N, name: &str) -> N;
+}
+
+macro_rules! input_integers {
+    ($($ty:ty),*) => {
+        $(impl InputNumber for $ty {
+            fn write_attr<N: HasAttributes>(self, node: N, name: &str) -> N {
+                node.attr_int(name, self)
+            }
+        })*
+    };
+}
+
+macro_rules! input_floats {
+    ($($ty:ty),*) => {
+        $(impl InputNumber for $ty {
+            fn write_attr<N: HasAttributes>(self, node: N, name: &str) -> N {
+                // NaN and the infinities aren't valid floating point numbers in HTML
+                assert!(self.is_finite(), "{} of a numeric input can't be {}", name, self);
+                node.attr_float(name, self)
+            }
+        })*
+    };
+}
+
+input_integers!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+input_floats!(f32, f64);
+
+fn numeric_input<N>(input_type: InputType, min: N, max: N, step: N) -> Node<Input, Void>
+where
+    N: InputNumber,
+{
+    debug_assert!(min <= max, "min {} is larger than max {}", min, max);
+    let node = input().input_type(input_type);
+    let node = min.write_attr(node, "min");
+    let node = max.write_attr(node, "max");
+    step.write_attr(node, "step")
+}
+
+/// Renders a slider, `<input type="range">`, with the bounds, step and value written from numbers.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<input type=\"range\" min=\"0\" max=\"1\" step=\"0.05\" value=\"0.8\" name=\"volume\" />",
+///     range_input(0.0, 1.0, 0.05, 0.8).name("volume").render()
+/// );
+/// ```
+///
+/// # Panics
+///
+/// If a float is NaN or infinite, HTML has no way to write them as numbers.
+pub fn range_input<N>(min: N, max: N, step: N, value: N) -> Node<Input, Void>
+where
+    N: InputNumber,
+{
+    debug_assert!(
+        min <= value && value <= max,
+        "value {} is outside of {}..={}",
+        value,
+        min,
+        max
+    );
+    value.write_attr(numeric_input(InputType::Range, min, max, step), "value")
+}
+
+/// Renders `<input type="number">` with the bounds and step written from numbers, `None` leaves it empty.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<input type=\"number\" min=\"1\" max=\"10\" step=\"1\" value=\"2\" name=\"guests\" />",
+///     number_input(1, 10, 1, Some(2)).name("guests").render()
+/// );
+/// ```
+///
+/// # Panics
+///
+/// If a float is NaN or infinite, HTML has no way to write them as numbers.
+pub fn number_input<N>(min: N, max: N, step: N, value: Option<N>) -> Node<Input, Void>
+where
+    N: InputNumber,
+{
+    let node = numeric_input(InputType::Number, min, max, step);
+    match value {
+        Some(value) => value.write_attr(node, "value"),
+        None => node,
+    }
+}
+
+/// Renders a slider with an `<output>` showing its value, named and identified by `name`. Like the other field
+/// helpers, the id is numbered like `rating-2` if the render context scope has it already.
+///
+/// The output starts with the rendered value and follows the slider through an `oninput` handler, which a
+/// Content-Security-Policy without `'unsafe-hashes'` blocks. The starting value stays correct either way.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<input type=\"range\" min=\"1\" max=\"5\" step=\"1\" value=\"3\" id=\"rating\" name=\"rating\" \
+///      oninput=\"this.nextElementSibling.value=this.value\" /><output for=\"rating\">3</output>",
+///     range_with_output("rating", 1, 5, 1, 3).render()
+/// );
+/// ```
+pub fn range_with_output<N>(name: &str, min: N, max: N, step: N, value: N) -> Fragment
+where
+    N: InputNumber,
+{
+    let id = field_id(name);
+
+    fragment()
+        .child(
+            range_input(min, max, step, value)
+                .id(&id)
+                .name(name)
+                .attr("oninput", "this.nextElementSibling.value=this.value"),
+        )
+        .child(output().for_(&id).text_fmt(format_args!("{}", value)))
+}
+
+/// Renders a checkbox submitting `true` when checked, followed by its label.
+///
+/// The id is the name, numbered like `terms-2` if the render context scope has it already.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<input type=\"checkbox\" id=\"terms\" name=\"terms\" value=\"true\" checked /><label for=\"terms\">Accept terms</label>",
+///     checkbox("terms", "Accept terms", true).render()
+/// );
+/// ```
+pub fn checkbox(name: &str, label: &str, checked: bool) -> Fragment {
+    let field = Field {
+        name,
+        label,
+        ..Default::default()
+    };
+    bool::render_field(&field, Some(&checked))
+}
+
+/// Renders a radio button with a label for every `(value, label)` option, checking the one with the value `checked`.
+///
+/// The first radio has the name as its id, the others the name and value, numbered if the render context scope
+/// has them already. Wrap the group in a `<fieldset>` with a `<legend>` to name it.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<input type=\"radio\" id=\"size\" name=\"size\" value=\"s\" /><label for=\"size\">Small</label>\
+///      <input type=\"radio\" id=\"size-m\" name=\"size\" value=\"m\" checked /><label for=\"size-m\">Medium</label>",
+///     radio_group("size", [("s", "Small"), ("m", "Medium")], Some("m")).render()
+/// );
+/// ```
+pub fn radio_group<It, V, L>(name: &str, options: It, checked: Option<&str>) -> Fragment
+where
+    It: IntoIterator<Item = (V, L)>,
+    V: AsRef<str>,
+    L: AsRef<str>,
+{
+    let field = Field {
+        name,
+        ..Default::default()
+    };
+    // the first radio has the bare name, so the error summary can link to the group
+    let first_id = field_id(name);
+    let errors = error_ids(&field, &first_id);
+
+    fragment()
+        .children(options.into_iter().enumerate(), |(i, (value, text))| {
+            let value = value.as_ref();
+            let id = match i {
+                0 => first_id.clone(),
+                _ => field_id(&control_id(name, value)),
+            };
+
+            fragment()
+                .child(
+                    input()
+                        .input_type(InputType::Radio)
+                        .id(&id)
+                        .name(name)
+                        .value(value)
+                        .map_when(checked == Some(value), |node| node.checked())
+                        .map(|node| invalid_attrs(node, &errors)),
+                )
+                .child(label().for_(&id).text(text))
+        })
+        .child(error_messages(&field, &errors))
+}
+
+// "{name}-{value}" reduced to characters that are safe in ids and CSS selectors
+fn control_id(name: &str, value: &str) -> String {
+    let mut id = String::with_capacity(name.len() + value.len() + 1);
+    id.push_str(name);
+    id.push('-');
+    id.extend(value.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+            c
+        } else {
+            '-'
+        }
+    }));
+    id
+}
+
+/// Hidden input holding a CSRF token, named `csrf_token` unless the render context configures another name.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<input type=\"hidden\" name=\"csrf_token\" value=\"s3cr3t\" />",
+///     csrf_field("s3cr3t").render()
+/// );
+/// ```
+pub fn csrf_field(token: impl AsRef<str>) -> Node<Input, Void> {
+    input()
+        .input_type(InputType::Hidden)
+        .name(context::current_csrf_field_name())
+        .value(token)
+}
+
+/// Like [`csrf_field`] but takes the token from the render context.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = RenderContext::new()
+///     .csrf_token("s3cr3t")
+///     .scope(|| form().child(csrf()).render());
+///
+/// assert_eq!(
+///     "<form><input type=\"hidden\" name=\"csrf_token\" value=\"s3cr3t\" /></form>",
+///     res
+/// );
+/// ```
+///
+/// # Panics
+///
+/// If the active context has no CSRF token, a form that would be rejected on submit is a bug that should show up
+/// right away.
+pub fn csrf() -> Node<Input, Void> {
+    match context::current_csrf_token() {
+        Some(token) => csrf_field(token),
+        None => panic!("csrf() called without a CSRF token in the render context"),
+    }
+}
+
+/// Renders a label and the input built by `fun`, associated through an id.
+///
+/// The id is the input's name like for the other field helpers, so [`error_summary`] can link to it, and errors of
+/// the field are shown the same way. Inputs without a name get a freshly generated id. An id set by `fun` is kept,
+/// but the error summary doesn't know about it and keeps linking to the name.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = RenderContext::new().scope(|| {
+///     labeled_input("E-Mail", |input| input.input_type(InputType::Email).name("email")).render()
+/// });
+///
+/// assert_eq!(
+///     "<label for=\"email\">E-Mail</label><input type=\"email\" name=\"email\" id=\"email\" />",
+///     res
+/// );
+/// ```
+pub fn labeled_input<Fn>(label_text: impl AsRef<str>, fun: Fn) -> Fragment
+where
+    Fn: FnOnce(Node<Input, Void>) -> Node<Input, Void>,
+{
+    let control = fun(input());
+    let name = control.written_attr("name");
+    // an id set by `fun` is kept, otherwise the input gets one
+    let (id, control) = match (control.written_attr("id"), &name) {
+        (Some(id), _) => (id, control),
+        (None, Some(name)) => {
+            let id = field_id(name);
+            let control = control.id(&id);
+            (id, control)
+        }
+        (None, None) => {
+            let id = unique_id("input").to_string();
+            let control = control.id(&id);
+            (id, control)
+        }
+    };
+    let Some(name) = name else {
+        return fragment()
+            .child(label().for_(&id).text(label_text))
+            .child(control);
+    };
+
+    let field = Field {
+        name: &name,
+        ..Default::default()
+    };
+    let errors = error_ids(&field, &id);
+
+    fragment()
+        .child(label().for_(&id).text(label_text))
+        .child(invalid_attrs(control, &errors))
+        .child(error_messages(&field, &errors))
+}
+
+/// Renders a `<select>` field, used by `#[derive(Form)]` on enums.
+///
+/// `options` are `(value, label)` pairs, the one with the value `selected` is preselected.
+pub fn select_field(
+    field: &Field<'_>,
+    options: &[(&str, &str)],
+    selected: Option<&str>,
+) -> Fragment {
+    let id = field_id(field.name);
+    let errors = error_ids(field, &id);
+    let control = with_name(select(), field, &id)
+        .map_when(field.required, |node| node.required())
+        .map(|node| invalid_attrs(node, &errors))
+        .child(select_options(options, |option| *option, selected));
+    labeled(field, &id, &errors, control)
+}
+
+fn text_input(field: &Field<'_>, input_type: &str, value: Option<&str>) -> Fragment {
+    let id = field_id(field.name);
+    let errors = error_ids(field, &id);
+    let control = input()
+        .attr("type", input_type)
+        .map(|node| with_name(node, field, &id))
+        .map(|node| control_attrs(node, field, &errors))
+        .map_when(value.is_some(), |node| {
+            node.value(value.unwrap_or_default())
+        });
+    labeled(field, &id, &errors, control)
+}
+
+fn labeled(field: &Field<'_>, id: &str, errors: &[String], control: impl Renderable) -> Fragment {
+    fragment()
+        .child(label().for_(id).text(field.label))
+        .child(control)
+        .child(error_messages(field, errors))
+}
+
+fn with_name<N>(node: N, field: &Field<'_>, id: &str) -> N
+where
+    N: HasGlobalAttributes,
+{
+    node.id(id).attr("name", field.name)
+}
+
+// ids of controls are their name, numbered like `name-2` if another control of the render context scope has the
+// name already so repeated fields and forms don't clash. Outside of a context the name is used as it is
+fn field_id(name: &str) -> String {
+    context::unique_control_id(name).unwrap_or_else(|| name.to_string())
+}
+
+fn control_attrs<N>(node: N, field: &Field<'_>, errors: &[String]) -> N
+where
+    N: HasAttributes,
+{
+    let node = invalid_attrs(node, errors);
+    let node = match field.placeholder {
+        Some(placeholder) => node.attr("placeholder", placeholder),
+        None => node,
+    };
+
+    if field.required {
+        node.flag("required")
+    } else {
+        node
+    }
+}
+
+/// Validation errors by field name, rendered by the form helpers once they are set on the render context with
+/// [`field_errors`](crate::context::RenderContext::field_errors).
+///
+/// Controls of fields with errors are marked with `aria-invalid` and followed by a `<p class="field-error">` for
+/// each message, which the control references with `aria-describedby` so screen readers read the error together
+/// with the label.
+///
+/// ```
+/// use lira::forms::{Field, FieldErrors, FormField};
+/// use lira::prelude::*;
+///
+/// let errors = FieldErrors::new().add("email", "Enter an email address");
+/// let field = Field {
+///     name: "email",
+///     label: "E-Mail",
+///     ..Default::default()
+/// };
+///
+/// let res = RenderContext::new()
+///     .field_errors(errors)
+///     .scope(|| str::render_field(&field, None).render());
+///
+/// assert_eq!(
+///     "<label for=\"email\">E-Mail</label><input type=\"text\" id=\"email\" name=\"email\" \
+///      aria-invalid=\"true\" aria-describedby=\"email-error-1\" />\
+///      <p id=\"email-error-1\" class=\"field-error\">Enter an email address</p>",
+///     res
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldErrors {
+    fields: Vec<(String, Vec<String>)>,
+}
+
+impl FieldErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error message for the field `name`.
+    pub fn add(mut self, name: impl AsRef<str>, message: impl AsRef<str>) -> Self {
+        self.push(name, message);
+        self
+    }
+
+    /// Like [`add`](FieldErrors::add), for collecting errors in a loop.
+    pub fn push(&mut self, name: impl AsRef<str>, message: impl AsRef<str>) {
+        let name = name.as_ref();
+        let message = message.as_ref().to_string();
+
+        match self.fields.iter_mut().find(|(field, _)| field == name) {
+            Some((_, messages)) => messages.push(message),
+            None => self.fields.push((name.to_string(), vec![message])),
+        }
+    }
+
+    /// Error messages of the field `name`.
+    pub fn get(&self, name: &str) -> &[String] {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map_or(&[], |(_, messages)| messages.as_slice())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Fields with their messages, in the order they were first added.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.fields
+            .iter()
+            .map(|(field, messages)| (field.as_str(), messages.as_slice()))
+    }
+}
+
+/// Collects `(field, message)` pairs.
+impl<K, V> FromIterator<(K, V)> for FieldErrors
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn from_iter<It: IntoIterator<Item = (K, V)>>(iter: It) -> Self {
+        iter.into_iter()
+            .fold(FieldErrors::new(), |errors, (name, message)| {
+                errors.add(name, message)
+            })
+    }
+}
+
+/// Lists all `errors` with links to their fields, meant to be shown above the form after a failed submit. Renders
+/// nothing if there are no errors.
+///
+/// The links point at the id the first control of every field gets from the field helpers, the field's name unless
+/// another control of the render context scope took it already.
+///
+/// ```
+/// use lira::forms::{FieldErrors, error_summary};
+/// use lira::prelude::*;
+///
+/// let errors = FieldErrors::new().add("email", "Enter an email address");
+///
+/// assert_eq!(
+///     "<div role=\"alert\" aria-live=\"assertive\" aria-atomic=\"true\" class=\"error-summary\" tabindex=\"-1\">\
+///      <h2>There is a problem</h2><ul><li><a href=\"#email\">Enter an email address</a></li></ul></div>",
+///     error_summary(&errors, "There is a problem").render()
+/// );
+/// ```
+pub fn error_summary(errors: &FieldErrors, title: impl AsRef<str>) -> Fragment {
+    if errors.is_empty() {
+        return fragment();
+    }
+
+    let items = errors
+        .iter()
+        .flat_map(|(name, messages)| messages.iter().map(move |message| (name, message)));
+
+    fragment().child(
+        alert()
+            .class("error-summary")
+            .tabindex(-1)
+            .child(h2().text(title))
+            .child(ul().children(items, |(name, message)| {
+                let id = context::first_control_id(name).unwrap_or_else(|| name.to_string());
+                li().child(a().href(format!("#{}", id)).text(message))
+            })),
+    )
+}
+
+fn error_count(field: &Field<'_>) -> usize {
+    context::current_field_errors().map_or(0, |errors| errors.get(field.name).len())
+}
+
+// ids of the error messages of the control `id`, handed out like control ids so repeated fields don't share them
+fn error_ids(field: &Field<'_>, id: &str) -> Vec<String> {
+    (1..=error_count(field))
+        .map(|n| field_id(&format!("{}-error-{}", id, n)))
+        .collect()
+}
+
+fn invalid_attrs<N>(node: N, errors: &[String]) -> N
+where
+    N: HasAttributes,
+{
+    if errors.is_empty() {
+        return node;
+    }
+
+    node.attr("aria-invalid", "true")
+        .attr("aria-describedby", errors.join(" "))
+}
+
+fn error_messages(field: &Field<'_>, ids: &[String]) -> Fragment {
+    let Some(errors) = context::current_field_errors() else {
+        return fragment();
+    };
+
+    fragment().children(errors.get(field.name).iter().zip(ids), |(message, id)| {
+        p().id(id).class("field-error").text(message)
+    })
+}
@@ -0,0 +1,400 @@
+use crate::core::{self, Fragment, HasAttributes, Node, Open, Renderable, Void, fragment};
+use crate::css::{Rule, rule};
+use crate::html5::{
+    CrossOrigin, HasCrossOrigin, HasGlobalAttributes, HasHref, HasRel, HasSrc, Link, LinkAs, Meta,
+    Rel, Script, link, meta, script,
+};
+use crate::json::Value;
+
+// resource hints
+
+/// Preloads a resource needed by the current page.
+///
+/// Fonts and fetches are always requested in CORS mode, so `crossorigin` is set for them, otherwise the
+/// preloaded response can't be reused and the resource is downloaded twice. For fonts and images the `type` is
+/// derived from the file extension so browsers can skip formats they don't support.
+pub fn preload(href: impl AsRef<str>, as_: LinkAs) -> Node<Link, Void> {
+    let href = href.as_ref();
+
+    let node = link().rel(Rel::Preload).href(href).as_(as_);
+
+    let node = match (as_, mime_type_of(href)) {
+        (LinkAs::Font, Some(mime)) if mime.starts_with("font/") => node.type_(mime),
+        (LinkAs::Image, Some(mime)) if mime.starts_with("image/") => node.type_(mime),
+        _ => node,
+    };
+
+    match as_ {
+        LinkAs::Font | LinkAs::Fetch => node.crossorigin(CrossOrigin::Anonymous),
+        _ => node,
+    }
+}
+
+/// Tells the browser to open a connection to the given origin ahead of time.
+pub fn preconnect(origin: impl AsRef<str>) -> Node<Link, Void> {
+    link().rel(Rel::Preconnect).href(origin)
+}
+
+/// Tells the browser to resolve the DNS of the given origin ahead of time.
+pub fn dns_prefetch(origin: impl AsRef<str>) -> Node<Link, Void> {
+    link().rel(Rel::DnsPrefetch).href(origin)
+}
+
+/// Preloads an ES module and its dependencies.
+pub fn modulepreload(href: impl AsRef<str>) -> Node<Link, Void> {
+    link().rel(Rel::ModulePreload).href(href)
+}
+
+// document metadata
+
+/// Reloads the page after `seconds`, or redirects to `url` if there is one.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<meta http-equiv=\"refresh\" content=\"3; url=/login\" />",
+///     meta_refresh(3, Some("/login")).render()
+/// );
+/// ```
+pub fn meta_refresh(seconds: u32, url: Option<&str>) -> Node<Meta, Void> {
+    let content = match url {
+        Some(url) => format!("{}; url={}", seconds, url),
+        None => seconds.to_string(),
+    };
+    meta().http_equiv("refresh").content(content)
+}
+
+/// Color browsers use for their UI around the page, e.g. the address bar on mobile.
+pub fn theme_color(color: impl AsRef<str>) -> Node<Meta, Void> {
+    meta().name("theme-color").content(color)
+}
+
+/// Theme colors for light and dark mode, picked according to `prefers-color-scheme`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<meta name=\"theme-color\" content=\"#fff\" media=\"(prefers-color-scheme: light)\" />\
+///      <meta name=\"theme-color\" content=\"#111\" media=\"(prefers-color-scheme: dark)\" />",
+///     theme_colors("#fff", "#111").render()
+/// );
+/// ```
+pub fn theme_colors(light: impl AsRef<str>, dark: impl AsRef<str>) -> Fragment {
+    fragment()
+        .child(theme_color(light).media("(prefers-color-scheme: light)"))
+        .child(theme_color(dark).media("(prefers-color-scheme: dark)"))
+}
+
+/// Color schemes the page supports, e.g. `"light dark"`, so browsers render form controls and scrollbars to
+/// match before any CSS is loaded.
+pub fn color_scheme(value: impl AsRef<str>) -> Node<Meta, Void> {
+    meta().name("color-scheme").content(value)
+}
+
+/// Opts into view transitions when navigating between pages of the same origin, as
+/// `<meta name="view-transition" content="same-origin">`.
+///
+/// That's the syntax of the first browsers supporting cross-document transitions, newer ones only read the CSS
+/// rule from [`view_transition_rule`], so add both.
+pub fn view_transition() -> Node<Meta, Void> {
+    meta().name("view-transition").content("same-origin")
+}
+
+/// The `@view-transition` rule opting into cross-document view transitions, for a [`stylesheet`].
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = head()
+///     .child(view_transition())
+///     .child(style().child(stylesheet().rule(view_transition_rule())))
+///     .render();
+///
+/// assert_eq!(
+///     "<head><meta name=\"view-transition\" content=\"same-origin\" />\
+///      <style>@view-transition{navigation:auto}</style></head>",
+///     res
+/// );
+/// ```
+///
+/// [`stylesheet`]: crate::css::stylesheet
+pub fn view_transition_rule() -> Rule {
+    rule("@view-transition").prop("navigation", "auto")
+}
+
+// feeds
+
+/// Lets browsers and feed readers find the RSS feed of the site, as `<link rel="alternate">` with the
+/// `application/rss+xml` type. `title` is what readers offer it as, a page can link several feeds.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<link rel=\"alternate\" type=\"application/rss+xml\" title=\"Blog\" href=\"/feed.xml\" />",
+///     rss_autodiscovery("/feed.xml", "Blog").render()
+/// );
+/// ```
+pub fn rss_autodiscovery(href: impl AsRef<str>, title: impl AsRef<str>) -> Node<Link, Void> {
+    feed_link(href, title, "application/rss+xml")
+}
+
+/// Like [`rss_autodiscovery`] for an Atom feed, typed `application/atom+xml`.
+pub fn atom_autodiscovery(href: impl AsRef<str>, title: impl AsRef<str>) -> Node<Link, Void> {
+    feed_link(href, title, "application/atom+xml")
+}
+
+/// Like [`rss_autodiscovery`] for a [JSON Feed](https://www.jsonfeed.org/), typed `application/feed+json`.
+pub fn json_feed_autodiscovery(href: impl AsRef<str>, title: impl AsRef<str>) -> Node<Link, Void> {
+    feed_link(href, title, "application/feed+json")
+}
+
+fn feed_link(href: impl AsRef<str>, title: impl AsRef<str>, mime: &str) -> Node<Link, Void> {
+    link()
+        .rel(Rel::Alternate)
+        .type_(mime)
+        .title(title)
+        .href(href)
+}
+
+/// Everything an installable web app needs in the head: the manifest link, the theme color, the meta tags making
+/// it run standalone on iOS and an `apple-touch-icon` for every `(href, size)` icon, iOS doesn't read the icons of
+/// the manifest.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = pwa("/app.webmanifest", "#0f172a", [("/icons/180.png", 180)]).render();
+///
+/// assert_eq!(
+///     "<link rel=\"manifest\" href=\"/app.webmanifest\" />\
+///      <meta name=\"theme-color\" content=\"#0f172a\" />\
+///      <meta name=\"mobile-web-app-capable\" content=\"yes\" />\
+///      <meta name=\"apple-mobile-web-app-capable\" content=\"yes\" />\
+///      <meta name=\"apple-mobile-web-app-status-bar-style\" content=\"default\" />\
+///      <link rel=\"apple-touch-icon\" sizes=\"180x180\" href=\"/icons/180.png\" />",
+///     res
+/// );
+/// ```
+///
+/// Icons are square, so they are given by their edge length in pixels. In debug builds empty values and icons
+/// without a size panic, those leave the app uninstallable without any error in the browser.
+pub fn pwa<It, H>(
+    manifest_href: impl AsRef<str>,
+    theme_color: impl AsRef<str>,
+    icons: It,
+) -> Fragment
+where
+    It: IntoIterator<Item = (H, u32)>,
+    H: AsRef<str>,
+{
+    let manifest_href = manifest_href.as_ref();
+    let color = theme_color.as_ref();
+    debug_assert!(!manifest_href.is_empty(), "pwa() needs a manifest href");
+    debug_assert!(!color.trim().is_empty(), "pwa() needs a theme color");
+
+    let head = fragment()
+        .child(link().rel(Rel::Manifest).href(manifest_href))
+        .child(self::theme_color(color))
+        .child(meta().name("mobile-web-app-capable").content("yes"))
+        .child(meta().name("apple-mobile-web-app-capable").content("yes"))
+        .child(
+            meta()
+                .name("apple-mobile-web-app-status-bar-style")
+                .content("default"),
+        );
+
+    icons.into_iter().fold(head, |head, (href, size)| {
+        let href = href.as_ref();
+        debug_assert!(!href.is_empty(), "pwa() icon without an href");
+        debug_assert!(size > 0, "pwa() icon {} without a size", href);
+        head.child(
+            link()
+                .attr("rel", "apple-touch-icon")
+                .attr("sizes", format!("{}x{}", size, size))
+                .href(href),
+        )
+    })
+}
+
+// modules
+
+/// Import map built by [`importmap`].
+#[derive(Debug, Clone, Default)]
+pub struct ImportMap {
+    imports: Vec<(String, String)>,
+    scopes: Vec<(String, Vec<(String, String)>)>,
+    integrity: Vec<(String, String)>,
+}
+
+/// Maps bare module specifiers to URLs, rendered as `<script type="importmap">`.
+///
+/// The JSON is escaped so it can't close the script element, and the script gets the CSP nonce of the active
+/// render context like any other [`script`].
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = importmap()
+///     .import("vue", "/js/vue.esm.js")
+///     .scope("/admin/", [("vue", "/js/vue.debug.js")])
+///     .render();
+///
+/// assert_eq!(
+///     "<script type=\"importmap\">{\"imports\":{\"vue\":\"/js/vue.esm.js\"},\
+///      \"scopes\":{\"/admin/\":{\"vue\":\"/js/vue.debug.js\"}}}</script>",
+///     res
+/// );
+/// ```
+pub fn importmap() -> ImportMap {
+    ImportMap::default()
+}
+
+impl ImportMap {
+    /// Maps `specifier` to `url`, a specifier ending with `/` maps a whole path prefix.
+    pub fn import(mut self, specifier: impl AsRef<str>, url: impl AsRef<str>) -> Self {
+        self.imports
+            .push((specifier.as_ref().to_string(), url.as_ref().to_string()));
+        self
+    }
+
+    /// Mappings only used by modules whose URL starts with `prefix`.
+    pub fn scope<It, S, U>(mut self, prefix: impl AsRef<str>, imports: It) -> Self
+    where
+        It: IntoIterator<Item = (S, U)>,
+        S: AsRef<str>,
+        U: AsRef<str>,
+    {
+        let imports = imports
+            .into_iter()
+            .map(|(specifier, url)| (specifier.as_ref().to_string(), url.as_ref().to_string()))
+            .collect();
+        self.scopes.push((prefix.as_ref().to_string(), imports));
+        self
+    }
+
+    /// Subresource integrity hash the module at `url` has to match, see [`sri`](crate::sri).
+    pub fn integrity(mut self, url: impl AsRef<str>, hash: impl AsRef<str>) -> Self {
+        self.integrity
+            .push((url.as_ref().to_string(), hash.as_ref().to_string()));
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let object = |entries: &[(String, String)]| {
+            Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+                    .collect(),
+            )
+        };
+
+        let mut entries = vec![("imports".to_string(), object(&self.imports))];
+        if !self.scopes.is_empty() {
+            let scopes = self
+                .scopes
+                .iter()
+                .map(|(prefix, imports)| (prefix.clone(), object(imports)))
+                .collect();
+            entries.push(("scopes".to_string(), Value::Object(scopes)));
+        }
+        if !self.integrity.is_empty() {
+            entries.push(("integrity".to_string(), object(&self.integrity)));
+        }
+        Value::Object(entries)
+    }
+}
+
+impl Renderable for ImportMap {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        script()
+            .type_("importmap")
+            .raw(self.to_json().to_string())
+            .render_into(buf);
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+// third-party scripts
+
+/// Loads a third-party script with `defer`, so it never blocks parsing, passing its configuration as `data-*`
+/// attributes.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = deferred_script("https://cdn.example.com/widget.js", [("site", "a\"b"), ("theme", "dark")]).render();
+///
+/// assert_eq!(
+///     "<script src=\"https://cdn.example.com/widget.js\" defer data-site=\"a&quot;b\" data-theme=\"dark\"></script>",
+///     res
+/// );
+/// ```
+pub fn deferred_script<It, K, V>(src: impl AsRef<str>, data_attrs: It) -> Node<Script, Open>
+where
+    It: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    data_attrs
+        .into_iter()
+        .fold(script().src(src).defer(), |script, (key, value)| {
+            script.attr(format!("data-{}", key.as_ref()), value)
+        })
+}
+
+/// The [Plausible](https://plausible.io) analytics snippet for `domain`, as
+/// `<script src="https://plausible.io/js/script.js" defer data-domain="..."></script>`.
+///
+/// Self-hosted instances serve the script themselves, use [`deferred_script`] with their URL instead.
+pub fn plausible(domain: impl AsRef<str>) -> Node<Script, Open> {
+    deferred_script(
+        "https://plausible.io/js/script.js",
+        [("domain", domain.as_ref())],
+    )
+}
+
+/// The [Umami](https://umami.is) analytics snippet, `src` is the `script.js` of the instance, e.g.
+/// `https://cloud.umami.is/script.js`.
+pub fn umami(src: impl AsRef<str>, website_id: impl AsRef<str>) -> Node<Script, Open> {
+    deferred_script(src, [("website-id", website_id.as_ref())])
+}
+
+/// The [Fathom](https://usefathom.com) analytics snippet for the site with the id `site`.
+pub fn fathom(site: impl AsRef<str>) -> Node<Script, Open> {
+    deferred_script(
+        "https://cdn.usefathom.com/script.js",
+        [("site", site.as_ref())],
+    )
+}
+
+fn mime_type_of(href: &str) -> Option<&'static str> {
+    let path = href.split(['?', '#']).next().unwrap_or(href);
+    let (_, ext) = path.rsplit_once('.')?;
+
+    match ext.to_ascii_lowercase().as_str() {
+        "woff2" => Some("font/woff2"),
+        "woff" => Some("font/woff"),
+        "ttf" => Some("font/ttf"),
+        "otf" => Some("font/otf"),
+        "avif" => Some("image/avif"),
+        "webp" => Some("image/webp"),
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "svg" => Some("image/svg+xml"),
+        _ => None,
+    }
+}
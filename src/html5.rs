@@ -1,4 +1,10 @@
-use crate::core::{CanAddChildren, CanAddText, HasAttributes, Node, Open, Void};
+use crate::context;
+use crate::core::{
+    CanAddChildren, CanAddText, Element, ElementWriter, HasAttributes, IntoBuffer, Node, Open,
+    Renderable, Void, render_child,
+};
+use crate::datetime::DateTimeValue;
+use crate::ids::Id;
 
 // attributes
 
@@ -56,9 +62,36 @@ pub trait HasGlobalAttributes: HasAttributes + Sized {
         self.flag("contenteditable")
     }
 
+    /// Id of the element whose contents or presence this element controls, e.g. the menu of a toggle button.
+    fn aria_controls(self, id: &Id) -> Self {
+        self.attr("aria-controls", id)
+    }
+
+    /// Id of the element describing this one, e.g. help text or an error message of an input.
+    fn aria_describedby(self, id: &Id) -> Self {
+        self.attr("aria-describedby", id)
+    }
+
+    /// Id of the element labeling this one.
+    fn aria_labelledby(self, id: &Id) -> Self {
+        self.attr("aria-labelledby", id)
+    }
+
+    /// Inline CSS declarations, usually built with [`css`](crate::css::css).
+    fn style(self, value: impl AsRef<str>) -> Self {
+        self.attr("style", value)
+    }
+
+    /// Name the element is matched by across a view transition, written as its `style` attribute. Use
+    /// [`css().view_transition_name(...)`](crate::css::InlineStyle::view_transition_name) instead if the element
+    /// has other inline styles.
+    fn view_transition_name(self, name: impl AsRef<str>) -> Self {
+        self.style(crate::css::css().view_transition_name(name))
+    }
+
     /// Provides an explicit tab order for the element.
     fn tabindex(self, value: i32) -> Self {
-        self.attr("tabindex", value.to_string())
+        self.attr_int("tabindex", value)
     }
 
     /// Hides the element from the page.
@@ -71,6 +104,12 @@ pub trait HasGlobalAttributes: HasAttributes + Sized {
         self.attr("autocapitalize", value)
     }
 
+    /// Stable key used by DOM morphing libraries (idiomorph, morphdom, htmx morph swaps) to match elements across
+    /// updates. Written as `id` unless the render context configures another attribute.
+    fn key(self, value: impl AsRef<str>) -> Self {
+        self.attr(context::current_key_attribute(), value)
+    }
+
     /// Forms a class of attributes, called custom data attributes, that allow proprietary information to be exchanged
     /// between the HTML and its DOM representation that may be used by scripts. All such custom data are available
     /// via the HTMLElement interface of the element the attribute is set on. The HTMLElement.dataset property gives
@@ -91,6 +130,11 @@ pub trait HasHref: HasAttributes + Sized {
     fn href(self, value: impl AsRef<str>) -> Self {
         self.attr("href", value)
     }
+
+    /// Links to the element with the given id on the same page.
+    fn href_fragment(self, id: &Id) -> Self {
+        self.attr("href", id.href())
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -126,6 +170,10 @@ pub enum Rel {
     /// Web app manifest.
     Manifest,
 
+    /// Tells the browser to preemptively fetch the script and store it in the document's module map for later
+    /// evaluation.
+    ModulePreload,
+
     /// Indicates that the current document is a part of a series and that the next document in the series is the
     /// referenced document.
     Next,
@@ -144,6 +192,9 @@ pub enum Rel {
     /// that is not an auxiliary browsing context (i.e., has "_blank" as target attribute value).
     Opener,
 
+    /// Specifies that the user agent should preemptively connect to the target resource's origin.
+    Preconnect,
+
     /// Specifies that the user agent should preemptively fetch and cache the target resource as it is likely to
     /// be required for a followup navigation.
     Prefetch,
@@ -180,11 +231,13 @@ pub trait HasRel: HasAttributes + Sized {
             Rel::Icon => "icon",
             Rel::License => "license",
             Rel::Manifest => "manifest",
+            Rel::ModulePreload => "modulepreload",
             Rel::Next => "next",
             Rel::NoFollow => "nofollow",
             Rel::NoOpener => "noopener",
             Rel::NoReferrer => "noreferrer",
             Rel::Opener => "opener",
+            Rel::Preconnect => "preconnect",
             Rel::Prefetch => "prefetch",
             Rel::Preload => "preload",
             Rel::Prev => "prev",
@@ -203,6 +256,43 @@ pub trait HasSrc: HasAttributes + Sized {
     }
 }
 
+/// Potential destination of a preloaded resource, used by the `as` attribute of `<link>`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LinkAs {
+    Audio,
+    Document,
+    Embed,
+    Fetch,
+    Font,
+    Image,
+    Object,
+    Script,
+    Style,
+    Track,
+    Video,
+    Worker,
+}
+
+/// CORS settings of an element fetching a resource.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CrossOrigin {
+    /// Request uses CORS headers, credentials are only sent for same-origin requests.
+    Anonymous,
+
+    /// Request uses CORS headers and always sends credentials.
+    UseCredentials,
+}
+
+pub trait HasCrossOrigin: HasAttributes + Sized {
+    fn crossorigin(self, value: CrossOrigin) -> Self {
+        let value = match value {
+            CrossOrigin::Anonymous => "anonymous",
+            CrossOrigin::UseCredentials => "use-credentials",
+        };
+        self.attr("crossorigin", value)
+    }
+}
+
 pub enum InputType {
     Text,
     Password,
@@ -215,7 +305,13 @@ pub enum InputType {
     Hidden,
     Email,
     Number,
+    Range,
+    Color,
     Date,
+    DatetimeLocal,
+    Month,
+    Time,
+    Week,
 }
 
 pub trait HasInputType: HasAttributes + Sized {
@@ -232,7 +328,13 @@ pub trait HasInputType: HasAttributes + Sized {
             InputType::Hidden => "hidden",
             InputType::Email => "email",
             InputType::Number => "number",
+            InputType::Range => "range",
+            InputType::Color => "color",
             InputType::Date => "date",
+            InputType::DatetimeLocal => "datetime-local",
+            InputType::Month => "month",
+            InputType::Time => "time",
+            InputType::Week => "week",
         };
         self.attr("type", value)
     }
@@ -263,6 +365,13 @@ pub enum FormMethod {
     Dialog,
 }
 
+fn with_context_nonce<Tag>(node: Node<Tag, Open>) -> Node<Tag, Open> {
+    match context::current_nonce() {
+        Some(nonce) => node.attr("nonce", nonce),
+        None => node,
+    }
+}
+
 // HTML elements
 
 // <html> element
@@ -273,9 +382,9 @@ pub struct Html;
 /// Root element of an HTML document. All other elements must be descendants of this.
 pub fn html() -> Node<Html, Open> {
     // since this will be used for an entire page, make it big
-    let mut buf = Vec::with_capacity(2048);
+    let mut buf = Vec::with_capacity(context::flags().buffer_capacities.page);
     buf.extend_from_slice(b"<!DOCTYPE html>");
-    Node::with_buffer("html", buf)
+    Node::in_buffer("html", buf)
 }
 
 impl CanAddChildren for Html {}
@@ -300,8 +409,33 @@ pub fn link() -> Node<Link, Void> {
 
 impl HasHref for Node<Link, Void> {}
 impl HasRel for Node<Link, Void> {}
+impl HasCrossOrigin for Node<Link, Void> {}
 
 impl Node<Link, Void> {
+    /// Type of content being loaded, required for `rel="preload"`.
+    pub fn as_(self, value: LinkAs) -> Self {
+        let value = match value {
+            LinkAs::Audio => "audio",
+            LinkAs::Document => "document",
+            LinkAs::Embed => "embed",
+            LinkAs::Fetch => "fetch",
+            LinkAs::Font => "font",
+            LinkAs::Image => "image",
+            LinkAs::Object => "object",
+            LinkAs::Script => "script",
+            LinkAs::Style => "style",
+            LinkAs::Track => "track",
+            LinkAs::Video => "video",
+            LinkAs::Worker => "worker",
+        };
+        self.attr("as", value)
+    }
+
+    /// MIME type of the linked resource.
+    pub fn type_(self, value: impl AsRef<str>) -> Self {
+        self.attr("type", value)
+    }
+
     /// Specifies a Subresource Integrity value that allows browsers to verify what they fetch.
     pub fn integrity(self, value: impl AsRef<str>) -> Self {
         self.attr("integrity", value)
@@ -338,19 +472,28 @@ impl Node<Meta, Void> {
 pub struct Style;
 
 /// Contains CSS styling rules for the document.
+///
+/// Gets the CSP nonce of the active render context, if there is one.
 pub fn style() -> Node<Style, Open> {
-    Node::new("style")
+    with_context_nonce(Node::new("style"))
 }
 
 impl CanAddText for Style {}
 impl CanAddChildren for Style {}
 
+impl Node<Style, Open> {
+    /// Cryptographic nonce used by the Content Security Policy to allow this stylesheet.
+    pub fn nonce(self, value: impl AsRef<str>) -> Self {
+        self.attr("nonce", value)
+    }
+}
+
 // <title>
 pub struct Title;
 
 /// Defines the document title shown in browser tab/title bar.
 pub fn title() -> Node<Title, Open> {
-    Node::new("title")
+    Node::new_small("title")
 }
 
 impl CanAddText for Title {}
@@ -381,7 +524,7 @@ pub struct Span;
 
 /// Generic inline container for phrasing content.
 pub fn span() -> Node<Span, Open> {
-    Node::new("span")
+    Node::new_small("span")
 }
 
 impl CanAddChildren for Span {}
@@ -447,6 +590,74 @@ pub fn h6() -> Node<H6, Open> {
 impl CanAddChildren for H6 {}
 impl CanAddText for H6 {}
 
+// <h1> - <h6>
+pub struct Heading;
+
+/// Level of a [`h`] heading.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum HeadingLevel {
+    H1,
+    H2,
+    H3,
+    H4,
+    H5,
+    H6,
+}
+
+impl HeadingLevel {
+    /// The level for `level`, clamped to 1 - 6.
+    pub fn new(level: u8) -> Self {
+        match level {
+            0 | 1 => HeadingLevel::H1,
+            2 => HeadingLevel::H2,
+            3 => HeadingLevel::H3,
+            4 => HeadingLevel::H4,
+            5 => HeadingLevel::H5,
+            _ => HeadingLevel::H6,
+        }
+    }
+
+    /// The level as a number from 1 to 6.
+    pub fn get(self) -> u8 {
+        self as u8 + 1
+    }
+
+    /// The next deeper level for nested sections, `H6` stays `H6`.
+    pub fn next(self) -> Self {
+        HeadingLevel::new(self.get() + 1)
+    }
+}
+
+impl From<u8> for HeadingLevel {
+    fn from(level: u8) -> Self {
+        HeadingLevel::new(level)
+    }
+}
+
+/// Heading of a level only known at runtime, for components rendered at different depths.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let card = |level: HeadingLevel| article().child(h(level).text("Title"));
+///
+/// assert_eq!("<article><h2>Title</h2></article>", card(HeadingLevel::H2).render());
+/// assert_eq!("<h6>Deep</h6>", h(HeadingLevel::new(9)).text("Deep").render());
+/// ```
+pub fn h(level: HeadingLevel) -> Node<Heading, Open> {
+    Node::new(match level {
+        HeadingLevel::H1 => "h1",
+        HeadingLevel::H2 => "h2",
+        HeadingLevel::H3 => "h3",
+        HeadingLevel::H4 => "h4",
+        HeadingLevel::H5 => "h5",
+        HeadingLevel::H6 => "h6",
+    })
+}
+
+impl CanAddChildren for Heading {}
+impl CanAddText for Heading {}
+
 // <p>
 pub struct Paragraph;
 
@@ -467,6 +678,7 @@ pub fn img() -> Node<Img, Void> {
 }
 
 impl HasSrc for Node<Img, Void> {}
+impl HasCrossOrigin for Node<Img, Void> {}
 
 impl Node<Img, Void> {
     /// Text description of the image, shown if image fails to load.
@@ -476,12 +688,174 @@ impl Node<Img, Void> {
 
     /// Intrinsic width of the image in pixels.
     pub fn width(self, value: i32) -> Self {
-        self.attr("width", value.to_string())
+        self.attr_int("width", value)
     }
 
     /// Intrinsic height of the image in pixels.
     pub fn height(self, value: i32) -> Self {
-        self.attr("height", value.to_string())
+        self.attr_int("height", value)
+    }
+
+    /// Candidate images for different widths or pixel densities, e.g. `"a-480.jpg 480w, a-960.jpg 960w"`.
+    pub fn srcset(self, value: impl AsRef<str>) -> Self {
+        self.attr("srcset", value)
+    }
+
+    /// Width the image is displayed at for different media conditions, used to pick from the `srcset`.
+    pub fn sizes(self, value: impl AsRef<str>) -> Self {
+        self.attr("sizes", value)
+    }
+
+    /// When the image should be loaded.
+    pub fn loading(self, value: Loading) -> Self {
+        let value = match value {
+            Loading::Eager => "eager",
+            Loading::Lazy => "lazy",
+        };
+        self.attr("loading", value)
+    }
+
+    /// Whether the image may be decoded off the main thread.
+    pub fn decoding(self, value: Decoding) -> Self {
+        let value = match value {
+            Decoding::Sync => "sync",
+            Decoding::Async => "async",
+            Decoding::Auto => "auto",
+        };
+        self.attr("decoding", value)
+    }
+}
+
+/// Loading behavior of an image.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Loading {
+    /// Loads the image right away, the default.
+    Eager,
+
+    /// Defers loading until the image is about to scroll into view.
+    Lazy,
+}
+
+/// Decoding hint of an image.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Decoding {
+    /// Decodes the image together with the rest of the content.
+    Sync,
+
+    /// Allows other content to be shown before the image is decoded.
+    Async,
+
+    /// Lets the browser decide, the default.
+    Auto,
+}
+
+// <iframe>
+pub struct Iframe;
+
+/// Embeds another document into the page.
+pub fn iframe() -> Node<Iframe, Open> {
+    Node::new_small("iframe")
+}
+
+impl HasSrc for Node<Iframe, Open> {}
+
+impl Node<Iframe, Open> {
+    /// Renders `content` as the document shown in the frame.
+    ///
+    /// The markup is escaped once more for the attribute, so text that's already escaped in `content` ends up
+    /// escaped twice, which is what the browser expects.
+    ///
+    /// ```
+    /// use lira::prelude::*;
+    ///
+    /// let res = iframe()
+    ///     .sandbox("")
+    ///     .srcdoc(p().class("preview").text("Fish & Chips"))
+    ///     .render();
+    ///
+    /// assert_eq!(
+    ///     "<iframe sandbox=\"\" srcdoc=\"&lt;p class=&quot;preview&quot;&gt;Fish &amp;amp; Chips&lt;/p&gt;\"></iframe>",
+    ///     res
+    /// );
+    /// ```
+    pub fn srcdoc(self, content: impl Renderable) -> Self {
+        let mut buf = Vec::new();
+        render_child(content, &mut buf);
+        let html = std::str::from_utf8(&buf).expect("render_child writes valid UTF-8");
+        self.attr("srcdoc", html)
+    }
+
+    /// Restrictions for the framed document, `""` applies all of them, e.g. `"allow-scripts allow-forms"` lifts
+    /// some.
+    pub fn sandbox(self, value: impl AsRef<str>) -> Self {
+        self.attr("sandbox", value)
+    }
+
+    /// Permissions policy of the frame, e.g. `"fullscreen; clipboard-write"`.
+    pub fn allow(self, value: impl AsRef<str>) -> Self {
+        self.attr("allow", value)
+    }
+
+    /// Name of the frame, used as the `target` of links and forms.
+    pub fn name(self, value: impl AsRef<str>) -> Self {
+        self.attr("name", value)
+    }
+
+    /// When the frame should be loaded.
+    pub fn loading(self, value: Loading) -> Self {
+        let value = match value {
+            Loading::Eager => "eager",
+            Loading::Lazy => "lazy",
+        };
+        self.attr("loading", value)
+    }
+}
+
+// <picture>
+pub struct Picture;
+
+/// Contains `<source>` elements and an `<img>`, letting the browser pick the best format and size.
+pub fn picture() -> Node<Picture, Open> {
+    Node::new("picture")
+}
+
+impl CanAddChildren for Picture {}
+
+// <source>
+pub struct Source;
+
+/// Alternative resource of a `<picture>`, `<video>` or `<audio>` element.
+pub fn source() -> Node<Source, Void> {
+    Node::new_self_closing("source")
+}
+
+impl HasSrc for Node<Source, Void> {}
+
+impl Node<Source, Void> {
+    /// MIME type of the resource, browsers skip sources of types they don't support.
+    pub fn type_(self, value: impl AsRef<str>) -> Self {
+        self.attr("type", value)
+    }
+
+    pub fn srcset(self, value: impl AsRef<str>) -> Self {
+        self.attr("srcset", value)
+    }
+
+    pub fn sizes(self, value: impl AsRef<str>) -> Self {
+        self.attr("sizes", value)
+    }
+
+    /// Media query the source is used for.
+    pub fn media(self, value: impl AsRef<str>) -> Self {
+        self.attr("media", value)
+    }
+
+    pub fn width(self, value: i32) -> Self {
+        self.attr_int("width", value)
+    }
+
+    pub fn height(self, value: i32) -> Self {
+        self.attr_int("height", value)
     }
 }
 
@@ -545,11 +919,31 @@ impl Node<Input, Void> {
         self.attr("value", value)
     }
 
+    /// Earliest acceptable value, e.g. a date for date inputs.
+    pub fn min(self, value: impl DateTimeValue) -> Self {
+        self.attr("min", value.to_datetime_string())
+    }
+
+    /// Latest acceptable value, e.g. a date for date inputs.
+    pub fn max(self, value: impl DateTimeValue) -> Self {
+        self.attr("max", value.to_datetime_string())
+    }
+
     /// Indicates that the input should be checked by default (for checkboxes/radios).
     pub fn checked(self) -> Self {
         self.flag("checked")
     }
 
+    /// Hint shown while the input is empty.
+    pub fn placeholder(self, value: impl AsRef<str>) -> Self {
+        self.attr("placeholder", value)
+    }
+
+    /// Requires a value before the form can be submitted.
+    pub fn required(self) -> Self {
+        self.flag("required")
+    }
+
     /// Specifies accepted file types for file input.
     pub fn accept(self, value: impl AsRef<str>) -> Self {
         self.attr("accept", value)
@@ -622,12 +1016,22 @@ impl Node<Textarea, Open> {
 
     /// Number of visible rows.
     pub fn rows(self, value: i32) -> Self {
-        self.attr("rows", value.to_string())
+        self.attr_int("rows", value)
     }
 
     /// Number of visible columns.
     pub fn cols(self, value: i32) -> Self {
-        self.attr("cols", value.to_string())
+        self.attr_int("cols", value)
+    }
+
+    /// Hint shown while the textarea is empty.
+    pub fn placeholder(self, value: impl AsRef<str>) -> Self {
+        self.attr("placeholder", value)
+    }
+
+    /// Requires a value before the form can be submitted.
+    pub fn required(self) -> Self {
+        self.flag("required")
     }
 
     /// Disables the textarea.
@@ -667,6 +1071,11 @@ impl Node<Select, Open> {
         self.flag("multiple")
     }
 
+    /// Requires a selection before the form can be submitted.
+    pub fn required(self) -> Self {
+        self.flag("required")
+    }
+
     /// Enables browser autocomplete.
     pub fn autocomplete(self, value: bool) -> Self {
         self.attr("autocomplete", if value { "on" } else { "off" })
@@ -678,7 +1087,12 @@ pub struct OptionElement;
 
 /// Represents a single option inside a <select>.
 pub fn option() -> Node<OptionElement, Open> {
-    Node::new("option")
+    Node::new_small("option")
+}
+
+/// Short for [`option`], for code where `option` reads like the `Option` type or clashes with a local name.
+pub fn opt() -> Node<OptionElement, Open> {
+    option()
 }
 
 impl CanAddText for OptionElement {}
@@ -700,6 +1114,28 @@ impl Node<OptionElement, Open> {
     }
 }
 
+// <output>
+pub struct Output;
+
+/// Represents the result of a calculation or user action, e.g. the current value of a slider.
+pub fn output() -> Node<Output, Open> {
+    Node::new_small("output")
+}
+
+impl CanAddText for Output {}
+
+impl Node<Output, Open> {
+    /// Ids of the controls the result is calculated from.
+    pub fn for_(self, value: impl AsRef<str>) -> Self {
+        self.attr("for", value)
+    }
+
+    /// Name of the element when the form is submitted.
+    pub fn name(self, value: impl AsRef<str>) -> Self {
+        self.attr("name", value)
+    }
+}
+
 // <header>
 pub struct Header;
 
@@ -782,7 +1218,7 @@ pub struct Code;
 
 /// Represents a fragment of computer code.
 pub fn code() -> Node<Code, Open> {
-    Node::new("code")
+    Node::new_small("code")
 }
 
 impl CanAddChildren for Code {}
@@ -824,7 +1260,7 @@ pub struct B;
 
 /// Represents text with bold importance.
 pub fn b() -> Node<B, Open> {
-    Node::new("b")
+    Node::new_small("b")
 }
 
 impl CanAddChildren for B {}
@@ -835,7 +1271,7 @@ pub struct I;
 
 /// Represents text in italics, usually for emphasis or stylistic purposes.
 pub fn i() -> Node<I, Open> {
-    Node::new("i")
+    Node::new_small("i")
 }
 
 impl CanAddChildren for I {}
@@ -846,7 +1282,7 @@ pub struct U;
 
 /// Represents text that should be stylistically underlined.
 pub fn u() -> Node<U, Open> {
-    Node::new("u")
+    Node::new_small("u")
 }
 
 impl CanAddChildren for U {}
@@ -857,7 +1293,7 @@ pub struct Strong;
 
 /// Represents text with strong importance.
 pub fn strong() -> Node<Strong, Open> {
-    Node::new("strong")
+    Node::new_small("strong")
 }
 
 impl CanAddChildren for Strong {}
@@ -868,12 +1304,30 @@ pub struct Small;
 
 /// Represents smaller print text.
 pub fn small() -> Node<Small, Open> {
-    Node::new("small")
+    Node::new_small("small")
 }
 
 impl CanAddChildren for Small {}
 impl CanAddText for Small {}
 
+// <time>
+pub struct Time;
+
+/// Represents a specific period in time, with a machine-readable `datetime`.
+pub fn time() -> Node<Time, Open> {
+    Node::new_small("time")
+}
+
+impl CanAddChildren for Time {}
+impl CanAddText for Time {}
+
+impl Node<Time, Open> {
+    /// Machine-readable date and/or time of the element.
+    pub fn datetime(self, value: impl DateTimeValue) -> Self {
+        self.attr("datetime", value.to_datetime_string())
+    }
+}
+
 // <label>
 pub struct Label;
 
@@ -915,7 +1369,7 @@ pub struct Summary;
 
 /// Represents a summary, visible in a <details> element.
 pub fn summary() -> Node<Summary, Open> {
-    Node::new("summary")
+    Node::new_small("summary")
 }
 
 impl CanAddChildren for Summary {}
@@ -944,7 +1398,7 @@ pub struct Hr;
 
 /// Represents a thematic break (horizontal rule).
 pub fn hr() -> Node<Hr, Void> {
-    Node::new_self_closing("hr")
+    Node::new_small_self_closing("hr")
 }
 
 // <base>
@@ -952,7 +1406,7 @@ pub struct Base;
 
 /// Specifies the base URL and target for relative URLs.
 pub fn base() -> Node<Base, Void> {
-    Node::new_self_closing("base")
+    Node::new_small_self_closing("base")
 }
 
 impl HasHref for Node<Base, Void> {}
@@ -961,12 +1415,16 @@ impl HasTarget for Node<Base, Void> {}
 // <script>
 pub struct Script;
 
+/// Embeds executable code or data.
+///
+/// Gets the CSP nonce of the active render context, if there is one.
 pub fn script() -> Node<Script, Open> {
-    Node::new("script")
+    with_context_nonce(Node::new("script"))
 }
 
 impl CanAddText for Script {}
 impl HasSrc for Node<Script, Open> {}
+impl HasCrossOrigin for Node<Script, Open> {}
 
 impl Node<Script, Open> {
     /// Executes the script asynchronously.
@@ -984,12 +1442,28 @@ impl Node<Script, Open> {
         self.attr("integrity", value)
     }
 
+    /// Cryptographic nonce used by the Content Security Policy to allow this script.
+    pub fn nonce(self, value: impl AsRef<str>) -> Self {
+        self.attr("nonce", value)
+    }
+
     /// Type of script: "importmap", "module", ...
     pub fn type_(self, value: impl AsRef<str>) -> Self {
         self.attr("type", value)
     }
 }
 
+// <noscript>
+pub struct Noscript;
+
+/// Content shown when scripting is disabled, e.g. a fallback for a stylesheet loaded by a script.
+pub fn noscript() -> Node<Noscript, Open> {
+    Node::new_small("noscript")
+}
+
+impl CanAddChildren for Noscript {}
+impl CanAddText for Noscript {}
+
 // <ul>
 pub struct Ul;
 
@@ -1012,12 +1486,44 @@ impl CanAddChildren for Ol {}
 pub struct Li;
 
 pub fn li() -> Node<Li, Open> {
-    Node::new("li")
+    Node::new_small("li")
 }
 
 impl CanAddChildren for Li {}
 impl CanAddText for Li {}
 
+// <dl>
+pub struct Dl;
+
+/// Represents a description list of term and description groups.
+pub fn dl() -> Node<Dl, Open> {
+    Node::new("dl")
+}
+
+impl CanAddChildren for Dl {}
+
+// <dt>
+pub struct Dt;
+
+/// Represents a term in a description list.
+pub fn dt() -> Node<Dt, Open> {
+    Node::new_small("dt")
+}
+
+impl CanAddChildren for Dt {}
+impl CanAddText for Dt {}
+
+// <dd>
+pub struct Dd;
+
+/// Represents the description of the preceding term in a description list.
+pub fn dd() -> Node<Dd, Open> {
+    Node::new_small("dd")
+}
+
+impl CanAddChildren for Dd {}
+impl CanAddText for Dd {}
+
 // <table>
 pub struct Table;
 
@@ -1027,6 +1533,17 @@ pub fn table() -> Node<Table, Open> {
 
 impl CanAddChildren for Table {}
 
+// <caption>
+pub struct Caption;
+
+/// Title of a table, which has to be its first child.
+pub fn caption() -> Node<Caption, Open> {
+    Node::new_small("caption")
+}
+
+impl CanAddChildren for Caption {}
+impl CanAddText for Caption {}
+
 // <thead>
 pub struct THead;
 
@@ -1067,7 +1584,7 @@ impl CanAddChildren for Tr {}
 pub struct Th;
 
 pub fn th() -> Node<Th, Open> {
-    Node::new("th")
+    Node::new_small("th")
 }
 
 impl CanAddChildren for Th {}
@@ -1077,8 +1594,92 @@ impl CanAddText for Th {}
 pub struct Td;
 
 pub fn td() -> Node<Td, Open> {
-    Node::new("td")
+    Node::new_small("td")
 }
 
 impl CanAddChildren for Td {}
 impl CanAddText for Td {}
+
+// element impls, so ElementWriter can build the elements above in their parent's buffer
+
+macro_rules! writer_methods {
+    ($($tag:ident => $name:ident),* $(,)?) => {
+        impl ElementWriter {
+            $(
+                #[doc = concat!("Builds a `<", stringify!($name), ">` in place, see [`element`](ElementWriter::element).")]
+                pub fn $name<R>(&mut self, fun: impl FnOnce(Node<$tag, <$tag as Element>::State>) -> R) -> &mut Self
+                where
+                    R: IntoBuffer,
+                {
+                    self.element::<$tag, _, R>(fun)
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! elements {
+    ($($tag:ident => $name:ident),* $(,)?) => {
+        $(
+            impl Element for $tag {
+                type State = Open;
+
+                fn new_in(buf: Vec<u8>) -> Node<Self, Open> {
+                    Node::in_buffer(stringify!($name), buf)
+                }
+            }
+        )*
+
+        writer_methods!($($tag => $name),*);
+    };
+}
+
+macro_rules! void_elements {
+    ($($tag:ident => $name:ident),* $(,)?) => {
+        $(
+            impl Element for $tag {
+                type State = Void;
+
+                fn new_in(buf: Vec<u8>) -> Node<Self, Void> {
+                    Node::in_buffer_self_closing(stringify!($name), buf)
+                }
+            }
+        )*
+
+        writer_methods!($($tag => $name),*);
+    };
+}
+
+elements! {
+    Head => head, Title => title, Body => body, Div => div, Span => span, H1 => h1, H2 => h2,
+    H3 => h3, H4 => h4, H5 => h5, H6 => h6, Paragraph => p, Form => form, Button => button,
+    Textarea => textarea, Select => select, OptionElement => option, Header => header,
+    Footer => footer, Nav => nav, Section => section, Article => article, Aside => aside,
+    Main => main, Code => code, Pre => pre, A => a, B => b, I => i, U => u, Strong => strong,
+    Small => small, Time => time, Label => label, Details => details, Summary => summary,
+    Dialog => dialog, Ul => ul, Ol => ol, Li => li, Dl => dl, Dt => dt, Dd => dd,
+    Table => table, Caption => caption, THead => thead, TBody => tbody, TFoot => tfoot, Tr => tr, Th => th, Td => td,
+    Picture => picture, Noscript => noscript, Iframe => iframe,
+}
+
+void_elements! {
+    Link => link, Meta => meta, Img => img, Input => input, Hr => hr, Base => base, Source => source,
+}
+
+impl Element for Script {
+    type State = Open;
+
+    fn new_in(buf: Vec<u8>) -> Node<Self, Open> {
+        with_context_nonce(Node::in_buffer("script", buf))
+    }
+}
+
+impl Element for Style {
+    type State = Open;
+
+    fn new_in(buf: Vec<u8>) -> Node<Self, Open> {
+        with_context_nonce(Node::in_buffer("style", buf))
+    }
+}
+
+writer_methods!(Script => script, Style => style);
@@ -0,0 +1,112 @@
+use crate::core::{self, HasAttributes, Renderable};
+use crate::html5::div;
+
+/// How an out-of-band fragment is swapped into its target, see [`WsMessage::swap`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OobSwap {
+    /// Replaces the target with the fragment.
+    OuterHtml,
+
+    /// Replaces the content of the target with the fragment.
+    InnerHtml,
+
+    /// Inserts the fragment before the target.
+    BeforeBegin,
+
+    /// Inserts the fragment before the first child of the target.
+    AfterBegin,
+
+    /// Appends the fragment to the content of the target, e.g. a new chat message.
+    BeforeEnd,
+
+    /// Inserts the fragment after the target.
+    AfterEnd,
+
+    /// Removes the target, the fragment is ignored.
+    Delete,
+}
+
+impl OobSwap {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OobSwap::OuterHtml => "outerHTML",
+            OobSwap::InnerHtml => "innerHTML",
+            OobSwap::BeforeBegin => "beforebegin",
+            OobSwap::AfterBegin => "afterbegin",
+            OobSwap::BeforeEnd => "beforeend",
+            OobSwap::AfterEnd => "afterend",
+            OobSwap::Delete => "delete",
+        }
+    }
+}
+
+/// A message for htmx's WebSocket extension, built with [`ws_message`].
+#[derive(Default)]
+pub struct WsMessage {
+    buf: Vec<u8>,
+}
+
+/// Starts a message for the `ws` extension of htmx, which swaps every top level element of a message into the
+/// element of the page with the same id.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = ws_message()
+///     .fragment(span().id("online").text("3 online"))
+///     .swap(OobSwap::BeforeEnd, "messages", p().text("Hi!"))
+///     .render();
+///
+/// assert_eq!(
+///     "<span id=\"online\">3 online</span><div hx-swap-oob=\"beforeend:#messages\"><p>Hi!</p></div>",
+///     res
+/// );
+/// ```
+pub fn ws_message() -> WsMessage {
+    WsMessage::default()
+}
+
+impl WsMessage {
+    /// Adds a fragment replacing the element with the same id, the fragment has to have one.
+    pub fn fragment(mut self, content: impl Renderable) -> Self {
+        core::render_child(content, &mut self.buf);
+        self
+    }
+
+    /// Adds `content` to be swapped into the element with the id `target` the way `swap` says.
+    ///
+    /// `content` is wrapped in a `<div>` carrying the swap, htmx only swaps in what's inside it.
+    pub fn swap(
+        mut self,
+        swap: OobSwap,
+        target: impl AsRef<str>,
+        content: impl Renderable,
+    ) -> Self {
+        let node = div()
+            .attr(
+                "hx-swap-oob",
+                format!("{}:#{}", swap.as_str(), target.as_ref()),
+            )
+            .child(content);
+        node.render_into(&mut self.buf);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+impl Renderable for WsMessage {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.buf);
+    }
+
+    fn render(self) -> String {
+        core::finish(self.buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
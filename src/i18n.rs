@@ -0,0 +1,58 @@
+use fluent_bundle::concurrent::FluentBundle;
+pub use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+
+use crate::context;
+use crate::core::{CanAddText, Content, Node, Open};
+
+/// Bundle holding the messages of a single locale, shareable between threads.
+pub type Bundle = FluentBundle<FluentResource>;
+
+/// Resolves the message `key` through the bundle of the active render context.
+///
+/// Falls back to the key itself if there is no bundle or the message doesn't exist, so missing translations are
+/// visible on the page instead of silently rendering nothing.
+pub fn t(key: &str, args: Option<&FluentArgs>) -> String {
+    context::current_bundle()
+        .and_then(|bundle| format_message(&bundle, key, args))
+        .unwrap_or_else(|| key.to_string())
+}
+
+fn format_message(bundle: &Bundle, key: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let pattern = bundle.get_message(key)?.value()?;
+    let mut errors = Vec::new();
+    Some(
+        bundle
+            .format_pattern(pattern, args, &mut errors)
+            .into_owned(),
+    )
+}
+
+impl<Tag> Node<Tag, Open>
+where
+    Tag: CanAddText,
+{
+    /// Adds the translated message `key` as escaped text.
+    pub fn t(self, key: &str) -> Node<Tag, Content> {
+        self.text(t(key, None))
+    }
+
+    /// Adds the translated message `key`, formatted with `args`, as escaped text.
+    pub fn t_with(self, key: &str, args: &FluentArgs) -> Node<Tag, Content> {
+        self.text(t(key, Some(args)))
+    }
+}
+
+impl<Tag> Node<Tag, Content>
+where
+    Tag: CanAddText,
+{
+    /// Adds the translated message `key` as escaped text.
+    pub fn t(self, key: &str) -> Self {
+        self.text(t(key, None))
+    }
+
+    /// Adds the translated message `key`, formatted with `args`, as escaped text.
+    pub fn t_with(self, key: &str, args: &FluentArgs) -> Self {
+        self.text(t(key, Some(args)))
+    }
+}
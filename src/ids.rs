@@ -0,0 +1,203 @@
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::context;
+
+/// Value of an `id` attribute, pass it to everything referencing the element so the references can't go stale.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = RenderContext::new().scope(|| {
+///     let menu = IdGen::new("menu").next();
+///
+///     div()
+///         .child(button().aria_controls(&menu).text("Menu"))
+///         .child(ul().id(&menu).child(li().child(a().href_fragment(&menu).text("Top"))))
+///         .render()
+/// });
+///
+/// assert!(res.contains("aria-controls=\"menu-1\""));
+/// assert!(res.contains("<ul id=\"menu-1\">"));
+/// assert!(res.contains("href=\"#menu-1\""));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Id(String);
+
+impl Id {
+    /// A fixed id, e.g. for anchors that are linked to from other pages.
+    pub fn new(value: impl AsRef<str>) -> Self {
+        Id(value.as_ref().to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The `#id` URL fragment pointing at the element.
+    pub fn href(&self) -> String {
+        format!("#{}", self.0)
+    }
+}
+
+impl AsRef<str> for Id {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Generates unique [`Id`]s made of a prefix and a number.
+///
+/// All generators count with the same counter of the active [`RenderContext`](context::RenderContext), which
+/// starts at 1 in every scope so a page renders with the same ids every time. Outside of a context a counter of
+/// the current thread is used instead.
+#[derive(Debug, Clone)]
+pub struct IdGen {
+    prefix: String,
+}
+
+thread_local! {
+    static COUNTER: Cell<usize> = const { Cell::new(0) };
+}
+
+impl IdGen {
+    pub fn new(prefix: impl AsRef<str>) -> Self {
+        IdGen {
+            prefix: prefix.as_ref().to_string(),
+        }
+    }
+
+    pub fn next(&self) -> Id {
+        let number = context::next_id_number().unwrap_or_else(|| {
+            COUNTER.with(|counter| {
+                counter.set(counter.get() + 1);
+                counter.get()
+            })
+        });
+        Id(format!("{}-{}", self.prefix, number))
+    }
+}
+
+/// Generates an id with the given prefix, see [`IdGen`].
+pub fn unique_id(prefix: impl AsRef<str>) -> Id {
+    IdGen::new(prefix).next()
+}
+
+/// `text` as an `id`, lowercased and with everything but letters and digits turned into single dashes, so
+/// `"What's new?"` becomes `whats-new`.
+pub(crate) fn slug(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if matches!(c, '\'' | '’') {
+            // part of the word, "what's" reads better as `whats` than `what-s`
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let len = slug.trim_end_matches('-').len();
+    slug.truncate(len);
+    slug
+}
+
+/// Like [`slug`], but `fallback` for text without letters or digits.
+pub(crate) fn slug_or(text: &str, fallback: &str) -> String {
+    match slug(text) {
+        slug if slug.is_empty() => fallback.to_string(),
+        slug => slug,
+    }
+}
+
+/// Hands out slugs that are unique within a page, numbering repeated ones `intro`, `intro-2`, `intro-3`.
+#[derive(Debug, Default)]
+pub(crate) struct Slugs {
+    taken: HashSet<String>,
+}
+
+impl Slugs {
+    /// Marks an id the page already has as taken.
+    pub(crate) fn reserve(&mut self, id: &str) {
+        self.taken.insert(id.to_string());
+    }
+
+    /// The slug of `text`, or `fallback` for text without letters or digits.
+    pub(crate) fn unique(&mut self, text: &str, fallback: &str) -> String {
+        self.number(&slug_or(text, fallback))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.taken.len()
+    }
+
+    /// `base` as it is if it's free, numbered otherwise.
+    pub(crate) fn number(&mut self, base: &str) -> String {
+        let candidate = self.peek(base, |_| false);
+        self.taken.insert(candidate.clone());
+        candidate
+    }
+
+    /// Like [`unique`](Self::unique) but also skips the ids `other` handed out, so headings don't repeat the id
+    /// of a control rendered before them.
+    pub(crate) fn unique_besides(
+        &mut self,
+        text: &str,
+        fallback: &str,
+        other: &ControlIds,
+    ) -> String {
+        let candidate = self.peek(&slug_or(text, fallback), |id| {
+            other.slugs.taken.contains(id)
+        });
+        self.taken.insert(candidate.clone());
+        candidate
+    }
+
+    // the id `number` would hand out next
+    fn peek(&self, base: &str, also_taken: impl Fn(&str) -> bool) -> String {
+        let mut candidate = base.to_string();
+        let mut n = 1;
+        while self.taken.contains(&candidate) || also_taken(&candidate) {
+            n += 1;
+            candidate = format!("{}-{}", base, n);
+        }
+        candidate
+    }
+}
+
+/// Ids of form controls, numbered like [`Slugs`] but kept apart from heading slugs so a heading can't move a
+/// control off the id the error summary links to.
+#[derive(Debug, Default)]
+pub(crate) struct ControlIds {
+    slugs: Slugs,
+    // id the first control with a base got
+    first: HashMap<String, String>,
+}
+
+impl ControlIds {
+    pub(crate) fn number(&mut self, base: &str) -> String {
+        let id = self.slugs.number(base);
+        self.first
+            .entry(base.to_string())
+            .or_insert_with(|| id.clone());
+        id
+    }
+
+    /// The id of the first control with `base`, or the one it is going to get if it wasn't rendered yet.
+    pub(crate) fn first(&self, base: &str) -> String {
+        match self.first.get(base) {
+            Some(id) => id.clone(),
+            None => self.slugs.peek(base, |_| false),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.slugs.len()
+    }
+}
@@ -0,0 +1,217 @@
+use crate::core::{self, Fragment, Node, Renderable, Void, fragment};
+use crate::html5::{Decoding, HasSrc, Img, Loading, img, picture, source};
+
+/// Image formats offered by a [`responsive_img`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImageFormat {
+    Avif,
+    Webp,
+    Jpeg,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ImageFormat::Avif => "image/avif",
+            ImageFormat::Webp => "image/webp",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Png => "image/png",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Avif => "avif",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+type UrlFn = dyn Fn(&str, Option<u32>, Option<ImageFormat>) -> String;
+
+/// Image built by [`responsive_img`].
+pub struct ResponsiveImage {
+    src: String,
+    alt: String,
+    widths: Vec<u32>,
+    formats: Vec<ImageFormat>,
+    sizes: Option<String>,
+    dimensions: Option<(u32, u32)>,
+    lazy: bool,
+    url: Box<UrlFn>,
+}
+
+/// Renders `src` as a `<picture>` offering every format in every width, with the original format as fallback.
+///
+/// Images are loaded lazily and decoded asynchronously unless [`eager`](ResponsiveImage::eager) is used, e.g. for
+/// the hero image. Set the intrinsic [`size`](ResponsiveImage::size) so the browser can reserve the space and the
+/// layout doesn't shift once the image is loaded.
+///
+/// The URLs of the variants are derived from `src`, `/img/hero.jpg` in 480 pixels as WebP becomes
+/// `/img/hero-480w.webp`, use [`url_with`](ResponsiveImage::url_with) if your image pipeline names them differently.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = responsive_img("/img/hero.jpg", "A mountain lake")
+///     .widths([480, 960])
+///     .formats([ImageFormat::Webp])
+///     .size(960, 640)
+///     .render();
+///
+/// assert_eq!(
+///     "<picture><source type=\"image/webp\" srcset=\"/img/hero-480w.webp 480w, /img/hero-960w.webp 960w\" />\
+///      <img src=\"/img/hero.jpg\" srcset=\"/img/hero-480w.jpg 480w, /img/hero-960w.jpg 960w\" \
+///      alt=\"A mountain lake\" width=\"960\" height=\"640\" loading=\"lazy\" decoding=\"async\" /></picture>",
+///     res
+/// );
+/// ```
+pub fn responsive_img(src: impl AsRef<str>, alt: impl AsRef<str>) -> ResponsiveImage {
+    ResponsiveImage {
+        src: src.as_ref().to_string(),
+        alt: alt.as_ref().to_string(),
+        widths: Vec::new(),
+        formats: vec![ImageFormat::Avif, ImageFormat::Webp],
+        sizes: None,
+        dimensions: None,
+        lazy: true,
+        url: Box::new(variant_url),
+    }
+}
+
+impl ResponsiveImage {
+    /// Widths in pixels the image is available in.
+    pub fn widths(mut self, widths: impl IntoIterator<Item = u32>) -> Self {
+        self.widths = widths.into_iter().collect();
+        self
+    }
+
+    /// Formats offered in addition to the one of `src`, in order of preference. AVIF and WebP by default.
+    pub fn formats(mut self, formats: impl IntoIterator<Item = ImageFormat>) -> Self {
+        self.formats = formats.into_iter().collect();
+        self
+    }
+
+    /// The `sizes` attribute, e.g. `"(max-width: 600px) 100vw, 50vw"`.
+    pub fn sizes(mut self, value: impl AsRef<str>) -> Self {
+        self.sizes = Some(value.as_ref().to_string());
+        self
+    }
+
+    /// Intrinsic width and height of the image in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.dimensions = Some((width, height));
+        self
+    }
+
+    /// Loads the image right away, for images that are visible without scrolling.
+    pub fn eager(mut self) -> Self {
+        self.lazy = false;
+        self
+    }
+
+    /// Builds the URL of a variant from `src`, its width and its format, `None` meaning the one of `src`.
+    pub fn url_with<F>(mut self, fun: F) -> Self
+    where
+        F: Fn(&str, Option<u32>, Option<ImageFormat>) -> String + 'static,
+    {
+        self.url = Box::new(fun);
+        self
+    }
+
+    fn srcset(&self, format: Option<ImageFormat>) -> String {
+        if self.widths.is_empty() {
+            return (self.url)(&self.src, None, format);
+        }
+
+        self.widths
+            .iter()
+            .map(|&width| format!("{} {}w", (self.url)(&self.src, Some(width), format), width))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn img(&self) -> Node<Img, Void> {
+        let node = img()
+            .src(&self.src)
+            .map_when(!self.widths.is_empty(), |node| {
+                node.srcset(self.srcset(None))
+            })
+            .map_when(self.sizes.is_some(), |node| {
+                node.sizes(self.sizes.as_deref().unwrap_or_default())
+            })
+            .alt(&self.alt);
+
+        let node = match self.dimensions {
+            Some((width, height)) => node.width(width as i32).height(height as i32),
+            None => node,
+        };
+
+        if self.lazy {
+            node.loading(Loading::Lazy).decoding(Decoding::Async)
+        } else {
+            node
+        }
+    }
+
+    fn sources(&self) -> Fragment {
+        fragment().children(&self.formats, |&format| {
+            source()
+                .type_(format.mime_type())
+                .srcset(self.srcset(Some(format)))
+                .map_when(self.sizes.is_some(), |node| {
+                    node.sizes(self.sizes.as_deref().unwrap_or_default())
+                })
+        })
+    }
+}
+
+impl Renderable for ResponsiveImage {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        picture()
+            .child(self.sources())
+            .child(self.img())
+            .render_into(buf);
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+// "/img/hero.jpg?v=2" -> "/img/hero-480w.webp?v=2"
+fn variant_url(src: &str, width: Option<u32>, format: Option<ImageFormat>) -> String {
+    let (path, suffix) = match src.find(['?', '#']) {
+        Some(i) => src.split_at(i),
+        None => (src, ""),
+    };
+    let file_start = path.rfind('/').map_or(0, |i| i + 1);
+    let (stem, ext) = match path[file_start..].rfind('.') {
+        Some(i) => (&path[..file_start + i], &path[file_start + i + 1..]),
+        None => (path, ""),
+    };
+
+    let mut url = stem.to_string();
+    if let Some(width) = width {
+        url.push_str(&format!("-{}w", width));
+    }
+    let ext = match format {
+        Some(format) => format.extension(),
+        None => ext,
+    };
+    if !ext.is_empty() {
+        url.push('.');
+        url.push_str(ext);
+    }
+    url.push_str(suffix);
+    url
+}
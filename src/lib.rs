@@ -1,7 +1,144 @@
+pub mod a11y;
+#[cfg(feature = "askama")]
+mod askama;
+pub mod assets;
+#[cfg(feature = "a11y-audit")]
+pub mod audit;
+#[cfg(feature = "http-body")]
+pub mod body;
+pub mod color;
+#[cfg(feature = "gzip")]
+pub mod compress;
+pub mod context;
 pub mod core;
+pub mod critical;
+pub mod css;
+pub mod datetime;
+#[cfg(feature = "debug")]
+pub mod debug;
+pub mod document;
+#[cfg(feature = "wasm")]
+pub mod dom;
+pub mod email;
+pub mod embed;
+pub mod entities;
+pub mod errors;
+pub mod etag;
+pub mod forms;
+pub mod head;
 pub mod html5;
+pub mod htmx;
+#[cfg(feature = "fluent")]
+pub mod i18n;
+pub mod ids;
+pub mod images;
+mod json;
+pub mod lists;
+#[cfg(feature = "maud")]
+pub mod maud;
+pub mod memo;
+pub mod minify;
+mod named_references;
+pub mod nav;
+pub mod pool;
+pub mod segments;
+mod selector;
+#[cfg(feature = "serde")]
+mod ser;
+pub mod sri;
+pub mod sse;
+pub mod stats;
+pub mod svg;
+pub mod table;
+#[cfg(feature = "tailwind")]
+pub mod tailwind;
+pub mod testing;
+pub mod text;
+pub mod toc;
+mod tokenizer;
+#[cfg(feature = "tower")]
+pub mod tower;
+pub mod transform;
+pub mod tree;
+pub mod url;
 
+/// Everything at once, `use lira::prelude::*`.
+///
+/// For smaller imports there are preludes with parts of it, like [`mod@prelude::html`] for just the elements.
 pub mod prelude {
+    /// The HTML elements and what's needed to build and render them.
+    pub mod html {
+        pub use crate::core::{
+            Fragment, HasAttributes, IntoAny, RenderFmt, Renderable, either, empty, fragment,
+            repeat,
+        };
+        pub use crate::entities::*;
+        pub use crate::html5::*;
+    }
+
+    /// The attribute traits and the values they take, to set attributes on elements built elsewhere.
+    pub mod attrs {
+        pub use crate::core::HasAttributes;
+        pub use crate::html5::{
+            CrossOrigin, Decoding, FormMethod, HasCrossOrigin, HasGlobalAttributes, HasHref,
+            HasInputType, HasRel, HasSrc, HasTarget, HeadingLevel, InputType, LinkAs, Loading, Rel,
+            Target,
+        };
+    }
+
+    /// The SVG elements and sprite helpers.
+    pub mod svg {
+        pub use crate::core::{HasAttributes, Renderable};
+        pub use crate::html5::HasGlobalAttributes;
+        pub use crate::svg::*;
+    }
+
+    pub use crate::a11y::*;
+    pub use crate::assets::*;
+    #[cfg(feature = "a11y-audit")]
+    pub use crate::audit::RenderWithReport;
+    #[cfg(feature = "http-body")]
+    pub use crate::body::*;
+    pub use crate::color::*;
+    #[cfg(feature = "gzip")]
+    pub use crate::compress::RenderCompressed;
+    pub use crate::context::*;
     pub use crate::core::*;
+    pub use crate::critical::*;
+    pub use crate::css::*;
+    pub use crate::datetime::*;
+    #[cfg(feature = "debug")]
+    pub use crate::debug::*;
+    pub use crate::document::*;
+    pub use crate::embed::*;
+    pub use crate::entities::*;
+    pub use crate::errors::*;
+    pub use crate::etag::*;
+    pub use crate::forms::{
+        checkbox, csrf, csrf_field, labeled_input, number_input, radio_group, range_input,
+        range_with_output, select_options,
+    };
+    pub use crate::head::*;
     pub use crate::html5::*;
+    pub use crate::htmx::*;
+    #[cfg(feature = "fluent")]
+    pub use crate::i18n::*;
+    pub use crate::ids::*;
+    pub use crate::images::*;
+    pub use crate::lists::*;
+    pub use crate::memo::*;
+    pub use crate::nav::*;
+    pub use crate::pool::*;
+    pub use crate::segments::*;
+    pub use crate::sse::*;
+    pub use crate::stats::*;
+    pub use crate::svg::*;
+    pub use crate::table::{DataTable, TableCell, ToTableRow, csv_table, data_table, table_of};
+    #[cfg(feature = "tailwind")]
+    pub use crate::tailwind::*;
+    pub use crate::text::*;
+    pub use crate::toc::*;
+    pub use crate::transform::*;
+    pub use crate::tree::{RenderTree, Tree};
+    pub use crate::url::*;
 }
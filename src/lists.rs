@@ -0,0 +1,210 @@
+use crate::core::{Content, Fragment, Node, Open, Renderable};
+use crate::html5::{Dd, Dl, Ol, Ul, dd, dl, dt, li, ol, ul};
+
+#[cfg(feature = "derive")]
+pub use lira_derive::ToDescriptionList;
+
+/// A type that renders as the entries of a description list, usually derived for detail pages and debug views:
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use lira::prelude::*;
+/// use lira::lists::ToDescriptionList;
+///
+/// #[derive(ToDescriptionList)]
+/// struct Package {
+///     name: String,
+///     #[dl(rename = "Latest version")]
+///     version: String,
+///     homepage: Option<String>,
+///     #[dl(skip)]
+///     api_token: String,
+/// }
+///
+/// let package = Package {
+///     name: "lira".to_string(),
+///     version: "0.3.1".to_string(),
+///     homepage: None,
+///     api_token: String::new(),
+/// };
+///
+/// assert_eq!(
+///     "<dl><dt>Name</dt><dd>lira</dd><dt>Latest version</dt><dd>0.3.1</dd><dt>Homepage</dt><dd></dd></dl>",
+///     package.to_dl().render()
+/// );
+/// # }
+/// ```
+///
+/// Fields can be configured with `#[dl(...)]`:
+///
+/// - `rename = "..."` overrides the term, which defaults to the humanized field name
+/// - `with = "path"` renders the description with a function taking a reference to the field and returning a `<dd>`
+/// - `skip` leaves the field out
+///
+/// All other fields have to implement [`Description`].
+pub trait ToDescriptionList {
+    /// The `<dt>` and `<dd>` of each field.
+    fn entries(&self) -> Fragment;
+
+    /// The entries wrapped in a `<dl>`, add them to [`dl()`] directly for attributes on the list.
+    fn to_dl(&self) -> Node<Dl, Content> {
+        dl().child(self.entries())
+    }
+}
+
+impl<T> ToDescriptionList for &T
+where
+    T: ToDescriptionList + ?Sized,
+{
+    fn entries(&self) -> Fragment {
+        T::entries(self)
+    }
+}
+
+/// Values that can be rendered as the description of a term.
+pub trait Description {
+    fn description(&self) -> Node<Dd, Content>;
+}
+
+impl Description for str {
+    fn description(&self) -> Node<Dd, Content> {
+        dd().text(self)
+    }
+}
+
+impl<T> Description for &T
+where
+    T: Description + ?Sized,
+{
+    fn description(&self) -> Node<Dd, Content> {
+        T::description(self)
+    }
+}
+
+/// Renders an empty description for `None`.
+impl<T> Description for Option<T>
+where
+    T: Description,
+{
+    fn description(&self) -> Node<Dd, Content> {
+        match self {
+            Some(value) => value.description(),
+            None => dd().close(),
+        }
+    }
+}
+
+macro_rules! display_descriptions {
+    ($($ty:ty),*) => {
+        $(
+            impl Description for $ty {
+                fn description(&self) -> Node<Dd, Content> {
+                    dd().text_fmt(format_args!("{}", self))
+                }
+            }
+        )*
+    };
+}
+
+display_descriptions!(
+    String, char, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+impl Node<Dl, Open> {
+    /// Adds a `<dt>` and `<dd>` for each `(term, description)` pair.
+    pub fn entries_of<It, T, D>(self, entries: It) -> Node<Dl, Content>
+    where
+        It: IntoIterator<Item = (T, D)>,
+        T: AsRef<str>,
+        D: AsRef<str>,
+    {
+        let mut node = self.close();
+        for (term, description) in entries {
+            node = node.child(dt().text(term)).child(dd().text(description));
+        }
+        node
+    }
+}
+
+/// Renders `(term, description)` pairs as a description list, use [`entries_of`](Node::entries_of) to add
+/// attributes to the `<dl>`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<dl><dt>Version</dt><dd>0.3.1</dd><dt>License</dt><dd>MIT</dd></dl>",
+///     dl_of([("Version", "0.3.1"), ("License", "MIT")]).render()
+/// );
+/// ```
+pub fn dl_of<It, T, D>(entries: It) -> Node<Dl, Content>
+where
+    It: IntoIterator<Item = (T, D)>,
+    T: AsRef<str>,
+    D: AsRef<str>,
+{
+    dl().entries_of(entries)
+}
+
+macro_rules! items_of {
+    ($($tag:ty),*) => {
+        $(
+            impl Node<$tag, Open> {
+                /// Adds an `<li>` for each item, holding what `fun` renders for it.
+                pub fn items_of<It, Fn, T, R>(self, items: It, mut fun: Fn) -> Node<$tag, Content>
+                where
+                    It: IntoIterator<Item = T>,
+                    Fn: FnMut(T) -> R,
+                    R: Renderable,
+                {
+                    self.children(items, |item| li().child(fun(item)))
+                }
+            }
+        )*
+    };
+}
+
+items_of!(Ul, Ol);
+
+/// Renders each item as an `<li>` of an unordered list, use [`items_of`](Node::items_of) to add attributes to
+/// the `<ul>`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let tags = ["rust", "html"];
+///
+/// assert_eq!(
+///     "<ul><li><a href=\"/tags/rust\">rust</a></li><li><a href=\"/tags/html\">html</a></li></ul>",
+///     ul_of(tags, |tag| a().href(format!("/tags/{}", tag)).text(tag)).render()
+/// );
+/// ```
+pub fn ul_of<It, Fn, T, R>(items: It, fun: Fn) -> Node<Ul, Content>
+where
+    It: IntoIterator<Item = T>,
+    Fn: FnMut(T) -> R,
+    R: Renderable,
+{
+    ul().items_of(items, fun)
+}
+
+/// Renders each item as an `<li>` of an ordered list, see [`ul_of`].
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let steps = ["Install", "Configure"];
+///
+/// assert_eq!(
+///     "<ol><li><strong>Install</strong></li><li><strong>Configure</strong></li></ol>",
+///     ol_of(steps, |step| strong().text(step)).render()
+/// );
+/// ```
+pub fn ol_of<It, Fn, T, R>(items: It, fun: Fn) -> Node<Ol, Content>
+where
+    It: IntoIterator<Item = T>,
+    Fn: FnMut(T) -> R,
+    R: Renderable,
+{
+    ol().items_of(items, fun)
+}
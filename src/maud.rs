@@ -0,0 +1,74 @@
+use ::maud::{PreEscaped, Render};
+
+use crate::core::{self, Content, Fragment, Node, Renderable, Void};
+
+// lira nodes spliced into maud templates, `html! { main { (sidebar) } }`. maud only borrows what it renders, so
+// this writes out the buffer as it is, without the post-processing of the render context
+
+impl<Tag> Render for Node<Tag, Content> {
+    fn render_to(&self, buffer: &mut String) {
+        buffer.push_str(&String::from_utf8_lossy(self.as_bytes()));
+        buffer.push_str("</");
+        buffer.push_str(self.tag_name());
+        buffer.push('>');
+    }
+}
+
+impl<Tag> Render for Node<Tag, Void> {
+    fn render_to(&self, buffer: &mut String) {
+        buffer.push_str(&String::from_utf8_lossy(self.as_bytes()));
+        buffer.push_str(&String::from_utf8_lossy(core::void_end()));
+    }
+}
+
+impl Render for Fragment {
+    fn render_to(&self, buffer: &mut String) {
+        buffer.push_str(&String::from_utf8_lossy(self.as_bytes()));
+    }
+}
+
+/// maud markup as a child, inserted as it is since maud already escaped it.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let legacy = maud::html! { p.lead { "Written " em { "in maud" } } };
+/// let res = article().child(h1().text("Post")).child(legacy).render();
+///
+/// assert_eq!(
+///     "<article><h1>Post</h1><p class=\"lead\">Written <em>in maud</em></p></article>",
+///     res
+/// );
+/// ```
+///
+/// The other way around, finished lira nodes can be used in `html!` like any other value:
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let nav = ul().child(li().text("Home"));
+/// let page = maud::html! { header { (nav) } };
+///
+/// assert_eq!("<header><ul><li>Home</li></ul></header>", page.into_string());
+/// ```
+///
+/// With `maud::Render` imported `.render()` is ambiguous for lira nodes, call it as `Renderable::render(node)`
+/// there.
+impl<T> Renderable for PreEscaped<T>
+where
+    T: AsRef<str>,
+{
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.0.as_ref().as_bytes());
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
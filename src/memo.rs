@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::context;
+use crate::core::{self, Renderable};
+
+/// Storage for the rendered partials of [`memo`], register it with
+/// [`fragment_cache`](crate::context::RenderContext::fragment_cache).
+///
+/// Implement it to keep the partials in a shared cache like Redis, [`MemoryCache`] keeps them in the process.
+pub trait FragmentCache: Send + Sync {
+    /// The HTML stored for `key`, unless there is none or it expired.
+    fn get(&self, key: &str) -> Option<Arc<str>>;
+
+    /// Stores `html` for `key`, to be dropped after `ttl`.
+    fn insert(&self, key: &str, html: Arc<str>, ttl: Duration);
+}
+
+/// In-process [`FragmentCache`], expired entries are dropped when they are looked up.
+#[derive(Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, (Instant, Arc<str>)>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored entries, including expired ones that weren't looked up since.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Drops all entries, e.g. after the content they were rendered from changed.
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    /// Drops the entry for `key`.
+    pub fn remove(&self, key: &str) {
+        self.entries().remove(key);
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Arc<str>)>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl FragmentCache for MemoryCache {
+    fn get(&self, key: &str) -> Option<Arc<str>> {
+        let mut entries = self.entries();
+        match entries.get(key) {
+            Some((expires, html)) if *expires > Instant::now() => Some(html.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: &str, html: Arc<str>, ttl: Duration) {
+        let expires = Instant::now() + ttl;
+        self.entries().insert(key.to_string(), (expires, html));
+    }
+}
+
+/// A partial rendered by [`memo`].
+pub struct Memo {
+    html: Arc<str>,
+}
+
+/// Renders the partial built by `fun` once and reuses the HTML for `ttl`, as long as the active context has a
+/// [`fragment_cache`](crate::context::RenderContext::fragment_cache). Without one it's rendered every time.
+///
+/// `key` has to cover everything the partial depends on, e.g. `format!("product-card:{}:{}", id, locale)`. Only
+/// the HTML is cached, so anything `fun` does besides building the partial, like registering
+/// [`require_stylesheet`](crate::document::require_stylesheet), doesn't happen when it comes from the cache.
+///
+/// Partials that are only valid for the current page aren't cached but rendered every time: those carrying the
+/// CSP [`nonce`](crate::context::RenderContext::nonce), e.g. in a `<script>`, and those using generated ids like
+/// [`unique_id`](crate::ids::unique_id), which could clash with the ids of the page they are reused in.
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use lira::prelude::*;
+///
+/// let cache = Arc::new(MemoryCache::new());
+///
+/// for _ in 0..2 {
+///     let res = RenderContext::new().fragment_cache(cache.clone()).scope(|| {
+///         div()
+///             .child(memo("sidebar", Duration::from_secs(60), || aside().text("Popular posts")))
+///             .render()
+///     });
+///
+///     assert_eq!("<div><aside>Popular posts</aside></div>", res);
+/// }
+///
+/// assert_eq!(1, cache.len());
+/// ```
+pub fn memo<Fn, R>(key: impl AsRef<str>, ttl: Duration, fun: Fn) -> Memo
+where
+    Fn: FnOnce() -> R,
+    R: Renderable,
+{
+    let Some(cache) = context::current_fragment_cache() else {
+        return Memo {
+            html: render(fun).into(),
+        };
+    };
+
+    let key = key.as_ref();
+    if let Some(html) = cache.get(key) {
+        return Memo { html };
+    }
+
+    let ids = context::handed_out_ids();
+    let html: Arc<str> = render(fun).into();
+    if !is_per_request(&html, ids) {
+        cache.insert(key, html.clone(), ttl);
+    }
+    Memo { html }
+}
+
+// partials with the CSP nonce or ids generated for this page can't be reused for another one
+fn is_per_request(html: &str, ids_before: usize) -> bool {
+    context::handed_out_ids() != ids_before
+        || context::current_nonce().is_some_and(|nonce| html.contains(nonce.as_str()))
+}
+
+// renders from within `fun` are part of the page the memo ends up in
+fn render<R: Renderable>(fun: impl FnOnce() -> R) -> String {
+    let mut buf = Vec::new();
+    context::nested(|| core::render_child(fun(), &mut buf));
+    String::from_utf8(buf).expect("render_child writes valid UTF-8")
+}
+
+impl Memo {
+    pub(crate) fn into_shared(self) -> Arc<str> {
+        self.html
+    }
+}
+
+impl Renderable for Memo {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.html.as_bytes());
+    }
+
+    fn render(self) -> String {
+        core::finish(self.html.as_bytes().to_vec())
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
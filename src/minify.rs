@@ -0,0 +1,288 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::tokenizer::{self, Attribute, Token};
+
+/// Minifies rendered HTML, for output mixing builder markup with indented templates inserted through `raw()`.
+///
+/// - whitespace in text is collapsed to a single space and dropped next to block level elements, except within
+///   `<pre>`, `<textarea>`, `<script>` and `<style>`. Elements that aren't rendered, like `<script>` and `<link>`,
+///   are skipped over, so the spaces on both sides of them become one
+/// - comments are removed, apart from the conditional comments of Outlook, see [`mso`](crate::email::mso)
+/// - attribute values are written without quotes where that's unambiguous, void elements without the slash
+///
+/// Text and attribute values are kept as they were written otherwise, character references included.
+///
+/// Enable [`minify`](crate::context::RenderContext::minify) on the render context to minify every render.
+///
+/// ```
+/// use lira::minify::minify;
+///
+/// let html = "<ul class=\"nav\">\n  <li>  Home  </li>\n  <!-- TODO -->\n  <li>Hello   <b>you</b></li>\n</ul>";
+///
+/// assert_eq!("<ul class=nav><li>Home</li><li>Hello <b>you</b></li></ul>", minify(html));
+/// ```
+pub fn minify(html: &str) -> String {
+    let tokens = tokenizer::tokenize(html);
+    // the tokens cover all of `html`, each ends where the next one starts
+    let ends: Vec<usize> = tokens
+        .iter()
+        .skip(1)
+        .map(|(offset, _)| *offset)
+        .chain([html.len()])
+        .collect();
+    let mut kept: Vec<(Cow<str>, Token)> = Vec::new();
+    for ((start, token), end) in tokens.into_iter().zip(ends) {
+        if is_removed_comment(&token) {
+            continue;
+        }
+        let src = &html[start..end];
+        // the text on both sides of a removed comment is one text
+        if let (Token::Text(_), Some((text, Token::Text(_)))) = (&token, kept.last_mut()) {
+            text.to_mut().push_str(src);
+            continue;
+        }
+        kept.push((Cow::Borrowed(src), token));
+    }
+
+    let mut out = String::with_capacity(html.len());
+    // depth of the elements whose whitespace is kept
+    let mut preformatted = 0usize;
+    // depth of `<template>` elements, their content isn't rendered where it is
+    let mut template = 0usize;
+    // whether the rendered text so far ends in a space, elements that aren't rendered don't count
+    let mut after_space = false;
+    // nothing in the head is rendered, so its whitespace can go
+    let mut in_head = false;
+
+    for (i, (src, token)) in kept.iter().enumerate() {
+        match token {
+            Token::Text(_) if preformatted > 0 => out.push_str(src),
+            Token::Text(_) if in_head && src.trim_ascii().is_empty() => {}
+            Token::Text(_) => {
+                let mut text = collapse_whitespace(src);
+                let before = i.checked_sub(1).and_then(|i| kept.get(i));
+                if is_block(before.map(|(_, token)| token)) {
+                    text = text.trim_start().to_string();
+                }
+                if is_block(kept.get(i + 1).map(|(_, token)| token)) {
+                    text = text.trim_end().to_string();
+                }
+                if template == 0 {
+                    if after_space {
+                        text = text.trim_start().to_string();
+                    }
+                    if !text.is_empty() {
+                        after_space = text.ends_with(' ');
+                    }
+                }
+                out.push_str(&text);
+            }
+            Token::StartTag {
+                name,
+                attrs,
+                spans,
+                self_closing,
+            } => {
+                if !HIDDEN_ELEMENTS.contains(&name.as_str()) {
+                    after_space = false;
+                }
+                in_head |= name == "head";
+                write_start_tag(&mut out, html, name, attrs, spans);
+                if tokenizer::is_void(name) {
+                    continue;
+                }
+                if *self_closing {
+                    write_end_tag(&mut out, name);
+                } else if is_preformatted(name) {
+                    preformatted += 1;
+                } else if name == "template" {
+                    template += 1;
+                }
+            }
+            Token::EndTag { name } => {
+                if !HIDDEN_ELEMENTS.contains(&name.as_str()) {
+                    after_space = false;
+                }
+                in_head &= name != "head";
+                if is_preformatted(name) {
+                    preformatted = preformatted.saturating_sub(1);
+                } else if name == "template" {
+                    template = template.saturating_sub(1);
+                }
+                write_end_tag(&mut out, name);
+            }
+            Token::Doctype(_) | Token::Comment(_) => {
+                after_space = false;
+                out.push_str(src);
+            }
+        }
+    }
+
+    out
+}
+
+// elements that aren't rendered
+const HIDDEN_ELEMENTS: &[&str] = &[
+    "base", "link", "meta", "script", "source", "style", "template",
+];
+
+// elements around which whitespace isn't rendered
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "caption",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "ul",
+];
+
+// text next to these or at the start or end of the document loses its leading or trailing whitespace
+fn is_block(token: Option<&Token>) -> bool {
+    match token {
+        Some(Token::StartTag { name, .. } | Token::EndTag { name }) => {
+            BLOCK_ELEMENTS.contains(&name.as_str())
+        }
+        Some(Token::Text(_) | Token::Comment(_)) => false,
+        Some(Token::Doctype(_)) | None => true,
+    }
+}
+
+fn is_preformatted(name: &str) -> bool {
+    matches!(name, "pre" | "textarea" | "script" | "style")
+}
+
+fn write_start_tag(
+    out: &mut String,
+    html: &str,
+    name: &str,
+    attrs: &[Attribute],
+    spans: &[Range<usize>],
+) {
+    out.push('<');
+    out.push_str(name);
+    for (attr, span) in attrs.iter().zip(spans) {
+        out.push(' ');
+        out.push_str(&attr.name);
+        if attr.value.is_some() {
+            out.push('=');
+            write_attr_value(out, raw_value(&html[span.clone()], attr.name.len()));
+        }
+    }
+    out.push('>');
+}
+
+fn write_end_tag(out: &mut String, name: &str) {
+    out.push_str("</");
+    out.push_str(name);
+    out.push('>');
+}
+
+// the value of `attr`, which is `name="value"` or similar as it was written, without quotes
+fn raw_value(attr: &str, name_len: usize) -> &str {
+    let value = attr[name_len..]
+        .trim_start_matches(|c: char| c.is_ascii_whitespace())
+        .strip_prefix('=')
+        .unwrap_or_default()
+        .trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+    match value.as_bytes().first() {
+        Some(b'"' | b'\'') if value.len() >= 2 => &value[1..value.len() - 1],
+        _ => value,
+    }
+}
+
+fn write_attr_value(out: &mut String, value: &str) {
+    let unquoted = !value.is_empty()
+        && !value.chars().any(|c| {
+            c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '=' | '<' | '>' | '`' | '&')
+        });
+    if unquoted {
+        out.push_str(value);
+        return;
+    }
+
+    // references in the value stay as they are, only a quote that would end the value early is escaped
+    let quote = if value.contains('"') && !value.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+    out.push(quote);
+    if quote == '"' {
+        out.push_str(&value.replace('"', "&quot;"));
+    } else {
+        out.push_str(value);
+    }
+    out.push(quote);
+}
+
+// Outlook's conditional comments, `<!--[if mso]>...<![endif]-->` and `<!--[if !mso]><!-->...<!--<![endif]-->`
+fn is_removed_comment(token: &Token) -> bool {
+    match token {
+        Token::Comment(comment) => {
+            !(comment.starts_with("[if")
+                || comment.starts_with("<![endif]")
+                || comment.ends_with("<![endif]"))
+        }
+        _ => false,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !in_whitespace {
+                out.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            out.push(c);
+            in_whitespace = false;
+        }
+    }
+    out
+}
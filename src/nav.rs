@@ -0,0 +1,287 @@
+use crate::core::{self, Content, HasAttributes, Node, Renderable};
+use crate::html5::{
+    HasGlobalAttributes, HasHref, Ul, a, details, li, nav, ol, script, summary, ul,
+};
+use crate::json::Value;
+
+/// Breadcrumb trail built by [`breadcrumbs`].
+pub struct Breadcrumbs {
+    items: Vec<(String, String)>,
+    label: String,
+    json_ld: bool,
+}
+
+/// Renders `(label, href)` items as an accessible breadcrumb trail, the last item is the current page.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = breadcrumbs([("Home", "/"), ("Blog", "/blog")]).render();
+///
+/// assert_eq!(
+///     "<nav aria-label=\"Breadcrumb\"><ol><li><a href=\"/\">Home</a></li>\
+///      <li><a href=\"/blog\" aria-current=\"page\">Blog</a></li></ol></nav>",
+///     res
+/// );
+/// ```
+pub fn breadcrumbs<It, L, H>(items: It) -> Breadcrumbs
+where
+    It: IntoIterator<Item = (L, H)>,
+    L: AsRef<str>,
+    H: AsRef<str>,
+{
+    Breadcrumbs {
+        items: items
+            .into_iter()
+            .map(|(label, href)| (label.as_ref().to_string(), href.as_ref().to_string()))
+            .collect(),
+        label: "Breadcrumb".to_string(),
+        json_ld: false,
+    }
+}
+
+impl Breadcrumbs {
+    /// Accessible name of the `<nav>`, "Breadcrumb" by default.
+    pub fn label(mut self, value: impl AsRef<str>) -> Self {
+        self.label = value.as_ref().to_string();
+        self
+    }
+
+    /// Also renders the trail as schema.org `BreadcrumbList` JSON-LD, so search engines can show it.
+    ///
+    /// Search engines expect absolute URLs in there, so use them as hrefs when enabling this.
+    pub fn json_ld(mut self) -> Self {
+        self.json_ld = true;
+        self
+    }
+
+    fn structured_data(&self) -> Value {
+        let items = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, (label, href))| {
+                Value::Object(vec![
+                    ("@type".to_string(), Value::String("ListItem".to_string())),
+                    ("position".to_string(), Value::Number((i + 1) as f64)),
+                    ("name".to_string(), Value::String(label.clone())),
+                    ("item".to_string(), Value::String(href.clone())),
+                ])
+            })
+            .collect();
+
+        Value::Object(vec![
+            (
+                "@context".to_string(),
+                Value::String("https://schema.org".to_string()),
+            ),
+            (
+                "@type".to_string(),
+                Value::String("BreadcrumbList".to_string()),
+            ),
+            ("itemListElement".to_string(), Value::Array(items)),
+        ])
+    }
+}
+
+impl Renderable for Breadcrumbs {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        let last = self.items.len().saturating_sub(1);
+        let json_ld = self.json_ld.then(|| self.structured_data());
+
+        nav()
+            .attr("aria-label", &self.label)
+            .child(
+                ol().children(self.items.iter().enumerate(), |(i, (label, href))| {
+                    li().child(
+                        a().href(href)
+                            .map_when(i == last, |node| node.attr("aria-current", "page"))
+                            .text(label),
+                    )
+                }),
+            )
+            .render_into(buf);
+
+        if let Some(json_ld) = json_ld {
+            script()
+                .type_("application/ld+json")
+                .raw(json_ld.to_string())
+                .render_into(buf);
+        }
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// Entry of a [`nav_menu`], either a link or a group of entries.
+pub struct MenuItem {
+    label: String,
+    href: Option<String>,
+    children: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    pub fn link(label: impl AsRef<str>, href: impl AsRef<str>) -> Self {
+        MenuItem {
+            label: label.as_ref().to_string(),
+            href: Some(href.as_ref().to_string()),
+            children: Vec::new(),
+        }
+    }
+
+    /// Sub-menu rendered as a `<details>` element, open if it contains the active item.
+    pub fn group(label: impl AsRef<str>, items: impl IntoIterator<Item = MenuItem>) -> Self {
+        MenuItem {
+            label: label.as_ref().to_string(),
+            href: None,
+            children: items.into_iter().collect(),
+        }
+    }
+}
+
+/// Navigation menu built by [`nav_menu`].
+pub struct NavMenu {
+    items: Vec<MenuItem>,
+    current_path: String,
+    label: Option<String>,
+    active_class: Option<String>,
+    match_prefix: bool,
+}
+
+/// Renders a menu of nested lists, marking the item linking to `current_path` with `aria-current="page"`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = nav_menu(
+///     [
+///         MenuItem::link("Home", "/"),
+///         MenuItem::group("Docs", [MenuItem::link("Install", "/docs/install")]),
+///     ],
+///     "/docs/install",
+/// )
+/// .render();
+///
+/// assert_eq!(
+///     "<nav><ul><li><a href=\"/\">Home</a></li><li><details open><summary>Docs</summary><ul>\
+///      <li><a href=\"/docs/install\" aria-current=\"page\">Install</a></li></ul></details></li></ul></nav>",
+///     res
+/// );
+/// ```
+pub fn nav_menu(
+    items: impl IntoIterator<Item = MenuItem>,
+    current_path: impl AsRef<str>,
+) -> NavMenu {
+    NavMenu {
+        items: items.into_iter().collect(),
+        current_path: current_path.as_ref().to_string(),
+        label: None,
+        active_class: None,
+        match_prefix: false,
+    }
+}
+
+impl NavMenu {
+    /// Accessible name of the `<nav>`, needed when a page has more than one.
+    pub fn label(mut self, value: impl AsRef<str>) -> Self {
+        self.label = Some(value.as_ref().to_string());
+        self
+    }
+
+    /// Class added to the active link, for stylesheets that don't select on `aria-current`.
+    pub fn active_class(mut self, value: impl AsRef<str>) -> Self {
+        self.active_class = Some(value.as_ref().to_string());
+        self
+    }
+
+    /// Also treats links to a parent path as active, so `/blog` stays highlighted on `/blog/some-post`.
+    pub fn match_prefix(mut self) -> Self {
+        self.match_prefix = true;
+        self
+    }
+
+    fn is_active(&self, href: &str) -> bool {
+        let path = normalize_path(&self.current_path);
+        let href = normalize_path(href);
+
+        path == href
+            || (self.match_prefix
+                && href != "/"
+                && path
+                    .strip_prefix(href)
+                    .is_some_and(|rest| rest.starts_with('/')))
+    }
+
+    fn contains_active(&self, item: &MenuItem) -> bool {
+        item.href
+            .as_deref()
+            .is_some_and(|href| self.is_active(href))
+            || item
+                .children
+                .iter()
+                .any(|child| self.contains_active(child))
+    }
+
+    fn list(&self, items: &[MenuItem]) -> Node<Ul, Content> {
+        ul().children(items, |item| {
+            let li = li();
+            match &item.href {
+                Some(href) if item.children.is_empty() => {
+                    let active = self.is_active(href);
+                    li.child(
+                        a().href(href)
+                            .map_when(active, |node| node.attr("aria-current", "page"))
+                            .map_when(active && self.active_class.is_some(), |node| {
+                                node.class(self.active_class.as_deref().unwrap_or_default())
+                            })
+                            .text(&item.label),
+                    )
+                }
+                _ => li.child(
+                    details()
+                        .map_when(self.contains_active(item), |node| node.open())
+                        .child(summary().text(&item.label))
+                        .child(self.list(&item.children)),
+                ),
+            }
+        })
+    }
+}
+
+// drops query, fragment and trailing slash so "/blog/?page=2" matches "/blog"
+fn normalize_path(path: &str) -> &str {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    }
+}
+
+impl Renderable for NavMenu {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        nav()
+            .map_when(self.label.is_some(), |node| {
+                node.attr("aria-label", self.label.as_deref().unwrap_or_default())
+            })
+            .child(self.list(&self.items))
+            .render_into(buf);
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
@@ -0,0 +1,105 @@
+use std::cell::RefCell;
+
+// more than enough for wide trees, keeps a single huge render from pinning memory forever
+const MAX_SPARE_BUFFERS: usize = 256;
+
+/// Pool of node buffers, reused by all nodes built inside [`scope`](BufferPool::scope).
+///
+/// Every node normally allocates its own buffer which is freed again as soon as it has been rendered into its
+/// parent. Inside of a pool scope rendered nodes hand their buffers back instead and the next node picks one up,
+/// so a handler keeping a pool around (e.g. one per worker thread) stops allocating once it has warmed up.
+///
+/// Nodes can't allocate from a bump arena like `bumpalo` handed in by the caller, their buffers are plain `Vec<u8>`s
+/// from the global allocator and the pool only saves freeing and allocating them again. Between scopes the pool
+/// owns its spare buffers, at most 256 of them, and frees them when it's dropped or [`clear`](BufferPool::clear)ed.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let mut pool = BufferPool::new();
+///
+/// let res = pool.scope(|| {
+///     ul().children(1..=3, |i| li().text(i.to_string())).render()
+/// });
+///
+/// assert_eq!("<ul><li>1</li><li>2</li><li>3</li></ul>", res);
+/// assert!(!pool.is_empty());
+/// ```
+#[derive(Default)]
+pub struct BufferPool {
+    buffers: Vec<Vec<u8>>,
+}
+
+impl BufferPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a pool with `count` buffers of `capacity` bytes allocated up front, at most 256.
+    pub fn with_buffers(count: usize, capacity: usize) -> Self {
+        BufferPool {
+            buffers: (0..count.min(MAX_SPARE_BUFFERS))
+                .map(|_| Vec::with_capacity(capacity))
+                .collect(),
+        }
+    }
+
+    /// Number of spare buffers in the pool.
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Frees the spare buffers, e.g. after an unusually large page left the pool with more memory than the
+    /// usual ones need.
+    pub fn clear(&mut self) {
+        self.buffers = Vec::new();
+    }
+
+    /// Calls `fun` with this pool providing the buffers of all nodes built on the current thread.
+    pub fn scope<R>(&mut self, fun: impl FnOnce() -> R) -> R {
+        let buffers = std::mem::take(&mut self.buffers);
+        let _guard = ScopeGuard {
+            previous: POOL.with(|pool| pool.replace(Some(buffers))),
+            pool: self,
+        };
+        fun()
+    }
+}
+
+thread_local! {
+    static POOL: RefCell<Option<Vec<Vec<u8>>>> = const { RefCell::new(None) };
+}
+
+struct ScopeGuard<'a> {
+    pool: &'a mut BufferPool,
+    previous: Option<Vec<Vec<u8>>>,
+}
+
+impl Drop for ScopeGuard<'_> {
+    fn drop(&mut self) {
+        let buffers = POOL.with(|pool| pool.replace(self.previous.take()));
+        self.pool.buffers = buffers.unwrap_or_default();
+    }
+}
+
+/// An empty buffer, taken from the active pool if there is one.
+pub(crate) fn buffer(capacity: usize) -> Vec<u8> {
+    POOL.with(|pool| pool.borrow_mut().as_mut().and_then(Vec::pop))
+        .unwrap_or_else(|| Vec::with_capacity(capacity))
+}
+
+/// Hands a buffer that is no longer needed back to the active pool, or frees it.
+pub(crate) fn recycle(mut buf: Vec<u8>) {
+    POOL.with(|pool| {
+        if let Some(pool) = pool.borrow_mut().as_mut()
+            && pool.len() < MAX_SPARE_BUFFERS
+        {
+            buf.clear();
+            pool.push(buf);
+        }
+    });
+}
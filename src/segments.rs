@@ -0,0 +1,160 @@
+use std::io::{self, IoSlice};
+use std::sync::Arc;
+
+use crate::core::{self, Renderable, render_child, write_escaped};
+use crate::memo::Memo;
+
+/// A page kept as a list of segments instead of one buffer, so large static chunks and cached partials are written
+/// to the socket from where they are instead of being copied into a buffer per request.
+///
+/// Dynamic parts are rendered as usual, consecutive ones share a buffer. [`write_to`](Segments::write_to) hands
+/// all segments to the writer at once with `write_vectored`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// static LAYOUT_START: &str = "<!DOCTYPE html><html><head><title>Shop</title></head><body>";
+/// static LAYOUT_END: &str = "</body></html>";
+///
+/// let page = segments()
+///     .static_html(LAYOUT_START)
+///     .child(h1().text("Cart"))
+///     .static_html(LAYOUT_END);
+///
+/// let mut out = Vec::new();
+/// page.write_to(&mut out).unwrap();
+///
+/// assert_eq!(
+///     "<!DOCTYPE html><html><head><title>Shop</title></head><body><h1>Cart</h1></body></html>",
+///     String::from_utf8(out).unwrap()
+/// );
+/// ```
+///
+/// Segments are written as they are, the post-processing of the render context like
+/// [`minify`](crate::context::RenderContext::minify) only applies when rendering them with `render()`.
+#[derive(Default)]
+pub struct Segments {
+    parts: Vec<Segment>,
+}
+
+enum Segment {
+    Static(&'static str),
+    Shared(Arc<str>),
+    Rendered(Vec<u8>),
+}
+
+impl Segment {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Segment::Static(html) => html.as_bytes(),
+            Segment::Shared(html) => html.as_bytes(),
+            Segment::Rendered(buf) => buf,
+        }
+    }
+}
+
+/// Creates empty [`Segments`].
+pub fn segments() -> Segments {
+    Segments::default()
+}
+
+impl Segments {
+    /// Markup that's part of the binary, written without being copied.
+    pub fn static_html(mut self, html: &'static str) -> Self {
+        if !html.is_empty() {
+            self.parts.push(Segment::Static(html));
+        }
+        self
+    }
+
+    /// Markup shared between requests, e.g. rendered once at startup, written without being copied.
+    pub fn shared_html(mut self, html: Arc<str>) -> Self {
+        if !html.is_empty() {
+            self.parts.push(Segment::Shared(html));
+        }
+        self
+    }
+
+    /// A cached partial from [`memo`](crate::memo::memo), written from the cache without being copied.
+    pub fn memo(self, memo: Memo) -> Self {
+        self.shared_html(memo.into_shared())
+    }
+
+    pub fn child(mut self, child: impl Renderable) -> Self {
+        render_child(child, self.rendered());
+        self
+    }
+
+    pub fn text(mut self, text: impl AsRef<str>) -> Self {
+        write_escaped(self.rendered(), text.as_ref());
+        self
+    }
+
+    /// Length of the page in bytes.
+    pub fn len(&self) -> usize {
+        self.parts.iter().map(|part| part.as_bytes().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The segments in order, for writers taking the slices themselves.
+    pub fn io_slices(&self) -> Vec<IoSlice<'_>> {
+        self.parts
+            .iter()
+            .map(|part| IoSlice::new(part.as_bytes()))
+            .collect()
+    }
+
+    /// Writes the whole page using `write_vectored`, retrying until the writer took everything.
+    pub fn write_to(&self, out: &mut impl io::Write) -> io::Result<()> {
+        let mut slices = self.io_slices();
+        let mut slices = &mut slices[..];
+
+        while !slices.is_empty() {
+            match out.write_vectored(slices) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the whole page",
+                    ));
+                }
+                Ok(written) => IoSlice::advance_slices(&mut slices, written),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    // the buffer for dynamic content, shared with the previous segment if that was rendered as well
+    fn rendered(&mut self) -> &mut Vec<u8> {
+        if !matches!(self.parts.last(), Some(Segment::Rendered(_))) {
+            self.parts.push(Segment::Rendered(Vec::new()));
+        }
+        match self.parts.last_mut() {
+            Some(Segment::Rendered(buf)) => buf,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Renderable for Segments {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.reserve(self.len());
+        for part in &self.parts {
+            buf.extend_from_slice(part.as_bytes());
+        }
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
@@ -0,0 +1,359 @@
+// A small CSS selector matcher over parsed trees.
+//
+// Supports type, universal, #id, .class and attribute selectors ([a], [a=v], [a~=v], [a^=v], [a$=v], [a*=v]),
+// descendant and child combinators and selector lists.
+
+use crate::tree::{Element, TreeNode};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AttrOp {
+    Exists,
+    Equals(String),
+    Includes(String),
+    Prefix(String),
+    Suffix(String),
+    Contains(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Compound {
+    tag: Option<String>,
+    ids: Vec<String>,
+    classes: Vec<String>,
+    attrs: Vec<(String, AttrOp)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+/// A parsed selector list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Selector {
+    // compounds of every complex selector paired with the combinator in front of them, only the first has none
+    alternatives: Vec<Vec<(Compound, Option<Combinator>)>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SelectorError {
+    pub(crate) selector: String,
+    pub(crate) message: &'static str,
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid selector \"{}\": {}",
+            self.selector, self.message
+        )
+    }
+}
+
+impl Selector {
+    pub(crate) fn parse(src: &str) -> Result<Selector, SelectorError> {
+        let error = |message| SelectorError {
+            selector: src.to_string(),
+            message,
+        };
+
+        let mut alternatives = Vec::new();
+
+        for part in split_list(src) {
+            let mut complex = Vec::new();
+            let mut combinator = None;
+            let mut chars = part.trim().chars().peekable();
+
+            if chars.peek().is_none() {
+                return Err(error("empty selector"));
+            }
+
+            while chars.peek().is_some() {
+                let mut whitespace = false;
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                    whitespace = true;
+                }
+
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                        chars.next();
+                    }
+                    if complex.is_empty() {
+                        return Err(error("combinator without a selector before it"));
+                    }
+                    combinator = Some(Combinator::Child);
+                } else if whitespace && !complex.is_empty() {
+                    combinator = Some(Combinator::Descendant);
+                }
+
+                let compound = parse_compound(&mut chars).map_err(error)?;
+                complex.push((compound, combinator.take()));
+            }
+
+            alternatives.push(complex);
+        }
+
+        Ok(Selector { alternatives })
+    }
+
+    /// All elements in `nodes` matching the selector, in document order.
+    pub(crate) fn select<'a>(&self, nodes: &'a [TreeNode]) -> Vec<&'a Element> {
+        let mut matches = Vec::new();
+        let mut ancestors = Vec::new();
+        self.walk(nodes, &mut ancestors, &mut matches);
+        matches
+    }
+
+    fn walk<'a>(
+        &self,
+        nodes: &'a [TreeNode],
+        ancestors: &mut Vec<&'a Element>,
+        matches: &mut Vec<&'a Element>,
+    ) {
+        for node in nodes {
+            if let TreeNode::Element(element) = node {
+                if self.matches(element, ancestors) {
+                    matches.push(element);
+                }
+
+                ancestors.push(element);
+                self.walk(&element.children, ancestors, matches);
+                ancestors.pop();
+            }
+        }
+    }
+
+    pub(crate) fn matches(&self, element: &Element, ancestors: &[&Element]) -> bool {
+        self.alternatives
+            .iter()
+            .any(|complex| matches_complex(complex, element, ancestors))
+    }
+}
+
+// matches from the right, `element` has to match the last compound and its ancestors the ones before it
+fn matches_complex(
+    complex: &[(Compound, Option<Combinator>)],
+    element: &Element,
+    ancestors: &[&Element],
+) -> bool {
+    let Some(((compound, combinator), rest)) = complex.split_last() else {
+        return true;
+    };
+
+    if !matches_compound(compound, element) {
+        return false;
+    }
+
+    match combinator {
+        None => true,
+        Some(Combinator::Child) => match ancestors.split_last() {
+            Some((parent, ancestors)) => matches_complex(rest, parent, ancestors),
+            None => false,
+        },
+        Some(Combinator::Descendant) => (0..ancestors.len())
+            .rev()
+            .any(|i| matches_complex(rest, ancestors[i], &ancestors[..i])),
+    }
+}
+
+fn matches_compound(compound: &Compound, element: &Element) -> bool {
+    if compound
+        .tag
+        .as_ref()
+        .is_some_and(|tag| !tag.eq_ignore_ascii_case(&element.name))
+    {
+        return false;
+    }
+
+    let attr = |name: &str| element.attr(name);
+
+    let class = attr("class").unwrap_or("");
+    if !compound
+        .classes
+        .iter()
+        .all(|wanted| class.split_ascii_whitespace().any(|c| c == wanted))
+    {
+        return false;
+    }
+
+    if !compound
+        .ids
+        .iter()
+        .all(|id| attr("id") == Some(id.as_str()))
+    {
+        return false;
+    }
+
+    compound.attrs.iter().all(|(name, op)| match attr(name) {
+        None => false,
+        Some(value) => match op {
+            AttrOp::Exists => true,
+            AttrOp::Equals(v) => value == v,
+            AttrOp::Includes(v) => value.split_ascii_whitespace().any(|w| w == v),
+            AttrOp::Prefix(v) => !v.is_empty() && value.starts_with(v.as_str()),
+            AttrOp::Suffix(v) => !v.is_empty() && value.ends_with(v.as_str()),
+            AttrOp::Contains(v) => !v.is_empty() && value.contains(v.as_str()),
+        },
+    })
+}
+
+// the selectors of a list, split at the commas that aren't part of a quoted value, an attribute selector or the
+// arguments of a pseudo-class like `:is(a, b)`
+fn split_list(src: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut escaped = false;
+
+    for (i, c) in src.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, c) {
+            (_, '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '(') => depth += 1,
+            (None, ']' | ')') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                parts.push(&src[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&src[start..]);
+    parts
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn parse_ident(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut ident = String::new();
+    while let Some(&c) = chars.peek() {
+        if !is_ident_char(c) {
+            break;
+        }
+        ident.push(c);
+        chars.next();
+    }
+    ident
+}
+
+fn parse_compound(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Result<Compound, &'static str> {
+    let mut compound = Compound::default();
+    let mut empty = true;
+
+    if chars.peek() == Some(&'*') {
+        chars.next();
+        empty = false;
+    } else if chars.peek().is_some_and(|c| is_ident_char(*c)) {
+        compound.tag = Some(parse_ident(chars).to_ascii_lowercase());
+        empty = false;
+    }
+
+    loop {
+        match chars.peek() {
+            Some('#') => {
+                chars.next();
+                let id = parse_ident(chars);
+                if id.is_empty() {
+                    return Err("expected an id after '#'");
+                }
+                compound.ids.push(id);
+            }
+            Some('.') => {
+                chars.next();
+                let class = parse_ident(chars);
+                if class.is_empty() {
+                    return Err("expected a class name after '.'");
+                }
+                compound.classes.push(class);
+            }
+            Some('[') => {
+                chars.next();
+                compound.attrs.push(parse_attr(chars)?);
+            }
+            _ => break,
+        }
+        empty = false;
+    }
+
+    if empty {
+        return Err("unexpected character");
+    }
+
+    Ok(compound)
+}
+
+fn parse_attr(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Result<(String, AttrOp), &'static str> {
+    let skip_whitespace = |chars: &mut std::iter::Peekable<std::str::Chars<'_>>| {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+    };
+
+    skip_whitespace(chars);
+    let name = parse_ident(chars).to_ascii_lowercase();
+    if name.is_empty() {
+        return Err("expected an attribute name");
+    }
+    skip_whitespace(chars);
+
+    let op = match chars.next() {
+        Some(']') => return Ok((name, AttrOp::Exists)),
+        Some('=') => None,
+        Some(c @ ('~' | '^' | '$' | '*')) => {
+            if chars.next() != Some('=') {
+                return Err("expected '=' in attribute selector");
+            }
+            Some(c)
+        }
+        _ => return Err("expected ']' or an operator in attribute selector"),
+    };
+
+    skip_whitespace(chars);
+    let value = match chars.peek() {
+        Some(&quote @ ('"' | '\'')) => {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some(c) if c == quote => break,
+                    Some(c) => value.push(c),
+                    None => return Err("unterminated string in attribute selector"),
+                }
+            }
+            value
+        }
+        _ => parse_ident(chars),
+    };
+    skip_whitespace(chars);
+
+    if chars.next() != Some(']') {
+        return Err("expected ']' after attribute selector");
+    }
+
+    let op = match op {
+        None => AttrOp::Equals(value),
+        Some('~') => AttrOp::Includes(value),
+        Some('^') => AttrOp::Prefix(value),
+        Some('$') => AttrOp::Suffix(value),
+        Some(_) => AttrOp::Contains(value),
+    };
+
+    Ok((name, op))
+}
@@ -0,0 +1,386 @@
+// Flattens structs and maps into their fields, for query strings and tables
+
+use std::fmt;
+
+use serde::ser::{self, Impossible, Serialize};
+
+/// Why a value couldn't be flattened into fields.
+#[derive(Debug)]
+pub(crate) struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// The fields of a struct or map with their values as strings, no value for `None` and one for each item of a
+/// sequence.
+pub(crate) fn fields<T>(value: &T) -> Result<Vec<(String, Vec<String>)>, Error>
+where
+    T: Serialize + ?Sized,
+{
+    let mut fields = Vec::new();
+    value.serialize(FieldSerializer {
+        fields: &mut fields,
+    })?;
+    Ok(fields)
+}
+
+fn unsupported<T>(what: &str) -> Result<T, Error> {
+    Err(Error(format!("{} isn't supported as a field", what)))
+}
+
+/// Serializes the top level struct or map into its fields.
+struct FieldSerializer<'a> {
+    fields: &'a mut Vec<(String, Vec<String>)>,
+}
+
+struct Fields<'a> {
+    fields: &'a mut Vec<(String, Vec<String>)>,
+    key: Option<String>,
+}
+
+impl Fields<'_> {
+    fn push<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        let values = value.serialize(ValueSerializer)?;
+        self.fields.push((key.to_string(), values));
+        Ok(())
+    }
+}
+
+macro_rules! top_level_unsupported {
+    ($($method:ident($($ty:ty),*) => $what:literal,)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<(), Error> {
+                unsupported($what)
+            }
+        )*
+    };
+}
+
+impl<'a> ser::Serializer for FieldSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Fields<'a>;
+    type SerializeStruct = Fields<'a>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    top_level_unsupported! {
+        serialize_bool(bool) => "a bool",
+        serialize_i8(i8) => "a number",
+        serialize_i16(i16) => "a number",
+        serialize_i32(i32) => "a number",
+        serialize_i64(i64) => "a number",
+        serialize_u8(u8) => "a number",
+        serialize_u16(u16) => "a number",
+        serialize_u32(u32) => "a number",
+        serialize_u64(u64) => "a number",
+        serialize_f32(f32) => "a number",
+        serialize_f64(f64) => "a number",
+        serialize_char(char) => "a char",
+        serialize_str(&str) => "a string",
+        serialize_bytes(&[u8]) => "bytes",
+        serialize_unit_variant(&'static str, u32, &'static str) => "an enum",
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Error> {
+        unsupported("an enum")
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        unsupported("a sequence")
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        unsupported("a tuple")
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        unsupported("a tuple struct")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        unsupported("an enum")
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(Fields {
+            fields: self.fields,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        Ok(Fields {
+            fields: self.fields,
+            key: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        unsupported("an enum")
+    }
+}
+
+impl ser::SerializeStruct for Fields<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for Fields<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        match key.serialize(ValueSerializer)?.as_slice() {
+            [key] => {
+                self.key = Some(key.clone());
+                Ok(())
+            }
+            _ => unsupported("a key that isn't a single value"),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.key.take().unwrap_or_default();
+        self.push(&key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Serializes the value of a field, none for `None` and one for each item of a sequence.
+struct ValueSerializer;
+
+struct Values(Vec<String>);
+
+macro_rules! value_to_string {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Vec<String>, Error> {
+                Ok(vec![value.to_string()])
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Vec<String>;
+    type Error = Error;
+    type SerializeSeq = Values;
+    type SerializeTuple = Values;
+    type SerializeTupleStruct = Impossible<Vec<String>, Error>;
+    type SerializeTupleVariant = Impossible<Vec<String>, Error>;
+    type SerializeMap = Impossible<Vec<String>, Error>;
+    type SerializeStruct = Impossible<Vec<String>, Error>;
+    type SerializeStructVariant = Impossible<Vec<String>, Error>;
+
+    value_to_string! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Vec<String>, Error> {
+        Ok(vec![String::from_utf8_lossy(value).into_owned()])
+    }
+
+    fn serialize_none(self) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<String>, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![variant.to_string()])
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Vec<String>, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Vec<String>, Error> {
+        unsupported("an enum with data")
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Values, Error> {
+        Ok(Values(Vec::with_capacity(len.unwrap_or_default())))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Values, Error> {
+        Ok(Values(Vec::with_capacity(len)))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        unsupported("a tuple struct")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        unsupported("an enum with data")
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        unsupported("a nested map")
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        unsupported("a nested struct")
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        unsupported("an enum with data")
+    }
+}
+
+impl ser::SerializeSeq for Values {
+    type Ok = Vec<String>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.0.extend(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Vec<String>, Error> {
+        Ok(self.0)
+    }
+}
+
+impl ser::SerializeTuple for Values {
+    type Ok = Vec<String>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Vec<String>, Error> {
+        Ok(self.0)
+    }
+}
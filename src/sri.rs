@@ -0,0 +1,494 @@
+use crate::core::{Node, Open, Void};
+use crate::html5::{
+    CrossOrigin, HasCrossOrigin, HasHref, HasRel, HasSrc, Link, Rel, Script, link, script,
+};
+
+/// Hash function used for a Subresource Integrity value.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Algo {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+// "sha512-" + base64 of a 64 byte digest
+const MAX_INTEGRITY_LEN: usize = 7 + 88;
+
+/// A formatted Subresource Integrity value like `sha384-...`.
+///
+/// Can be computed at compile time, which keeps the value in sync with the embedded asset:
+///
+/// ```
+/// use lira::sri::{Algo, Integrity};
+///
+/// const APP_JS: &[u8] = b"console.log('hello');";
+/// const APP_JS_SRI: Integrity = Integrity::new(APP_JS, Algo::Sha384);
+///
+/// assert!(APP_JS_SRI.as_str().starts_with("sha384-"));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Integrity {
+    buf: [u8; MAX_INTEGRITY_LEN],
+    len: usize,
+}
+
+impl Integrity {
+    pub const fn new(bytes: &[u8], algo: Algo) -> Self {
+        let mut integrity = Integrity {
+            buf: [0; MAX_INTEGRITY_LEN],
+            len: 0,
+        };
+
+        match algo {
+            Algo::Sha256 => {
+                integrity.push_str(b"sha256-");
+                integrity.push_base64(&sha256(bytes));
+            }
+            Algo::Sha384 => {
+                integrity.push_str(b"sha384-");
+                let digest = sha512(bytes, SHA384_H);
+                let (digest, _) = digest.split_at(48);
+                integrity.push_base64(digest);
+            }
+            Algo::Sha512 => {
+                integrity.push_str(b"sha512-");
+                integrity.push_base64(&sha512(bytes, SHA512_H));
+            }
+        }
+
+        integrity
+    }
+
+    pub const fn as_str(&self) -> &str {
+        let (bytes, _) = self.buf.split_at(self.len);
+        match std::str::from_utf8(bytes) {
+            Ok(s) => s,
+            Err(_) => panic!("Internal Error: Invalid integrity value"),
+        }
+    }
+
+    const fn push_str(&mut self, s: &[u8]) {
+        let mut i = 0;
+        while i < s.len() {
+            self.buf[self.len] = s[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+
+    const fn push_base64(&mut self, data: &[u8]) {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let mut i = 0;
+        while i < data.len() {
+            let b0 = data[i] as u32;
+            let b1 = if i + 1 < data.len() {
+                data[i + 1] as u32
+            } else {
+                0
+            };
+            let b2 = if i + 2 < data.len() {
+                data[i + 2] as u32
+            } else {
+                0
+            };
+            let n = (b0 << 16) | (b1 << 8) | b2;
+
+            self.push_str(&[
+                ALPHABET[(n >> 18) as usize & 63],
+                ALPHABET[(n >> 12) as usize & 63],
+                if i + 1 < data.len() {
+                    ALPHABET[(n >> 6) as usize & 63]
+                } else {
+                    b'='
+                },
+                if i + 2 < data.len() {
+                    ALPHABET[n as usize & 63]
+                } else {
+                    b'='
+                },
+            ]);
+
+            i += 3;
+        }
+    }
+}
+
+impl AsRef<str> for Integrity {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::fmt::Display for Integrity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::fmt::Debug for Integrity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Integrity").field(&self.as_str()).finish()
+    }
+}
+
+/// Computes the Subresource Integrity value of `bytes`, e.g. `sha384-...`.
+pub fn integrity_from_bytes(bytes: &[u8], algo: Algo) -> String {
+    Integrity::new(bytes, algo).as_str().to_string()
+}
+
+/// A `<script>` pointing to `src` with the SHA-384 integrity of `bytes`.
+///
+/// Integrity checks on cross-origin resources need CORS, so `crossorigin="anonymous"` is set as well.
+pub fn script_with_sri(src: impl AsRef<str>, bytes: &[u8]) -> Node<Script, Open> {
+    script()
+        .src(src)
+        .integrity(Integrity::new(bytes, Algo::Sha384))
+        .crossorigin(CrossOrigin::Anonymous)
+}
+
+/// A stylesheet `<link>` pointing to `href` with the SHA-384 integrity of `bytes`.
+///
+/// Integrity checks on cross-origin resources need CORS, so `crossorigin="anonymous"` is set as well.
+pub fn link_with_sri(href: impl AsRef<str>, bytes: &[u8]) -> Node<Link, Void> {
+    link()
+        .rel(Rel::Stylesheet)
+        .href(href)
+        .integrity(Integrity::new(bytes, Algo::Sha384))
+        .crossorigin(CrossOrigin::Anonymous)
+}
+
+// SHA-2, see FIPS 180-4
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const SHA256_H: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SHA512_K: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+const SHA512_H: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SHA384_H: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+pub(crate) const fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut h = SHA256_H;
+
+    let mut offset = 0;
+    while offset + 64 <= data.len() {
+        sha256_compress(&mut h, data, offset);
+        offset += 64;
+    }
+
+    // padding: 0x80, zeros, then the message length in bits as big endian u64
+    let mut tail = [0u8; 128];
+    let rem = data.len() - offset;
+    let mut i = 0;
+    while i < rem {
+        tail[i] = data[offset + i];
+        i += 1;
+    }
+    tail[rem] = 0x80;
+
+    let tail_len = if rem < 56 { 64 } else { 128 };
+    let bit_len = (data.len() as u64).wrapping_mul(8).to_be_bytes();
+    let mut i = 0;
+    while i < 8 {
+        tail[tail_len - 8 + i] = bit_len[i];
+        i += 1;
+    }
+
+    let mut offset = 0;
+    while offset < tail_len {
+        sha256_compress(&mut h, &tail, offset);
+        offset += 64;
+    }
+
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 8 {
+        let word = h[i].to_be_bytes();
+        let mut j = 0;
+        while j < 4 {
+            out[i * 4 + j] = word[j];
+            j += 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+const fn sha256_compress(h: &mut [u32; 8], data: &[u8], offset: usize) {
+    let mut w = [0u32; 64];
+    let mut t = 0;
+    while t < 16 {
+        let i = offset + t * 4;
+        w[t] = u32::from_be_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        t += 1;
+    }
+    while t < 64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+        t += 1;
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = *h;
+
+    let mut t = 0;
+    while t < 64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = hh
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA256_K[t])
+            .wrapping_add(w[t]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+
+        hh = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+        t += 1;
+    }
+
+    h[0] = h[0].wrapping_add(a);
+    h[1] = h[1].wrapping_add(b);
+    h[2] = h[2].wrapping_add(c);
+    h[3] = h[3].wrapping_add(d);
+    h[4] = h[4].wrapping_add(e);
+    h[5] = h[5].wrapping_add(f);
+    h[6] = h[6].wrapping_add(g);
+    h[7] = h[7].wrapping_add(hh);
+}
+
+/// SHA-512 and, with its own initial hash value and a truncated result, SHA-384.
+const fn sha512(data: &[u8], init: [u64; 8]) -> [u8; 64] {
+    let mut h = init;
+
+    let mut offset = 0;
+    while offset + 128 <= data.len() {
+        sha512_compress(&mut h, data, offset);
+        offset += 128;
+    }
+
+    // padding: 0x80, zeros, then the message length in bits as big endian u128
+    let mut tail = [0u8; 256];
+    let rem = data.len() - offset;
+    let mut i = 0;
+    while i < rem {
+        tail[i] = data[offset + i];
+        i += 1;
+    }
+    tail[rem] = 0x80;
+
+    let tail_len = if rem < 112 { 128 } else { 256 };
+    let bit_len = (data.len() as u128).wrapping_mul(8).to_be_bytes();
+    let mut i = 0;
+    while i < 16 {
+        tail[tail_len - 16 + i] = bit_len[i];
+        i += 1;
+    }
+
+    let mut offset = 0;
+    while offset < tail_len {
+        sha512_compress(&mut h, &tail, offset);
+        offset += 128;
+    }
+
+    let mut out = [0u8; 64];
+    let mut i = 0;
+    while i < 8 {
+        let word = h[i].to_be_bytes();
+        let mut j = 0;
+        while j < 8 {
+            out[i * 8 + j] = word[j];
+            j += 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+const fn sha512_compress(h: &mut [u64; 8], data: &[u8], offset: usize) {
+    let mut w = [0u64; 80];
+    let mut t = 0;
+    while t < 16 {
+        let i = offset + t * 8;
+        w[t] = u64::from_be_bytes([
+            data[i],
+            data[i + 1],
+            data[i + 2],
+            data[i + 3],
+            data[i + 4],
+            data[i + 5],
+            data[i + 6],
+            data[i + 7],
+        ]);
+        t += 1;
+    }
+    while t < 80 {
+        let s0 = w[t - 15].rotate_right(1) ^ w[t - 15].rotate_right(8) ^ (w[t - 15] >> 7);
+        let s1 = w[t - 2].rotate_right(19) ^ w[t - 2].rotate_right(61) ^ (w[t - 2] >> 6);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+        t += 1;
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = *h;
+
+    let mut t = 0;
+    while t < 80 {
+        let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = hh
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA512_K[t])
+            .wrapping_add(w[t]);
+        let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+
+        hh = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+        t += 1;
+    }
+
+    h[0] = h[0].wrapping_add(a);
+    h[1] = h[1].wrapping_add(b);
+    h[2] = h[2].wrapping_add(c);
+    h[3] = h[3].wrapping_add(d);
+    h[4] = h[4].wrapping_add(e);
+    h[5] = h[5].wrapping_add(f);
+    h[6] = h[6].wrapping_add(g);
+    h[7] = h[7].wrapping_add(hh);
+}
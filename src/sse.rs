@@ -0,0 +1,87 @@
+use std::fmt;
+
+use crate::core::{self, Renderable};
+
+/// A Server-Sent Event carrying rendered HTML, built with [`sse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    event: Option<String>,
+    id: Option<String>,
+    retry: Option<u32>,
+    data: String,
+}
+
+/// Frames `content` as a Server-Sent Event, e.g. for htmx's `sse-swap` or Datastar.
+///
+/// Every line of the HTML becomes its own `data:` field, which clients join back together with newlines. The
+/// event ends with the blank line that dispatches it, so events can be written to the response one after another.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let event = sse(ul().child(li().text("New message"))).event("messages").id("42");
+///
+/// assert_eq!(
+///     "event: messages\nid: 42\ndata: <ul><li>New message</li></ul>\n\n",
+///     event.to_string()
+/// );
+/// ```
+pub fn sse(content: impl Renderable) -> SseEvent {
+    let mut buf = Vec::new();
+    core::render_child(content, &mut buf);
+
+    SseEvent {
+        event: None,
+        id: None,
+        retry: None,
+        data: String::from_utf8(buf).expect("render_child writes valid UTF-8"),
+    }
+}
+
+impl SseEvent {
+    /// Event type, clients listen for `message` if there is none.
+    pub fn event(mut self, name: impl AsRef<str>) -> Self {
+        self.event = Some(single_line(name.as_ref()));
+        self
+    }
+
+    /// Id the client sends back as `Last-Event-ID` when it reconnects.
+    pub fn id(mut self, value: impl AsRef<str>) -> Self {
+        // browsers ignore ids containing NUL
+        self.id = Some(single_line(value.as_ref()).replace('\0', ""));
+        self
+    }
+
+    /// Milliseconds the client waits before reconnecting after the connection was lost.
+    pub fn retry(mut self, millis: u32) -> Self {
+        self.retry = Some(millis);
+        self
+    }
+}
+
+impl fmt::Display for SseEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(event) = &self.event {
+            writeln!(f, "event: {}", event)?;
+        }
+        if let Some(id) = &self.id {
+            writeln!(f, "id: {}", id)?;
+        }
+        if let Some(retry) = self.retry {
+            writeln!(f, "retry: {}", retry)?;
+        }
+        for line in self
+            .data
+            .split("\r\n")
+            .flat_map(|line| line.split(['\r', '\n']))
+        {
+            writeln!(f, "data: {}", line)?;
+        }
+        writeln!(f)
+    }
+}
+
+// a line break would end the field and start a new one with whatever follows
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
@@ -0,0 +1,64 @@
+use crate::core::Renderable;
+use crate::tree::{self, TreeNode};
+
+/// Size and shape of a rendered tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Length of the output in bytes.
+    pub bytes: usize,
+    pub elements: usize,
+    pub text_nodes: usize,
+    /// Deepest element nesting, top level elements have a depth of 1.
+    pub max_depth: usize,
+}
+
+impl RenderStats {
+    /// Measures already rendered HTML.
+    pub fn of(html: &str) -> Self {
+        let mut stats = RenderStats {
+            bytes: html.len(),
+            ..Default::default()
+        };
+        stats.count(&tree::parse(html), 1);
+        stats
+    }
+
+    fn count(&mut self, nodes: &[TreeNode], depth: usize) {
+        for node in nodes {
+            match node {
+                TreeNode::Element(element) => {
+                    self.elements += 1;
+                    self.max_depth = self.max_depth.max(depth);
+                    self.count(&element.children, depth + 1);
+                }
+                TreeNode::Text(_) => self.text_nodes += 1,
+                TreeNode::Doctype(_) | TreeNode::Comment(_) => {}
+            }
+        }
+    }
+}
+
+/// Renders and measures the output.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let (html, stats) = ul()
+///     .child(li().text("One"))
+///     .child(li().text("Two"))
+///     .render_with_stats();
+///
+/// assert_eq!(html.len(), stats.bytes);
+/// assert_eq!(3, stats.elements);
+/// assert_eq!(2, stats.text_nodes);
+/// assert_eq!(2, stats.max_depth);
+/// ```
+pub trait RenderWithStats: Renderable + Sized {
+    fn render_with_stats(self) -> (String, RenderStats) {
+        let html = self.render();
+        let stats = RenderStats::of(&html);
+        (html, stats)
+    }
+}
+
+impl<R> RenderWithStats for R where R: Renderable {}
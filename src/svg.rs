@@ -0,0 +1,190 @@
+use crate::core::{self, CanAddChildren, CanAddText, HasAttributes, Node, Open, Renderable, Void};
+use crate::html5::HasGlobalAttributes;
+
+// <svg>
+pub struct Svg;
+
+/// Inline SVG image.
+pub fn svg() -> Node<Svg, Open> {
+    Node::new("svg")
+}
+
+impl CanAddChildren for Svg {}
+impl CanAddText for Svg {}
+
+impl Node<Svg, Open> {
+    /// Coordinate system of the image, e.g. `"0 0 24 24"`.
+    ///
+    /// Written in lowercase like every attribute name, HTML parsers restore the case of SVG attributes.
+    pub fn viewbox(self, value: impl AsRef<str>) -> Self {
+        self.attr("viewbox", value)
+    }
+
+    pub fn width(self, value: u32) -> Self {
+        self.attr_int("width", value)
+    }
+
+    pub fn height(self, value: u32) -> Self {
+        self.attr_int("height", value)
+    }
+}
+
+// <symbol>
+pub struct Symbol;
+
+/// Reusable graphic, only rendered where it is referenced by a [`use_`] element.
+pub fn symbol() -> Node<Symbol, Open> {
+    Node::new("symbol")
+}
+
+impl CanAddChildren for Symbol {}
+impl CanAddText for Symbol {}
+
+impl Node<Symbol, Open> {
+    pub fn viewbox(self, value: impl AsRef<str>) -> Self {
+        self.attr("viewbox", value)
+    }
+}
+
+// <use>
+pub struct Use;
+
+/// Renders a copy of the element `href` points to, usually a `<symbol>` of a sprite sheet.
+pub fn use_() -> Node<Use, Void> {
+    Node::new_small_self_closing("use")
+}
+
+impl Node<Use, Void> {
+    pub fn href(self, value: impl AsRef<str>) -> Self {
+        self.attr("href", value)
+    }
+}
+
+/// Icon built by [`icon`].
+pub struct Icon {
+    href: String,
+    size: Option<u32>,
+    class: Option<String>,
+}
+
+/// Renders the symbol `symbol_id` of the sprite sheet at `sprite_href` as a decorative icon.
+///
+/// The icon is hidden from assistive technology, give whatever contains it an accessible name.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<svg aria-hidden=\"true\" focusable=\"false\" class=\"icon\" width=\"16\" height=\"16\">\
+///      <use href=\"/icons.svg#search\" /></svg>",
+///     icon("/icons.svg", "search").class("icon").size(16).render()
+/// );
+/// ```
+pub fn icon(sprite_href: impl AsRef<str>, symbol_id: impl AsRef<str>) -> Icon {
+    Icon {
+        href: format!("{}#{}", sprite_href.as_ref(), symbol_id.as_ref()),
+        size: None,
+        class: None,
+    }
+}
+
+impl Icon {
+    /// Width and height in pixels.
+    pub fn size(mut self, value: u32) -> Self {
+        self.size = Some(value);
+        self
+    }
+
+    pub fn class(mut self, value: impl AsRef<str>) -> Self {
+        self.class = Some(value.as_ref().to_string());
+        self
+    }
+}
+
+impl Renderable for Icon {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        svg()
+            .attr("aria-hidden", "true")
+            .attr("focusable", "false")
+            .map_when(self.class.is_some(), |node| {
+                node.class(self.class.as_deref().unwrap_or_default())
+            })
+            .map_when(self.size.is_some(), |node| {
+                let size = self.size.unwrap_or_default();
+                node.width(size).height(size)
+            })
+            .child(use_().href(&self.href))
+            .render_into(buf);
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// Sprite sheet built by [`sprite`].
+#[derive(Default)]
+pub struct Sprite {
+    symbols: Vec<(String, String, String)>,
+}
+
+/// Collects SVG symbols into a hidden sprite sheet, meant to be inlined once at the start of the `<body>` so
+/// [`icon`]s can reference them with `icon("", id)`.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// assert_eq!(
+///     "<svg xmlns=\"http://www.w3.org/2000/svg\" hidden><symbol id=\"dot\" viewbox=\"0 0 2 2\">\
+///      <circle cx=\"1\" cy=\"1\" r=\"1\"/></symbol></svg>",
+///     sprite().symbol("dot", "0 0 2 2", "<circle cx=\"1\" cy=\"1\" r=\"1\"/>").render()
+/// );
+/// ```
+pub fn sprite() -> Sprite {
+    Sprite::default()
+}
+
+impl Sprite {
+    /// Adds a symbol, `markup` is the trusted SVG content of the symbol and written as is.
+    pub fn symbol(
+        mut self,
+        id: impl AsRef<str>,
+        viewbox: impl AsRef<str>,
+        markup: impl AsRef<str>,
+    ) -> Self {
+        self.symbols.push((
+            id.as_ref().to_string(),
+            viewbox.as_ref().to_string(),
+            markup.as_ref().to_string(),
+        ));
+        self
+    }
+}
+
+impl Renderable for Sprite {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        svg()
+            .attr("xmlns", "http://www.w3.org/2000/svg")
+            .hidden()
+            .children(self.symbols, |(id, viewbox, markup)| {
+                symbol().id(id).viewbox(viewbox).raw(markup)
+            })
+            .render_into(buf);
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
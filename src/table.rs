@@ -0,0 +1,480 @@
+use std::borrow::Cow;
+
+use crate::core::{
+    self, Content, Fragment, HasAttributes, IntoAny, Node, Open, Renderable, fragment,
+};
+use crate::html5::{HasGlobalAttributes, Table, Td, caption, table, tbody, td, th, thead, tr};
+use crate::ids::{Id, unique_id};
+
+#[cfg(feature = "derive")]
+pub use lira_derive::ToTableRow;
+
+/// A type that renders as a table row, usually derived:
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use lira::prelude::*;
+/// use lira::table::ToTableRow;
+///
+/// #[derive(ToTableRow)]
+/// struct User {
+///     name: String,
+///     #[table(label = "E-Mail")]
+///     email: String,
+///     #[table(skip)]
+///     password_hash: String,
+/// }
+///
+/// let users = vec![User {
+///     name: "Ferris".to_string(),
+///     email: "ferris@example.com".to_string(),
+///     password_hash: String::new(),
+/// }];
+///
+/// assert_eq!(
+///     "<table><thead><tr><th scope=\"col\">Name</th><th scope=\"col\">E-Mail</th></tr></thead>\
+///      <tbody><tr><td>Ferris</td><td>ferris@example.com</td></tr></tbody></table>",
+///     table_of(&users).render()
+/// );
+/// # }
+/// ```
+///
+/// Fields can be configured with `#[table(...)]`:
+///
+/// - `label = "..."` overrides the header, which defaults to the humanized field name
+/// - `with = "path"` renders the cell with a function taking a reference to the field and returning a `<td>`
+/// - `skip` leaves the field out
+///
+/// All other fields have to implement [`TableCell`].
+pub trait ToTableRow {
+    /// Header labels, one for each cell.
+    fn columns() -> Vec<Cow<'static, str>>;
+
+    /// The `<td>` cells of this row.
+    fn cells(&self) -> Fragment;
+}
+
+impl<T> ToTableRow for &T
+where
+    T: ToTableRow + ?Sized,
+{
+    fn columns() -> Vec<Cow<'static, str>> {
+        T::columns()
+    }
+
+    fn cells(&self) -> Fragment {
+        T::cells(self)
+    }
+}
+
+/// Values that can be rendered as a table cell.
+pub trait TableCell {
+    fn cell(&self) -> Node<Td, Content>;
+}
+
+impl TableCell for str {
+    fn cell(&self) -> Node<Td, Content> {
+        td().text(self)
+    }
+}
+
+impl<T> TableCell for &T
+where
+    T: TableCell + ?Sized,
+{
+    fn cell(&self) -> Node<Td, Content> {
+        T::cell(self)
+    }
+}
+
+/// Renders an empty cell for `None`.
+impl<T> TableCell for Option<T>
+where
+    T: TableCell,
+{
+    fn cell(&self) -> Node<Td, Content> {
+        match self {
+            Some(value) => value.cell(),
+            None => td().close(),
+        }
+    }
+}
+
+macro_rules! display_cells {
+    ($($ty:ty),*) => {
+        $(
+            impl TableCell for $ty {
+                fn cell(&self) -> Node<Td, Content> {
+                    td().text_fmt(format_args!("{}", self))
+                }
+            }
+        )*
+    };
+}
+
+display_cells!(
+    String, char, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+impl Node<Table, Open> {
+    /// Adds a `<thead>` with the columns of `R` and a `<tbody>` with a row for each item.
+    pub fn rows_of<It, R>(self, rows: It) -> Node<Table, Content>
+    where
+        It: IntoIterator<Item = R>,
+        R: ToTableRow,
+    {
+        self.child(thead().child(tr().children(R::columns(), |column| {
+            th().attr("scope", "col").text(column)
+        })))
+        .child(tbody().children(rows, |row| tr().child(row.cells())))
+    }
+}
+
+/// Renders `rows` as a table, use [`rows_of`](Node::rows_of) to add attributes to the `<table>`.
+pub fn table_of<It, R>(rows: It) -> Node<Table, Content>
+where
+    It: IntoIterator<Item = R>,
+    R: ToTableRow,
+{
+    table().rows_of(rows)
+}
+
+/// Renders each value as a cell, helper for writing [`ToTableRow::cells`] by hand.
+pub fn cells<const N: usize>(values: [&dyn TableCell; N]) -> Fragment {
+    fragment().children(values, |value| value.cell())
+}
+
+type CellFormat = Box<dyn Fn(&str) -> Fragment>;
+
+/// Table built by [`data_table`], [`csv_table`] or `DataTable::serialize`.
+pub struct DataTable {
+    caption: Option<String>,
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+    formats: Vec<(String, CellFormat)>,
+    row_headers: bool,
+    headers: bool,
+}
+
+/// Renders `columns` as the header cells and each of `rows` as a row, with the markup screen readers need to
+/// announce every cell together with its headers.
+///
+/// Column headers get `scope="col"`, with [`row_headers`](DataTable::row_headers) the first cell of each row
+/// becomes a header with `scope="row"`. A [`caption`](DataTable::caption) names the table.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = data_table(["Planet", "Moons"], [["Mars", "2"], ["Jupiter", "95"]])
+///     .caption("Moons per planet")
+///     .row_headers()
+///     .render();
+///
+/// assert_eq!(
+///     "<table><caption>Moons per planet</caption>\
+///      <thead><tr><th scope=\"col\">Planet</th><th scope=\"col\">Moons</th></tr></thead>\
+///      <tbody><tr><th scope=\"row\">Mars</th><td>2</td></tr><tr><th scope=\"row\">Jupiter</th><td>95</td></tr>\
+///      </tbody></table>",
+///     res
+/// );
+/// ```
+pub fn data_table<C, R>(columns: C, rows: R) -> DataTable
+where
+    C: IntoIterator,
+    C::Item: AsRef<str>,
+    R: IntoIterator,
+    R::Item: IntoIterator,
+    <R::Item as IntoIterator>::Item: AsRef<str>,
+{
+    DataTable {
+        caption: None,
+        columns: columns
+            .into_iter()
+            .map(|column| column.as_ref().to_string())
+            .collect(),
+        rows: rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|cell| cell.as_ref().to_string())
+                    .collect()
+            })
+            .collect(),
+        formats: Vec::new(),
+        row_headers: false,
+        headers: false,
+    }
+}
+
+/// Renders CSV text as a [`DataTable`], the first record becomes the column headers.
+///
+/// Fields can be quoted to contain commas, line breaks and `""` for a quote, records end at `\n` or `\r\n`.
+/// Records with fewer fields get empty cells, extra fields are dropped.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let csv = "city,population\nBerlin,3850809\n\"Frankfurt, Main\",773068\n";
+///
+/// assert_eq!(
+///     "<table><thead><tr><th scope=\"col\">city</th><th scope=\"col\">population</th></tr></thead>\
+///      <tbody><tr><td>Berlin</td><td>3850809</td></tr><tr><td>Frankfurt, Main</td><td>773068</td></tr>\
+///      </tbody></table>",
+///     csv_table(csv).render()
+/// );
+/// ```
+pub fn csv_table(csv: &str) -> DataTable {
+    let mut records = parse_csv(csv).into_iter();
+    let columns = records.next().unwrap_or_default();
+    let rows = records
+        .map(|mut row| {
+            row.resize(columns.len(), String::new());
+            row
+        })
+        .collect();
+    data_table(columns, Vec::<Vec<String>>::new()).with_rows(rows)
+}
+
+fn parse_csv(csv: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+
+    // the last record doesn't need a line break
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+impl DataTable {
+    /// Builds the table from serializable rows, e.g. the results of a query. The columns are the field names serde
+    /// uses for the first row, `None` renders as an empty cell and sequences are joined with `, `.
+    ///
+    /// Nested structs and maps can't be flattened into cells and fail with a [`TableError`].
+    ///
+    /// ```
+    /// use lira::prelude::*;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Release {
+    ///     version: &'static str,
+    ///     #[serde(rename = "released")]
+    ///     date: Option<&'static str>,
+    /// }
+    ///
+    /// let releases = [
+    ///     Release { version: "1.1", date: None },
+    ///     Release { version: "1.0", date: Some("2024-05-01") },
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     "<table><thead><tr><th scope=\"col\">version</th><th scope=\"col\">released</th></tr></thead>\
+    ///      <tbody><tr><td>1.1</td><td></td></tr><tr><td>1.0</td><td>2024-05-01</td></tr></tbody></table>",
+    ///     DataTable::serialize(&releases).unwrap().render()
+    /// );
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn serialize<It, T>(rows: It) -> Result<Self, TableError>
+    where
+        It: IntoIterator<Item = T>,
+        T: serde::Serialize,
+    {
+        let mut columns: Option<Vec<String>> = None;
+        let mut cells = Vec::new();
+
+        for row in rows {
+            let fields = crate::ser::fields(&row).map_err(|err| TableError {
+                message: err.to_string(),
+            })?;
+            let columns = columns
+                .get_or_insert_with(|| fields.iter().map(|(name, _)| name.clone()).collect());
+
+            cells.push(
+                columns
+                    .iter()
+                    .map(|column| {
+                        fields
+                            .iter()
+                            .find(|(name, _)| name == column)
+                            .map(|(_, values)| values.join(", "))
+                            .unwrap_or_default()
+                    })
+                    .collect(),
+            );
+        }
+
+        Ok(data_table(columns.unwrap_or_default(), Vec::<Vec<String>>::new()).with_rows(cells))
+    }
+
+    fn with_rows(mut self, rows: Vec<Vec<String>>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    pub fn caption(mut self, value: impl AsRef<str>) -> Self {
+        self.caption = Some(value.as_ref().to_string());
+        self
+    }
+
+    /// Renders the first cell of each row as the header of the row.
+    pub fn row_headers(mut self) -> Self {
+        self.row_headers = true;
+        self
+    }
+
+    /// Gives every header cell a generated id and lists the ids of its headers in the `headers` attribute of each
+    /// data cell. Only needed for complex tables where `scope` isn't enough, e.g. ones that are styled into
+    /// irregular layouts.
+    pub fn headers(mut self) -> Self {
+        self.headers = true;
+        self
+    }
+
+    /// Renders the cells of `column` with `format` instead of as text, e.g. to right-align numbers or turn URLs
+    /// into links. The cell element itself stays, `format` renders its content.
+    ///
+    /// ```
+    /// use lira::prelude::*;
+    ///
+    /// let res = data_table(["Crate", "Downloads"], [["lira", "1234"]])
+    ///     .format_column("Crate", |name| a().href(format!("/crates/{}", name)).text(name))
+    ///     .format_column("Downloads", |n| span().class("num").text(n))
+    ///     .render();
+    ///
+    /// assert_eq!(
+    ///     "<table><thead><tr><th scope=\"col\">Crate</th><th scope=\"col\">Downloads</th></tr></thead>\
+    ///      <tbody><tr><td><a href=\"/crates/lira\">lira</a></td><td><span class=\"num\">1234</span></td></tr>\
+    ///      </tbody></table>",
+    ///     res
+    /// );
+    /// ```
+    pub fn format_column<F, R>(mut self, column: impl AsRef<str>, format: F) -> Self
+    where
+        F: Fn(&str) -> R + 'static,
+        R: Renderable,
+    {
+        self.formats.push((
+            column.as_ref().to_string(),
+            Box::new(move |value| format(value).into_any()),
+        ));
+        self
+    }
+}
+
+impl Renderable for DataTable {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        let column_ids: Vec<Option<Id>> = self
+            .columns
+            .iter()
+            .map(|_| self.headers.then(|| unique_id("col")))
+            .collect();
+
+        let head = tr().children(self.columns.iter().zip(&column_ids), |(column, id)| {
+            th().attr("scope", "col")
+                .map_when(id.is_some(), |node| node.id(id.as_ref().unwrap()))
+                .text(column)
+        });
+
+        let formats: Vec<Option<&CellFormat>> = self
+            .columns
+            .iter()
+            .map(|column| {
+                self.formats
+                    .iter()
+                    .rev()
+                    .find(|(name, _)| name == column)
+                    .map(|(_, format)| format)
+            })
+            .collect();
+        let content = |i: usize, cell: &str| match formats.get(i).copied().flatten() {
+            Some(format) => format(cell),
+            None => fragment().text(cell),
+        };
+
+        let body = tbody().children(&self.rows, |row| {
+            let row_id = (self.row_headers && self.headers).then(|| unique_id("row"));
+
+            tr().children(row.iter().enumerate(), |(i, cell)| {
+                if i == 0 && self.row_headers {
+                    return fragment().child(
+                        th().attr("scope", "row")
+                            .map_when(row_id.is_some(), |node| node.id(row_id.as_ref().unwrap()))
+                            .child(content(i, cell)),
+                    );
+                }
+
+                let headers = column_ids
+                    .get(i)
+                    .and_then(Option::as_ref)
+                    .into_iter()
+                    .chain(row_id.as_ref())
+                    .map(Id::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                fragment().child(
+                    td().map_when(!headers.is_empty(), |node| node.attr("headers", &headers))
+                        .child(content(i, cell)),
+                )
+            })
+        });
+
+        let table = match self.caption {
+            Some(text) => table().child(caption().text(text)),
+            None => table().close(),
+        };
+
+        table
+            .child(thead().child(head))
+            .child(body)
+            .render_into(buf);
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// Error of [`DataTable::serialize`], for rows that aren't structs or maps or have fields that can't be a cell.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableError {
+    message: String,
+}
+
+#[cfg(feature = "serde")]
+impl std::fmt::Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "can't serialize table row: {}", self.message)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for TableError {}
@@ -0,0 +1,482 @@
+use crate::html5::HasGlobalAttributes;
+
+/// Joins class lists the way `tailwind-merge` does: when two Tailwind utilities set the same thing, the later one
+/// wins and the earlier one is left out.
+///
+/// Utilities only conflict within the same variants, `md:p-2` doesn't replace `p-4`. Shorthands replace the
+/// utilities they cover, a later `p-4` removes an earlier `px-2`, while a later `px-2` refines an earlier `p-4`
+/// and both are kept. Classes that aren't Tailwind utilities are kept as they are, apart from duplicates.
+///
+/// ```
+/// use lira::tailwind::tw_merge;
+///
+/// assert_eq!(
+///     "text-sm bg-blue-500 p-4 px-4 rounded",
+///     tw_merge(["px-2 py-1 bg-red-500 text-sm", "bg-blue-500 p-4 px-4 rounded"])
+/// );
+/// assert_eq!(
+///     "md:flex block text-red-500",
+///     tw_merge(["hidden md:flex", "block text-gray-900 text-red-500"])
+/// );
+/// ```
+pub fn tw_merge<I, S>(classes: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let lists: Vec<S> = classes.into_iter().collect();
+    let all: Vec<&str> = lists
+        .iter()
+        .flat_map(|list| list.as_ref().split_ascii_whitespace())
+        .collect();
+
+    // walking backwards, a class is kept unless a later one already took its group
+    let mut taken: Vec<String> = Vec::new();
+    let mut kept: Vec<&str> = Vec::new();
+    for &class in all.iter().rev() {
+        let (prefix, group) = group_of(class);
+        let key = format!("{}{}", prefix, group);
+        if taken.contains(&key) {
+            continue;
+        }
+        kept.push(class);
+        taken.push(key);
+        if let Some(group) = group.strip_prefix(GROUP) {
+            for covered in covered_groups(group) {
+                taken.push(format!("{}{}{}", prefix, GROUP, covered));
+            }
+        }
+    }
+
+    kept.reverse();
+    kept.join(" ")
+}
+
+/// Class helpers for Tailwind, enabled with the `tailwind` feature.
+pub trait HasTailwindClasses: HasGlobalAttributes {
+    /// Sets the classes merged with [`tw_merge`], e.g. a component's defaults followed by the caller's overrides.
+    ///
+    /// ```
+    /// use lira::prelude::*;
+    ///
+    /// fn button_with(extra: &str) -> impl Renderable {
+    ///     button().classes_tw(["px-4 py-2 bg-blue-600", extra]).text("Save")
+    /// }
+    ///
+    /// assert_eq!(
+    ///     "<button class=\"px-4 py-2 bg-red-600\">Save</button>",
+    ///     button_with("bg-red-600").render()
+    /// );
+    /// ```
+    fn classes_tw<I, S>(self, classes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.class(tw_merge(classes))
+    }
+}
+
+impl<T> HasTailwindClasses for T where T: HasGlobalAttributes {}
+
+// marks keys of recognized utilities, everything else is only compared as the whole class
+const GROUP: &str = "\0";
+
+// (variants and important modifier, group)
+fn group_of(class: &str) -> (String, String) {
+    let (variants, utility) = class.split_at(variant_end(class));
+    let (important, base) = match utility.strip_prefix('!') {
+        Some(base) => (true, base),
+        None => match utility.strip_suffix('!') {
+            Some(base) => (true, base),
+            None => (false, utility),
+        },
+    };
+    let prefix = format!("{}{}", variants, if important { "!" } else { "" });
+
+    // `-mt-2` sets the same property as `mt-2`
+    match utility_group(base.strip_prefix('-').unwrap_or(base)) {
+        Some(group) => (prefix, format!("{}{}", GROUP, group)),
+        None => (prefix, base.to_string()),
+    }
+}
+
+// end of the variants like `md:hover:`, colons inside arbitrary values don't count
+fn variant_end(class: &str) -> usize {
+    let mut depth = 0usize;
+    let mut end = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => end = i + 1,
+            _ => {}
+        }
+    }
+    end
+}
+
+const DISPLAY: &[&str] = &[
+    "block",
+    "inline-block",
+    "inline",
+    "flex",
+    "inline-flex",
+    "grid",
+    "inline-grid",
+    "hidden",
+    "table",
+    "inline-table",
+    "table-row",
+    "table-cell",
+    "contents",
+    "flow-root",
+    "list-item",
+];
+const POSITION: &[&str] = &["static", "fixed", "absolute", "relative", "sticky"];
+const VISIBILITY: &[&str] = &["visible", "invisible", "collapse"];
+const DECORATION_LINE: &[&str] = &["underline", "overline", "line-through", "no-underline"];
+const TRANSFORM: &[&str] = &["uppercase", "lowercase", "capitalize", "normal-case"];
+const FONT_STYLE: &[&str] = &["italic", "not-italic"];
+const TEXT_OVERFLOW: &[&str] = &["truncate", "text-ellipsis", "text-clip"];
+
+// utilities named `<prefix>` or `<prefix>-<value>`, longer prefixes first
+const PREFIXES: &[&str] = &[
+    "pointer-events",
+    "justify-items",
+    "justify-self",
+    "place-content",
+    "place-items",
+    "place-self",
+    "grid-cols",
+    "grid-rows",
+    "grid-flow",
+    "col-start",
+    "row-start",
+    "translate-x",
+    "translate-y",
+    "overflow-x",
+    "overflow-y",
+    "line-clamp",
+    "whitespace",
+    "col-span",
+    "row-span",
+    "col-end",
+    "row-end",
+    "duration",
+    "rounded-tl",
+    "rounded-tr",
+    "rounded-br",
+    "rounded-bl",
+    "rounded-ss",
+    "rounded-se",
+    "rounded-ee",
+    "rounded-es",
+    "overflow",
+    "tracking",
+    "leading",
+    "columns",
+    "justify",
+    "content",
+    "inset-x",
+    "inset-y",
+    "rounded-t",
+    "rounded-r",
+    "rounded-b",
+    "rounded-l",
+    "rounded-s",
+    "rounded-e",
+    "rounded",
+    "opacity",
+    "min-w",
+    "max-w",
+    "min-h",
+    "max-h",
+    "cursor",
+    "object",
+    "aspect",
+    "select",
+    "rotate",
+    "gap-x",
+    "gap-y",
+    "shrink",
+    "bottom",
+    "inset",
+    "order",
+    "items",
+    "basis",
+    "scale",
+    "delay",
+    "right",
+    "break",
+    "self",
+    "size",
+    "grow",
+    "left",
+    "ease",
+    "list",
+    "top",
+    "gap",
+    "px",
+    "py",
+    "pt",
+    "pr",
+    "pb",
+    "pl",
+    "ps",
+    "pe",
+    "mx",
+    "my",
+    "mt",
+    "mr",
+    "mb",
+    "ml",
+    "ms",
+    "me",
+    "z",
+    "w",
+    "h",
+    "p",
+    "m",
+];
+
+// shorthands and the groups they replace
+fn covered_groups(group: &str) -> &'static [&'static str] {
+    match group {
+        "p" => &["px", "py", "pt", "pr", "pb", "pl", "ps", "pe"],
+        "px" => &["pr", "pl", "ps", "pe"],
+        "py" => &["pt", "pb"],
+        "m" => &["mx", "my", "mt", "mr", "mb", "ml", "ms", "me"],
+        "mx" => &["mr", "ml", "ms", "me"],
+        "my" => &["mt", "mb"],
+        "inset" => &["inset-x", "inset-y", "top", "right", "bottom", "left"],
+        "inset-x" => &["right", "left"],
+        "inset-y" => &["top", "bottom"],
+        "size" => &["w", "h"],
+        "gap" => &["gap-x", "gap-y"],
+        "overflow" => &["overflow-x", "overflow-y"],
+        "rounded" => &[
+            "rounded-t",
+            "rounded-r",
+            "rounded-b",
+            "rounded-l",
+            "rounded-s",
+            "rounded-e",
+            "rounded-tl",
+            "rounded-tr",
+            "rounded-br",
+            "rounded-bl",
+            "rounded-ss",
+            "rounded-se",
+            "rounded-ee",
+            "rounded-es",
+        ],
+        "rounded-t" => &["rounded-tl", "rounded-tr"],
+        "rounded-r" => &["rounded-tr", "rounded-br"],
+        "rounded-b" => &["rounded-br", "rounded-bl"],
+        "rounded-l" => &["rounded-tl", "rounded-bl"],
+        "border-w" => &[
+            "border-w-x",
+            "border-w-y",
+            "border-w-t",
+            "border-w-r",
+            "border-w-b",
+            "border-w-l",
+        ],
+        "border-w-x" => &["border-w-r", "border-w-l"],
+        "border-w-y" => &["border-w-t", "border-w-b"],
+        "border-color" => &[
+            "border-color-x",
+            "border-color-y",
+            "border-color-t",
+            "border-color-r",
+            "border-color-b",
+            "border-color-l",
+        ],
+        "border-color-x" => &["border-color-r", "border-color-l"],
+        "border-color-y" => &["border-color-t", "border-color-b"],
+        _ => &[],
+    }
+}
+
+fn utility_group(utility: &str) -> Option<String> {
+    let sets: [(&str, &[&str]); 7] = [
+        ("display", DISPLAY),
+        ("position", POSITION),
+        ("visibility", VISIBILITY),
+        ("decoration-line", DECORATION_LINE),
+        ("transform", TRANSFORM),
+        ("font-style", FONT_STYLE),
+        ("text-overflow", TEXT_OVERFLOW),
+    ];
+    if let Some((group, _)) = sets.iter().find(|(_, set)| set.contains(&utility)) {
+        return Some(group.to_string());
+    }
+
+    // `[mask-type:luminance]`
+    if let Some(property) = utility
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(':'))
+    {
+        return Some(format!("[{}]", property.0));
+    }
+
+    if let Some(group) = ambiguous_group(utility) {
+        return Some(group);
+    }
+
+    PREFIXES
+        .iter()
+        .find(|prefix| {
+            utility == **prefix
+                || utility
+                    .strip_prefix(**prefix)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+        .map(|prefix| prefix.to_string())
+}
+
+// prefixes shared by utilities for different properties, told apart by their value
+fn ambiguous_group(utility: &str) -> Option<String> {
+    if let Some(value) = utility.strip_prefix("text-") {
+        let group = match value {
+            "xs" | "sm" | "base" | "lg" => "font-size",
+            v if v.ends_with("xl") => "font-size",
+            "left" | "center" | "right" | "justify" | "start" | "end" => "text-align",
+            "wrap" | "nowrap" | "balance" | "pretty" => "text-wrap",
+            v if is_color(v) => "text-color",
+            v if v.starts_with('[') => "font-size",
+            _ => return None,
+        };
+        return Some(group.to_string());
+    }
+
+    if let Some(value) = utility.strip_prefix("font-") {
+        let weights = [
+            "thin",
+            "extralight",
+            "light",
+            "normal",
+            "medium",
+            "semibold",
+            "bold",
+            "extrabold",
+            "black",
+        ];
+        return Some(
+            if weights.contains(&value) {
+                "font-weight"
+            } else {
+                "font-family"
+            }
+            .to_string(),
+        );
+    }
+
+    if let Some(value) = utility.strip_prefix("bg-") {
+        let group = match value {
+            "fixed" | "local" | "scroll" => "bg-attachment",
+            "auto" | "cover" | "contain" => "bg-size",
+            v if v.starts_with("repeat") || v == "no-repeat" => "bg-repeat",
+            "none" => "bg-image",
+            v if v.starts_with("gradient-")
+                || v.starts_with("linear-")
+                || v.starts_with("radial") =>
+            {
+                "bg-image"
+            }
+            "center" | "top" | "bottom" | "left" | "right" | "left-top" | "left-bottom"
+            | "right-top" | "right-bottom" => "bg-position",
+            v if is_color(v) => "bg-color",
+            _ => return None,
+        };
+        return Some(group.to_string());
+    }
+
+    if utility == "border" || utility.starts_with("border-") {
+        return border_group(utility);
+    }
+
+    if let Some(value) = utility.strip_prefix("shadow") {
+        let value = value.strip_prefix('-').unwrap_or(value);
+        return Some(
+            if is_color(value) {
+                "shadow-color"
+            } else {
+                "shadow"
+            }
+            .to_string(),
+        );
+    }
+
+    if let Some(value) = utility.strip_prefix("flex") {
+        let group = match value {
+            "-row" | "-row-reverse" | "-col" | "-col-reverse" => "flex-direction",
+            "-wrap" | "-wrap-reverse" | "-nowrap" => "flex-wrap",
+            v if v.starts_with('-') => "flex",
+            _ => return None,
+        };
+        return Some(group.to_string());
+    }
+
+    None
+}
+
+fn border_group(utility: &str) -> Option<String> {
+    let value = utility.strip_prefix("border").unwrap_or(utility);
+    let value = value.strip_prefix('-').unwrap_or(value);
+
+    let group = match value {
+        "solid" | "dashed" | "dotted" | "double" | "hidden" | "none" => {
+            return Some("border-style".to_string());
+        }
+        "collapse" | "separate" => return Some("border-collapse".to_string()),
+        _ => value,
+    };
+
+    let (side, value) = match group.split_once('-') {
+        Some((side, value)) if matches!(side, "x" | "y" | "t" | "r" | "b" | "l") => {
+            (format!("-{}", side), value)
+        }
+        _ if matches!(group, "x" | "y" | "t" | "r" | "b" | "l") => (format!("-{}", group), ""),
+        _ => (String::new(), group),
+    };
+
+    if value.is_empty()
+        || value.chars().all(|c| c.is_ascii_digit())
+        || value.starts_with("[length:")
+    {
+        Some(format!("border-w{}", side))
+    } else if is_color(value) {
+        Some(format!("border-color{}", side))
+    } else {
+        None
+    }
+}
+
+const COLORS: &[&str] = &[
+    "slate", "gray", "zinc", "neutral", "stone", "red", "orange", "amber", "yellow", "lime",
+    "green", "emerald", "teal", "cyan", "sky", "blue", "indigo", "violet", "purple", "fuchsia",
+    "pink", "rose",
+];
+
+// `red-500`, `red-500/50`, `white`, `[#fff]`
+fn is_color(value: &str) -> bool {
+    let value = value.split('/').next().unwrap_or(value);
+    if let Some(arbitrary) = value.strip_prefix('[') {
+        return arbitrary.starts_with('#')
+            || ["rgb", "hsl", "oklch", "color:"]
+                .iter()
+                .any(|prefix| arbitrary.starts_with(prefix));
+    }
+    if matches!(
+        value,
+        "inherit" | "current" | "transparent" | "black" | "white"
+    ) {
+        return true;
+    }
+    match value.split_once('-') {
+        Some((name, shade)) => COLORS.contains(&name) && shade.chars().all(|c| c.is_ascii_digit()),
+        None => false,
+    }
+}
@@ -0,0 +1,355 @@
+use crate::core::Renderable;
+use crate::selector::Selector;
+use crate::tree::{self, Element, TreeNode};
+
+/// Anything that can be compared as HTML: lira nodes and already rendered strings.
+pub trait IntoHtml {
+    fn into_html(self) -> String;
+}
+
+impl<R> IntoHtml for R
+where
+    R: Renderable,
+{
+    fn into_html(self) -> String {
+        self.render()
+    }
+}
+
+impl IntoHtml for &str {
+    fn into_html(self) -> String {
+        self.to_string()
+    }
+}
+
+impl IntoHtml for String {
+    fn into_html(self) -> String {
+        self
+    }
+}
+
+impl IntoHtml for &String {
+    fn into_html(self) -> String {
+        self.clone()
+    }
+}
+
+/// Asserts that two HTML fragments are structurally equal.
+///
+/// Attribute order and insignificant whitespace are ignored, on failure a line based diff of both trees is printed.
+///
+/// ```
+/// use lira::assert_html_eq;
+/// use lira::prelude::*;
+///
+/// assert_html_eq!(
+///     div().id("main").class("card").text("Hello"),
+///     r#"
+///     <div class="card" id="main">
+///         Hello
+///     </div>
+///     "#
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_html_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_html_eq(
+            $crate::testing::IntoHtml::into_html($actual),
+            $crate::testing::IntoHtml::into_html($expected),
+        )
+    };
+}
+
+/// Function behind [`assert_html_eq!`].
+#[track_caller]
+pub fn assert_html_eq(actual: impl AsRef<str>, expected: impl AsRef<str>) {
+    let actual = normalize(actual.as_ref());
+    let expected = normalize(expected.as_ref());
+
+    if actual != expected {
+        panic!(
+            "HTML is not equal (- expected, + actual):\n{}",
+            diff(&expected, &actual)
+        );
+    }
+}
+
+/// Renders `html` as an indented tree, one tag or text per line, with sorted attributes and collapsed whitespace.
+///
+/// Two fragments normalize to the same string if they only differ in formatting.
+pub fn normalize(html: &str) -> String {
+    normalize_nodes(&tree::parse(html))
+}
+
+pub(crate) fn normalize_nodes(nodes: &[TreeNode]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        write_node(&mut out, node, 0, false);
+    }
+    out
+}
+
+/// Asserts that at least one element in the HTML matches a CSS selector.
+///
+/// Supported are type, `*`, `#id`, `.class` and attribute selectors (`[a]`, `[a=v]`, `[a~=v]`, `[a^=v]`, `[a$=v]`,
+/// `[a*=v]`), the descendant and child (`>`) combinators and comma separated lists.
+///
+/// ```
+/// use lira::assert_selector;
+/// use lira::prelude::*;
+///
+/// let page = form().id("login").child(input().attr("name", "email"));
+///
+/// assert_selector!(page, "form#login input[name=email]");
+/// ```
+#[macro_export]
+macro_rules! assert_selector {
+    ($html:expr, $selector:expr $(,)?) => {
+        $crate::testing::assert_selector($crate::testing::IntoHtml::into_html($html), $selector)
+    };
+}
+
+/// Asserts that no element in the HTML matches a CSS selector, see [`assert_selector!`] for the supported syntax.
+#[macro_export]
+macro_rules! assert_no_selector {
+    ($html:expr, $selector:expr $(,)?) => {
+        $crate::testing::assert_no_selector($crate::testing::IntoHtml::into_html($html), $selector)
+    };
+}
+
+/// Function behind [`assert_selector!`].
+#[track_caller]
+pub fn assert_selector(html: impl AsRef<str>, selector: &str) {
+    let html = html.as_ref();
+    if select(html, selector, |_| ()).is_empty() {
+        panic!(
+            "no element matches \"{}\" in:\n{}",
+            selector,
+            normalize(html)
+        );
+    }
+}
+
+/// Function behind [`assert_no_selector!`].
+#[track_caller]
+pub fn assert_no_selector(html: impl AsRef<str>, selector: &str) {
+    let html = html.as_ref();
+    let count = select(html, selector, |_| ()).len();
+    if count > 0 {
+        panic!(
+            "{} element(s) match \"{}\" in:\n{}",
+            count,
+            selector,
+            normalize(html)
+        );
+    }
+}
+
+/// Number of elements matching `selector`.
+#[track_caller]
+pub fn select_count(html: impl IntoHtml, selector: &str) -> usize {
+    select(&html.into_html(), selector, |_| ()).len()
+}
+
+/// Text content of the first element matching `selector`, with collapsed whitespace.
+#[track_caller]
+pub fn select_text(html: impl IntoHtml, selector: &str) -> Option<String> {
+    select_all_text(html, selector).into_iter().next()
+}
+
+/// Text content of every element matching `selector`, with collapsed whitespace.
+#[track_caller]
+pub fn select_all_text(html: impl IntoHtml, selector: &str) -> Vec<String> {
+    select(&html.into_html(), selector, |element| {
+        collapse_whitespace(&element.text_content())
+    })
+}
+
+/// Value of attribute `name` on the first element matching `selector`, `Some("")` for attributes without a value.
+#[track_caller]
+pub fn select_attr(html: impl IntoHtml, selector: &str, name: &str) -> Option<String> {
+    select(&html.into_html(), selector, |element| {
+        element.attr(name).map(str::to_string)
+    })
+    .into_iter()
+    .flatten()
+    .next()
+}
+
+#[track_caller]
+fn select<T>(html: &str, selector: &str, map: impl Fn(&Element) -> T) -> Vec<T> {
+    let selector = Selector::parse(selector).unwrap_or_else(|err| panic!("{}", err));
+    selector
+        .select(&tree::parse(html))
+        .into_iter()
+        .map(map)
+        .collect()
+}
+
+fn write_node(out: &mut String, node: &TreeNode, depth: usize, preserve: bool) {
+    match node {
+        TreeNode::Doctype(doctype) => line(out, depth, &format!("<!{}>", doctype)),
+        TreeNode::Comment(comment) => line(out, depth, &format!("<!--{}-->", comment)),
+        TreeNode::Text(text) => {
+            let text = if preserve {
+                text.clone()
+            } else {
+                collapse_whitespace(text)
+            };
+            if !text.is_empty() {
+                line(out, depth, &format!("{:?}", text));
+            }
+        }
+        TreeNode::Element(element) => {
+            let mut attrs: Vec<_> = element.attrs.iter().collect();
+            attrs.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let mut tag = format!("<{}", element.name);
+            for attr in attrs {
+                tag.push(' ');
+                tag.push_str(&attr.name);
+                if let Some(value) = &attr.value {
+                    let value = if attr.name == "class" {
+                        collapse_whitespace(value)
+                    } else {
+                        value.clone()
+                    };
+                    tag.push_str(&format!("={:?}", value));
+                }
+            }
+            tag.push('>');
+            line(out, depth, &tag);
+
+            let preserve = preserve
+                || matches!(
+                    element.name.as_str(),
+                    "pre" | "textarea" | "script" | "style"
+                );
+            for child in &element.children {
+                write_node(out, child, depth + 1, preserve);
+            }
+
+            if !crate::tokenizer::is_void(&element.name) {
+                line(out, depth, &format!("</{}>", element.name));
+            }
+        }
+    }
+}
+
+fn line(out: &mut String, depth: usize, content: &str) {
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+    out.push_str(content);
+    out.push('\n');
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_ascii_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// line diff based on the longest common subsequence, found with Hirschberg's algorithm so it only needs memory
+// linear in the number of lines, normalized pages of big documents have thousands of them
+pub(crate) fn diff(expected: &str, actual: &str) -> String {
+    let a: Vec<_> = expected.lines().collect();
+    let b: Vec<_> = actual.lines().collect();
+
+    // lines both have at the start and the end don't need to go through the algorithm
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut edits = Vec::with_capacity(a.len().max(b.len()));
+    edits.extend(a[..prefix].iter().map(|line| Edit::Keep(line)));
+    edit_script(
+        &a[prefix..a.len() - suffix],
+        &b[prefix..b.len() - suffix],
+        &mut edits,
+    );
+    edits.extend(a[a.len() - suffix..].iter().map(|line| Edit::Keep(line)));
+
+    let mut out = String::new();
+    for edit in edits {
+        let (marker, line) = match edit {
+            Edit::Keep(line) => (' ', line),
+            Edit::Delete(line) => ('-', line),
+            Edit::Insert(line) => ('+', line),
+        };
+        out.push(marker);
+        out.push(' ');
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+enum Edit<'a> {
+    Keep(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+// the edits turning `a` into `b`, splitting `a` in half and `b` where the halves' longest common subsequences meet
+fn edit_script<'a>(a: &[&'a str], b: &[&'a str], out: &mut Vec<Edit<'a>>) {
+    if a.is_empty() {
+        out.extend(b.iter().map(|line| Edit::Insert(line)));
+        return;
+    }
+    if b.is_empty() {
+        out.extend(a.iter().map(|line| Edit::Delete(line)));
+        return;
+    }
+    if let [line] = a {
+        match b.iter().position(|other| other == line) {
+            Some(k) => {
+                out.extend(b[..k].iter().map(|line| Edit::Insert(line)));
+                out.push(Edit::Keep(line));
+                out.extend(b[k + 1..].iter().map(|line| Edit::Insert(line)));
+            }
+            None => {
+                out.push(Edit::Delete(line));
+                out.extend(b.iter().map(|line| Edit::Insert(line)));
+            }
+        }
+        return;
+    }
+
+    let mid = a.len() / 2;
+    let forward = lcs_lengths(a[..mid].iter(), b.iter());
+    let backward = lcs_lengths(a[mid..].iter().rev(), b.iter().rev());
+    let split = (0..=b.len())
+        .max_by_key(|&k| (forward[k] + backward[b.len() - k], std::cmp::Reverse(k)))
+        .unwrap_or_default();
+
+    edit_script(&a[..mid], &b[..split], out);
+    edit_script(&a[mid..], &b[split..], out);
+}
+
+// lengths of the longest common subsequence of `a` and every prefix of `b`, keeping a single row
+fn lcs_lengths<'a, 'b>(
+    a: impl Iterator<Item = &'a &'b str>,
+    b: impl Iterator<Item = &'a &'b str> + Clone,
+) -> Vec<usize>
+where
+    'b: 'a,
+{
+    let mut row = vec![0; b.clone().count() + 1];
+    for x in a {
+        let mut diagonal = 0;
+        for (j, y) in b.clone().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if x == y {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row
+}
@@ -0,0 +1,308 @@
+use crate::core::Renderable;
+use crate::toc::HEADING_ANCHOR;
+use crate::tree::{Element, RenderTree, Tree, TreeNode};
+
+/// Renders into readable plain text instead of HTML, see [`Tree::to_text`].
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = div()
+///     .child(h1().text("Your order"))
+///     .child(p().text("Thanks for shopping with us!"))
+///     .child(ul().child(li().text("1x Tea")).child(li().text("2x Cake")))
+///     .child(p().text("Track it ").child(a().href("https://shop.example/42").text("here")).text("."))
+///     .render_text();
+///
+/// assert_eq!(
+///     "Your order\n\nThanks for shopping with us!\n\n- 1x Tea\n- 2x Cake\n\nTrack it here (https://shop.example/42).",
+///     res
+/// );
+/// ```
+pub trait RenderText: RenderTree {
+    fn render_text(self) -> String {
+        self.render_tree().to_text()
+    }
+}
+
+impl<R> RenderText for R where R: Renderable {}
+
+impl Tree {
+    /// The text of the tree laid out like a browser would, for the plain-text part of multipart emails and for
+    /// search indexes.
+    ///
+    /// Block elements start on a line of their own, paragraphs, headings and lists are separated by a blank line.
+    /// List items get `- ` or their number, quotes are prefixed with `> `, table cells are separated by ` | ` and
+    /// links are followed by their URL in parentheses unless it's the link text already. Images are replaced by
+    /// their `alt` text, the head, scripts, styles and hidden elements are left out.
+    pub fn to_text(&self) -> String {
+        let mut writer = TextWriter::default();
+        writer.nodes(&self.nodes);
+        writer.finish()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum List {
+    Unordered,
+    Ordered(i64),
+}
+
+#[derive(Default)]
+struct TextWriter {
+    out: String,
+    // newlines owed before the next text, written lazily so nested blocks don't add up
+    pending: usize,
+    space: bool,
+    line_start: bool,
+    // written at the start of every line, e.g. the indentation of list items
+    prefixes: Vec<String>,
+    // written once before the next text, the bullet of a list item
+    marker: Option<String>,
+    lists: Vec<List>,
+    pre: usize,
+}
+
+impl TextWriter {
+    fn nodes(&mut self, nodes: &[TreeNode]) {
+        let mut cell = 0;
+        for node in nodes {
+            match node {
+                TreeNode::Text(text) => self.text(text),
+                TreeNode::Element(element) => {
+                    if matches!(element.name.as_str(), "td" | "th") {
+                        if cell > 0 {
+                            self.literal(" | ");
+                        }
+                        cell += 1;
+                    }
+                    self.element(element);
+                }
+                TreeNode::Doctype(_) | TreeNode::Comment(_) => {}
+            }
+        }
+    }
+
+    fn element(&mut self, element: &Element) {
+        let name = element.name.as_str();
+        if is_skipped(element) {
+            return;
+        }
+
+        match name {
+            "br" => self.line_break(),
+            "hr" => {
+                self.block(2);
+                self.literal("---");
+                self.block(2);
+            }
+            "img" => {
+                if let Some(alt) = element.attr("alt") {
+                    self.text(alt);
+                }
+            }
+            "a" => {
+                self.nodes(&element.children);
+                if let Some(url) = link_url(element) {
+                    self.text(&format!(" ({})", url));
+                }
+            }
+            "ul" | "ol" | "menu" => {
+                let list = if name == "ol" {
+                    let start = element
+                        .attr("start")
+                        .and_then(|start| start.trim().parse().ok());
+                    List::Ordered(start.unwrap_or(1))
+                } else {
+                    List::Unordered
+                };
+                // lists within list items only go on the next line
+                let gap = if self.lists.is_empty() { 2 } else { 1 };
+                self.block(gap);
+                self.lists.push(list);
+                self.nodes(&element.children);
+                self.lists.pop();
+                self.block(gap);
+            }
+            "li" => {
+                let marker = match self.lists.last_mut() {
+                    Some(List::Ordered(next)) => {
+                        let number = *next;
+                        *next += 1;
+                        format!("{}. ", number)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.block(1);
+                self.prefixes.push(" ".repeat(marker.chars().count()));
+                self.marker = Some(marker);
+                self.nodes(&element.children);
+                self.marker = None;
+                self.prefixes.pop();
+                self.block(1);
+            }
+            "blockquote" => {
+                self.block(2);
+                self.prefixes.push("> ".to_string());
+                self.nodes(&element.children);
+                self.prefixes.pop();
+                self.block(2);
+            }
+            "pre" => {
+                self.block(2);
+                self.pre += 1;
+                self.nodes(&element.children);
+                self.pre -= 1;
+                self.block(2);
+            }
+            _ => {
+                let gap = block_gap(name);
+                self.block(gap);
+                self.nodes(&element.children);
+                self.block(gap);
+            }
+        }
+    }
+
+    fn block(&mut self, newlines: usize) {
+        if newlines > 0 {
+            self.pending = self.pending.max(newlines);
+            self.space = false;
+        }
+    }
+
+    fn line_break(&mut self) {
+        self.flush();
+        self.trim_line_end();
+        self.out.push('\n');
+        self.line_start = true;
+        self.space = false;
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.pre > 0 {
+            for c in text.chars() {
+                if c == '\n' {
+                    self.line_break();
+                } else {
+                    self.write(c);
+                }
+            }
+            return;
+        }
+
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.space = true;
+            } else {
+                if self.space && !self.line_start && !self.out.is_empty() && self.pending == 0 {
+                    self.write(' ');
+                }
+                self.space = false;
+                self.write(c);
+            }
+        }
+    }
+
+    // written as it is, without collapsing whitespace
+    fn literal(&mut self, text: &str) {
+        self.space = false;
+        text.chars().for_each(|c| self.write(c));
+    }
+
+    fn write(&mut self, c: char) {
+        self.flush();
+        if self.line_start {
+            self.line_start = false;
+            for prefix in &self.prefixes {
+                self.out.push_str(prefix);
+            }
+            if let Some(marker) = self.marker.take() {
+                // the marker takes the place of the indentation of its own item
+                let indent = self.prefixes.last().map_or(0, String::len);
+                self.out.truncate(self.out.len() - indent);
+                self.out.push_str(&marker);
+            }
+        }
+        self.out.push(c);
+    }
+
+    fn flush(&mut self) {
+        if self.pending == 0 {
+            return;
+        }
+        if self.out.is_empty() {
+            self.pending = 0;
+            self.line_start = true;
+            return;
+        }
+
+        self.trim_line_end();
+        let present = self.out.len() - self.out.trim_end_matches('\n').len();
+        for _ in present..self.pending {
+            self.out.push('\n');
+        }
+        self.pending = 0;
+        self.line_start = true;
+    }
+
+    fn trim_line_end(&mut self) {
+        let len = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(len);
+    }
+
+    fn finish(self) -> String {
+        self.out.trim_end().to_string()
+    }
+}
+
+fn is_skipped(element: &Element) -> bool {
+    matches!(
+        element.name.as_str(),
+        "head"
+            | "title"
+            | "script"
+            | "style"
+            | "template"
+            | "noscript"
+            | "svg"
+            | "iframe"
+            | "object"
+            | "canvas"
+            | "select"
+            | "datalist"
+            | "textarea"
+            | "input"
+    ) || element.attr("hidden").is_some()
+        || element.attr("aria-hidden") == Some("true")
+        || element.has_class(HEADING_ANCHOR)
+}
+
+fn block_gap(name: &str) -> usize {
+    match name {
+        "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "table" | "figure" | "dl" => 2,
+        "div" | "section" | "article" | "header" | "footer" | "main" | "nav" | "aside" | "tr"
+        | "dt" | "dd" | "details" | "summary" | "figcaption" | "address" | "fieldset" | "form"
+        | "legend" | "caption" | "body" | "html" | "thead" | "tbody" | "tfoot" => 1,
+        _ => 0,
+    }
+}
+
+// the URL to write after a link, unless it doesn't add anything to the text
+fn link_url(element: &Element) -> Option<&str> {
+    let href = element.attr("href")?.trim();
+    let text = element.text_content();
+    let text = text.trim();
+    let redundant = href.is_empty()
+        || href.starts_with('#')
+        || href.starts_with("javascript:")
+        || href == text
+        || href
+            .strip_prefix("mailto:")
+            .or_else(|| href.strip_prefix("tel:"))
+            .is_some_and(|rest| rest == text)
+        || href
+            .split_once("://")
+            .is_some_and(|(_, rest)| rest.trim_end_matches('/') == text.trim_end_matches('/'));
+    (!redundant).then_some(href)
+}
@@ -0,0 +1,205 @@
+use crate::a11y::visually_hidden;
+use crate::context;
+use crate::core::{self, Content, HasAttributes, Node, Renderable};
+use crate::html5::{HasGlobalAttributes, HasHref, Heading, HeadingLevel, Ol, a, h, li, ol, span};
+use crate::ids::{self, Slugs};
+use crate::tree::{RenderTree, Tree, TreeNode};
+
+/// Renders with a table of contents of the `<h2>` to `<h4>` headings, see [`RenderToc::with_toc`].
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let (article, toc) = article()
+///     .child(h2().text("Install"))
+///     .child(h3().id("cargo").text("With cargo"))
+///     .child(h2().text("Usage"))
+///     .with_toc();
+///
+/// let res = div().child(nav().child(toc)).child(article).render();
+///
+/// assert_eq!(
+///     "<div><nav><ol><li><a href=\"#install\">Install</a><ol><li><a href=\"#cargo\">With cargo</a></li></ol>\
+///      </li><li><a href=\"#usage\">Usage</a></li></ol></nav><article><h2 id=\"install\">Install</h2>\
+///      <h3 id=\"cargo\">With cargo</h3><h2 id=\"usage\">Usage</h2></article></div>",
+///     res
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Toc {
+    entries: Vec<TocEntry>,
+}
+
+/// A heading in a [`Toc`], with the headings of its section as children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// 2 for `<h2>` up to 4 for `<h4>`.
+    pub level: u8,
+    pub id: String,
+    pub text: String,
+    pub children: Vec<TocEntry>,
+}
+
+impl Toc {
+    /// The top level entries, usually the `<h2>` headings.
+    pub fn entries(&self) -> &[TocEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // a heading nests into the last entry of a lower level, skipped levels don't leave gaps
+    fn push(entries: &mut Vec<TocEntry>, entry: TocEntry) {
+        match entries.last_mut() {
+            Some(last) if last.level < entry.level => Self::push(&mut last.children, entry),
+            _ => entries.push(entry),
+        }
+    }
+}
+
+/// Renders the entries as nested `<ol>` lists of links to the headings, nothing if there are none.
+impl Renderable for Toc {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        if !self.entries.is_empty() {
+            list(self.entries).render_into(buf);
+        }
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+fn list(entries: Vec<TocEntry>) -> Node<Ol, Content> {
+    ol().children(entries, |entry| {
+        li().child(a().href(format!("#{}", entry.id)).text(entry.text))
+            .child_when(!entry.children.is_empty(), || list(entry.children))
+    })
+}
+
+/// Collects a table of contents while rendering.
+pub trait RenderToc: RenderTree {
+    /// Renders into a [`Tree`] and collects its `<h2>`, `<h3>` and `<h4>` headings into a [`Toc`], which can be
+    /// placed anywhere in the page, before the content as well.
+    ///
+    /// Headings without an `id` get one made from their text, numbered if the page has it already, e.g.
+    /// `faq-2` for a second "FAQ". Parsing the output makes this slower than a plain `render()`.
+    fn with_toc(self) -> (Tree, Toc) {
+        let mut tree = self.render_tree();
+
+        let mut slugs = Slugs::default();
+        tree.walk(|element| {
+            if let Some(id) = element.attr("id") {
+                slugs.reserve(id);
+            }
+        });
+
+        let mut toc = Toc::default();
+        tree.walk_mut(|element| {
+            let level = match element.name.as_str() {
+                "h2" => 2,
+                "h3" => 3,
+                "h4" => 4,
+                _ => return,
+            };
+            let text = heading_text(&element.children);
+            let id = match element.attr("id") {
+                Some(id) => id.to_string(),
+                None => {
+                    let id = slugs.unique(&text, "section");
+                    element.set_attr("id", &id);
+                    id
+                }
+            };
+
+            Toc::push(
+                &mut toc.entries,
+                TocEntry {
+                    level,
+                    id,
+                    text,
+                    children: Vec::new(),
+                },
+            );
+        });
+
+        (tree, toc)
+    }
+}
+
+impl<R> RenderToc for R where R: Renderable {}
+
+/// Class of the links [`heading_anchored`] adds to its headings.
+pub const HEADING_ANCHOR: &str = "heading-anchor";
+
+/// A heading with an `id` made from its text and a link to itself, the pattern docs sites use so readers can
+/// copy a link to a section.
+///
+/// The link shows a `#` hidden from screen readers and has a visually hidden name instead, style it with the
+/// [`HEADING_ANCHOR`] class, e.g. to only show it while the heading is hovered. Headings with the same text get
+/// numbered ids like `usage-2` within a [`RenderContext`](crate::context::RenderContext) scope, outside of one
+/// the slug is used as it is.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = RenderContext::new().scope(|| {
+///     div()
+///         .child(heading_anchored(2, "Getting started"))
+///         .child(heading_anchored(2, "Getting started"))
+///         .render()
+/// });
+///
+/// assert_eq!(
+///     "<div><h2 id=\"getting-started\">Getting started<a href=\"#getting-started\" class=\"heading-anchor\">\
+///      <span aria-hidden=\"true\">#</span><span class=\"visually-hidden\">Link to this section</span></a></h2>\
+///      <h2 id=\"getting-started-2\">Getting started<a href=\"#getting-started-2\" class=\"heading-anchor\">\
+///      <span aria-hidden=\"true\">#</span><span class=\"visually-hidden\">Link to this section</span></a></h2></div>",
+///     res
+/// );
+/// ```
+pub fn heading_anchored(
+    level: impl Into<HeadingLevel>,
+    text: impl AsRef<str>,
+) -> Node<Heading, Content> {
+    let text = text.as_ref();
+    let id = context::unique_slug(text, "section").unwrap_or_else(|| ids::slug_or(text, "section"));
+
+    h(level.into()).id(&id).text(text).child(
+        a().href(format!("#{}", id))
+            .class(HEADING_ANCHOR)
+            .child(span().attr("aria-hidden", "true").text("#"))
+            .child(visually_hidden("Link to this section")),
+    )
+}
+
+// text of a heading with whitespace collapsed, leaving out parts hidden from screen readers like icons and the
+// links of `heading_anchored`
+fn heading_text(nodes: &[TreeNode]) -> String {
+    fn collect(nodes: &[TreeNode], out: &mut String) {
+        for node in nodes {
+            match node {
+                TreeNode::Text(text) => out.push_str(text),
+                TreeNode::Element(element)
+                    if element.attr("aria-hidden") != Some("true")
+                        && !element.has_class(HEADING_ANCHOR) =>
+                {
+                    collect(&element.children, out)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut text = String::new();
+    collect(nodes, &mut text);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
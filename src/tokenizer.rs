@@ -0,0 +1,528 @@
+// Tokenizer for rendered HTML.
+//
+// It is written for lira's own output, which is always well formed, but stays lenient enough to cope with the
+// hand-written markup users put into raw() and with the usual shortcuts like unquoted attributes or void elements
+// without a trailing slash.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::named_references;
+
+/// Attribute of a [`tree::Element`](crate::tree::Element), `value` is `None` for flags like `disabled`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token<'a> {
+    Doctype(&'a str),
+    StartTag {
+        name: String,
+        attrs: Vec<Attribute>,
+        /// Where each of `attrs` is in the source, from the start of the name to the end of the value.
+        spans: Vec<Range<usize>>,
+        self_closing: bool,
+    },
+    EndTag {
+        name: String,
+    },
+    /// Decoded text, apart from the contents of `<script>` and `<style>` which are kept as is.
+    Text(Cow<'a, str>),
+    Comment(&'a str),
+}
+
+/// Elements that never have content or an end tag.
+pub(crate) const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+pub(crate) fn is_void(name: &str) -> bool {
+    VOID_ELEMENTS.contains(&name)
+}
+
+/// Splits `src` into tokens, each paired with the byte offset it starts at.
+pub(crate) fn tokenize(src: &str) -> Vec<(usize, Token<'_>)> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut text_start = 0;
+
+    while pos < bytes.len() {
+        if bytes[pos] != b'<' {
+            pos += 1;
+            continue;
+        }
+
+        let Some((token, end)) = markup(src, pos) else {
+            pos += 1;
+            continue;
+        };
+
+        push_text(&mut tokens, src, text_start, pos, true);
+
+        let raw_text = match &token {
+            Token::StartTag {
+                name,
+                self_closing: false,
+                ..
+            } => match name.as_str() {
+                "script" | "style" => Some((name.clone(), false)),
+                "textarea" | "title" => Some((name.clone(), true)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        tokens.push((pos, token));
+        pos = end;
+
+        // contents of these elements can't contain other elements, only their end tag ends them
+        if let Some((name, decode)) = raw_text {
+            let close = find_end_tag(src, pos, &name).unwrap_or(bytes.len());
+            push_text(&mut tokens, src, pos, close, decode);
+            pos = close;
+        }
+
+        text_start = pos;
+    }
+
+    push_text(&mut tokens, src, text_start, bytes.len(), true);
+    tokens
+}
+
+fn push_text<'a>(
+    tokens: &mut Vec<(usize, Token<'a>)>,
+    src: &'a str,
+    start: usize,
+    end: usize,
+    decode: bool,
+) {
+    if start >= end {
+        return;
+    }
+
+    let text = &src[start..end];
+    let text = if decode {
+        decode_entities(text)
+    } else {
+        Cow::Borrowed(text)
+    };
+    tokens.push((start, Token::Text(text)));
+}
+
+fn find_end_tag(src: &str, from: usize, name: &str) -> Option<usize> {
+    let bytes = src.as_bytes();
+    let mut pos = from;
+
+    while let Some(i) = src[pos..].find("</") {
+        let start = pos + i;
+        let name_start = start + 2;
+        let name_end = name_start + name.len();
+
+        if name_end <= bytes.len()
+            && bytes[name_start..name_end].eq_ignore_ascii_case(name.as_bytes())
+            && bytes
+                .get(name_end)
+                .is_none_or(|b| b.is_ascii_whitespace() || *b == b'>' || *b == b'/')
+        {
+            return Some(start);
+        }
+
+        pos = start + 2;
+    }
+
+    None
+}
+
+fn markup(src: &str, pos: usize) -> Option<(Token<'_>, usize)> {
+    let rest = &src[pos..];
+    let bytes = rest.as_bytes();
+
+    if let Some(comment) = rest.strip_prefix("<!--") {
+        return Some(match comment.find("-->") {
+            Some(end) => (Token::Comment(&comment[..end]), pos + 4 + end + 3),
+            None => (Token::Comment(comment), src.len()),
+        });
+    }
+
+    if let Some(decl) = rest.strip_prefix("<!") {
+        let end = decl.find('>')?;
+        return Some((Token::Doctype(&decl[..end]), pos + 2 + end + 1));
+    }
+
+    if let Some(tag) = rest.strip_prefix("</") {
+        let name_len = tag_name_len(tag.as_bytes());
+        if name_len == 0 {
+            return None;
+        }
+        let end = tag.find('>')?;
+        let name = element_name(&tag[..name_len]);
+        return Some((Token::EndTag { name }, pos + 2 + end + 1));
+    }
+
+    if bytes.len() < 2 || !bytes[1].is_ascii_alphabetic() {
+        return None;
+    }
+
+    let name_len = tag_name_len(&bytes[1..]);
+    let name = element_name(&rest[1..1 + name_len]);
+    let mut i = 1 + name_len;
+    let mut attrs = Vec::new();
+    let mut spans = Vec::new();
+
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        match bytes.get(i) {
+            None => return None,
+            Some(b'>') => {
+                return Some((
+                    Token::StartTag {
+                        name,
+                        attrs,
+                        spans,
+                        self_closing: false,
+                    },
+                    pos + i + 1,
+                ));
+            }
+            Some(b'/') if bytes.get(i + 1) == Some(&b'>') => {
+                return Some((
+                    Token::StartTag {
+                        name,
+                        attrs,
+                        spans,
+                        self_closing: true,
+                    },
+                    pos + i + 2,
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let name_start = i;
+        while i < bytes.len() && !ends_attr_name(bytes, i) {
+            i += 1;
+        }
+        let attr_name = attribute_name(&rest[name_start..i]);
+
+        // a stray '/' that isn't part of "/>"
+        if attr_name.is_empty() {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i;
+        while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+
+        if bytes.get(j) != Some(&b'=') {
+            attrs.push(Attribute {
+                name: attr_name,
+                value: None,
+            });
+            spans.push(pos + name_start..pos + i);
+            continue;
+        }
+
+        i = j + 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        let value = match bytes.get(i) {
+            Some(&quote @ (b'"' | b'\'')) => {
+                let end = rest[i + 1..].find(quote as char)? + i + 1;
+                let value = &rest[i + 1..end];
+                i = end + 1;
+                value
+            }
+            _ => {
+                let start = i;
+                while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                    i += 1;
+                }
+                &rest[start..i]
+            }
+        };
+
+        attrs.push(Attribute {
+            name: attr_name,
+            value: Some(decode_attr_value(value).into_owned()),
+        });
+        spans.push(pos + name_start..pos + i);
+    }
+}
+
+fn ends_attr_name(bytes: &[u8], i: usize) -> bool {
+    match bytes[i] {
+        b'=' | b'>' => true,
+        b'/' => bytes.get(i + 1) == Some(&b'>'),
+        b => b.is_ascii_whitespace(),
+    }
+}
+
+fn tag_name_len(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b':' | b'_'))
+        .count()
+}
+
+/// Replaces character references with the characters they stand for, unknown references are kept verbatim.
+pub(crate) fn decode_entities(src: &str) -> Cow<'_, str> {
+    decode(src, false)
+}
+
+// attribute values don't decode references without `;` that are followed by `=` or an alphanumeric, so query
+// strings like `?a=1&copy=2` stay intact
+fn decode_attr_value(src: &str) -> Cow<'_, str> {
+    decode(src, true)
+}
+
+fn decode(src: &str, in_attr: bool) -> Cow<'_, str> {
+    if !src.contains('&') {
+        return Cow::Borrowed(src);
+    }
+
+    let mut out = String::with_capacity(src.len());
+    let mut rest = src;
+
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        match decode_entity(rest, in_attr) {
+            Some((c, len)) => {
+                out.push_str(c.as_ref());
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+// decodes the reference at the start of `src` like browsers do, returns the replacement and the length of the
+// reference
+fn decode_entity(src: &str, in_attr: bool) -> Option<(Cow<'static, str>, usize)> {
+    let bytes = src.as_bytes();
+
+    if bytes.get(1) == Some(&b'#') {
+        let hex = matches!(bytes.get(2), Some(b'x' | b'X'));
+        let start = if hex { 3 } else { 2 };
+        let digits = bytes[start..]
+            .iter()
+            .take_while(|b| {
+                if hex {
+                    b.is_ascii_hexdigit()
+                } else {
+                    b.is_ascii_digit()
+                }
+            })
+            .count();
+        if digits == 0 {
+            return None;
+        }
+
+        let end = start + digits;
+        let code =
+            u32::from_str_radix(&src[start..end], if hex { 16 } else { 10 }).unwrap_or(u32::MAX);
+        let len = if bytes.get(end) == Some(&b';') {
+            end + 1
+        } else {
+            end
+        };
+        return Some((Cow::Owned(numeric_char(code).to_string()), len));
+    }
+
+    let name_len = bytes[1..]
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric())
+        .count();
+    let name = &src[1..1 + name_len];
+
+    if bytes.get(1 + name_len) == Some(&b';')
+        && let Some(c) = named_reference(name)
+    {
+        return Some((Cow::Borrowed(c), name_len + 2));
+    }
+
+    // the longest reference that is also valid without `;`
+    let legacy = (2..=name_len.min(6))
+        .rev()
+        .map(|len| &name[..len])
+        .find(|prefix| {
+            named_references::LEGACY_REFERENCES
+                .binary_search(prefix)
+                .is_ok()
+        })?;
+    let next = bytes.get(1 + legacy.len());
+    if in_attr && next.is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'=') {
+        return None;
+    }
+    Some((Cow::Borrowed(named_reference(legacy)?), legacy.len() + 1))
+}
+
+fn named_reference(name: &str) -> Option<&'static str> {
+    named_references::NAMED_REFERENCES
+        .binary_search_by(|(probe, _)| (*probe).cmp(name))
+        .ok()
+        .map(|i| named_references::NAMED_REFERENCES[i].1)
+}
+
+// characters that can't be written as references are replaced, the C1 controls are read as windows-1252 like
+// browsers do
+fn numeric_char(code: u32) -> char {
+    const WINDOWS_1252: [u32; 32] = [
+        0x20ac, 0x81, 0x201a, 0x192, 0x201e, 0x2026, 0x2020, 0x2021, 0x2c6, 0x2030, 0x160, 0x2039,
+        0x152, 0x8d, 0x17d, 0x8f, 0x90, 0x2018, 0x2019, 0x201c, 0x201d, 0x2022, 0x2013, 0x2014,
+        0x2dc, 0x2122, 0x161, 0x203a, 0x153, 0x9d, 0x17e, 0x178,
+    ];
+
+    let code = match code {
+        0x80..=0x9f => WINDOWS_1252[(code - 0x80) as usize],
+        0 => 0xfffd,
+        code => code,
+    };
+    char::from_u32(code).unwrap_or('\u{fffd}')
+}
+
+/// Name of an element as the tokenizer reports it, lowercased apart from the SVG elements whose case HTML
+/// parsers restore, like `linearGradient`.
+pub(crate) fn element_name(name: &str) -> String {
+    restore_case(name, SVG_ELEMENTS)
+}
+
+/// Name of an attribute as the tokenizer reports it, lowercased apart from the SVG and MathML attributes whose
+/// case HTML parsers restore, like `viewBox`.
+pub(crate) fn attribute_name(name: &str) -> String {
+    restore_case(name, SVG_ATTRIBUTES)
+}
+
+fn restore_case(name: &str, names: &[&str]) -> String {
+    match names.binary_search_by(|probe| {
+        probe
+            .bytes()
+            .map(|b| b.to_ascii_lowercase())
+            .cmp(name.bytes().map(|b| b.to_ascii_lowercase()))
+    }) {
+        Ok(i) => names[i].to_string(),
+        Err(_) => name.to_ascii_lowercase(),
+    }
+}
+
+// the tables of the HTML standard, sorted by their lowercase names
+const SVG_ELEMENTS: &[&str] = &[
+    "altGlyph",
+    "altGlyphDef",
+    "altGlyphItem",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "clipPath",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "foreignObject",
+    "glyphRef",
+    "linearGradient",
+    "radialGradient",
+    "textPath",
+];
+
+const SVG_ATTRIBUTES: &[&str] = &[
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseProfile",
+    "calcMode",
+    "clipPathUnits",
+    "definitionURL",
+    "diffuseConstant",
+    "edgeMode",
+    "filterUnits",
+    "glyphRef",
+    "gradientTransform",
+    "gradientUnits",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "limitingConeAngle",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "maskContentUnits",
+    "maskUnits",
+    "numOctaves",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "requiredExtensions",
+    "requiredFeatures",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "textLength",
+    "viewBox",
+    "viewTarget",
+    "xChannelSelector",
+    "yChannelSelector",
+    "zoomAndPan",
+];
@@ -0,0 +1,403 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::header::{self, HeaderValue};
+use http::{HeaderMap, Request, Response, StatusCode};
+use http_body::{Body, Frame, SizeHint};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::compress::gzip;
+use crate::core::Renderable;
+use crate::etag::ContentHash;
+
+/// Wraps services whose responses are renderables, turning them into `text/html` responses, see [`HtmlService`].
+///
+/// ```
+/// use std::convert::Infallible;
+///
+/// use lira::prelude::*;
+/// use lira::tower::HtmlLayer;
+/// use tower::{ServiceBuilder, service_fn};
+///
+/// let service = ServiceBuilder::new()
+///     .layer(HtmlLayer::new())
+///     .service(service_fn(|_: http::Request<()>| async {
+///         Ok::<_, Infallible>(p().text("Hello"))
+///     }));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HtmlLayer {
+    etag: bool,
+    gzip: bool,
+    gzip_min_len: usize,
+}
+
+impl HtmlLayer {
+    /// ETags and gzip on, only bodies of at least 1 KiB are compressed.
+    pub fn new() -> Self {
+        Self {
+            etag: true,
+            gzip: true,
+            gzip_min_len: 1024,
+        }
+    }
+
+    /// Adds an `ETag` from the hash of the rendered body and answers a matching `If-None-Match` with
+    /// `304 Not Modified`. Gzipped bodies get the tag with a `-gzip` suffix, e.g. `"<hash>-gzip"`.
+    pub fn etag(mut self, value: bool) -> Self {
+        self.etag = value;
+        self
+    }
+
+    /// Compresses bodies for clients sending `Accept-Encoding: gzip`. Turn it off when a compression layer or
+    /// proxy in front of the service does it already.
+    pub fn gzip(mut self, value: bool) -> Self {
+        self.gzip = value;
+        self
+    }
+
+    /// Smallest body worth compressing, in bytes.
+    pub fn gzip_min_len(mut self, len: usize) -> Self {
+        self.gzip_min_len = len;
+        self
+    }
+}
+
+impl Default for HtmlLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for HtmlLayer {
+    type Service = HtmlService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HtmlService {
+            inner,
+            config: *self,
+        }
+    }
+}
+
+/// Renders the responses of `S` into `text/html; charset=utf-8` responses, built by [`HtmlLayer`].
+///
+/// The inner service returns anything implementing [`IntoHtmlResponse`]: a renderable for a `200 OK` page, or an
+/// [`HtmlResponse`] to pick the status, add headers or stream the page in chunks. Rendered pages get an `ETag`
+/// and are gzipped if the client accepts it, streamed pages are sent as they are.
+#[derive(Debug, Clone)]
+pub struct HtmlService<S> {
+    inner: S,
+    config: HtmlLayer,
+}
+
+impl<S, B> Service<Request<B>> for HtmlService<S>
+where
+    S: Service<Request<B>>,
+    S::Response: IntoHtmlResponse,
+{
+    type Response = Response<HtmlBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let negotiated = Negotiated {
+            gzip: self.config.gzip && accepts_gzip(req.headers()),
+            if_none_match: req.headers().get(header::IF_NONE_MATCH).cloned(),
+        };
+
+        ResponseFuture {
+            inner: self.inner.call(req),
+            config: self.config,
+            negotiated,
+        }
+    }
+}
+
+// what the request asked for, kept until the response is ready
+#[derive(Debug)]
+struct Negotiated {
+    gzip: bool,
+    if_none_match: Option<HeaderValue>,
+}
+
+pin_project_lite::pin_project! {
+    /// Future of [`HtmlService`], renders the response of the inner service once it's ready.
+    pub struct ResponseFuture<F> {
+        #[pin]
+        inner: F,
+        config: HtmlLayer,
+        negotiated: Negotiated,
+    }
+}
+
+impl<F, R, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<R, E>>,
+    R: IntoHtmlResponse,
+{
+    type Output = Result<Response<HtmlBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let response = match this.inner.poll(cx) {
+            Poll::Ready(Ok(response)) => response.into_html_response(),
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(Ok(finish(response, this.config, this.negotiated)))
+    }
+}
+
+fn finish(
+    response: HtmlResponse,
+    config: &HtmlLayer,
+    negotiated: &Negotiated,
+) -> Response<HtmlBody> {
+    let HtmlResponse {
+        status,
+        mut headers,
+        content,
+    } = response;
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/html; charset=utf-8"),
+    );
+
+    let html = match content {
+        Content::Rendered(html) => html,
+        Content::Streamed(chunks) => {
+            return build(status, headers, HtmlBody::chunked(chunks));
+        }
+    };
+
+    let compress = negotiated.gzip && html.len() >= config.gzip_min_len;
+
+    if config.etag && status.is_success() {
+        let hash = ContentHash::of(&html);
+        // a strong tag has to differ between the encodings of the page
+        let etag = if compress {
+            format!("\"{}-gzip\"", hash)
+        } else {
+            hash.etag()
+        };
+        let matches = negotiated
+            .if_none_match
+            .as_ref()
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| etag_matches(value, &etag));
+        if let Ok(value) = HeaderValue::from_str(&etag) {
+            headers.insert(header::ETAG, value);
+        }
+        if matches {
+            headers.remove(header::CONTENT_TYPE);
+            return build(StatusCode::NOT_MODIFIED, headers, HtmlBody::empty());
+        }
+    }
+
+    if config.gzip {
+        // the body differs by encoding, caches have to know
+        headers.append(header::VARY, HeaderValue::from_static("accept-encoding"));
+    }
+    if compress {
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        return build(status, headers, HtmlBody::full(gzip(html.as_bytes())));
+    }
+
+    build(status, headers, HtmlBody::full(html))
+}
+
+fn build(status: StatusCode, headers: HeaderMap, body: HtmlBody) -> Response<HtmlBody> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+    response
+}
+
+// `gzip`, `x-gzip` or `*` with a quality other than 0, an entry for gzip itself takes precedence over `*`
+fn accepts_gzip(headers: &HeaderMap) -> bool {
+    let mut gzip = None;
+    let mut any = None;
+
+    for coding in headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+    {
+        let mut parts = coding.split(';');
+        let name = parts.next().unwrap_or_default().trim();
+        let accepted = !parts.any(|param| {
+            param
+                .trim()
+                .strip_prefix("q=")
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+        });
+
+        if name.eq_ignore_ascii_case("gzip") || name.eq_ignore_ascii_case("x-gzip") {
+            gzip = Some(gzip.unwrap_or(false) || accepted);
+        } else if name == "*" {
+            any = Some(accepted);
+        }
+    }
+
+    gzip.or(any).unwrap_or(false)
+}
+
+// weak comparison as RFC 9110 asks for `If-None-Match`
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.trim() == "*"
+        || if_none_match
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == etag)
+}
+
+/// Responses [`HtmlService`] can render, every renderable is one.
+pub trait IntoHtmlResponse {
+    fn into_html_response(self) -> HtmlResponse;
+}
+
+impl<R> IntoHtmlResponse for R
+where
+    R: Renderable,
+{
+    fn into_html_response(self) -> HtmlResponse {
+        HtmlResponse::new(self)
+    }
+}
+
+impl IntoHtmlResponse for HtmlResponse {
+    fn into_html_response(self) -> HtmlResponse {
+        self
+    }
+}
+
+/// A page with a status and headers, for handlers behind [`HtmlService`] that need more than `200 OK`.
+///
+/// ```
+/// use lira::prelude::*;
+/// use lira::tower::HtmlResponse;
+///
+/// let response = HtmlResponse::new(h1().text("Not found"))
+///     .status(http::StatusCode::NOT_FOUND)
+///     .header(http::header::CACHE_CONTROL, "no-store");
+/// ```
+pub struct HtmlResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    content: Content,
+}
+
+enum Content {
+    Rendered(String),
+    Streamed(Box<dyn Iterator<Item = String> + Send>),
+}
+
+impl HtmlResponse {
+    pub fn new(page: impl Renderable) -> Self {
+        Self::with_content(Content::Rendered(page.render()))
+    }
+
+    /// Streams the page, each item is rendered into its own chunk while the body is sent, see
+    /// [`chunked_body`](crate::body::chunked_body). Streamed pages get no `ETag` and aren't compressed.
+    pub fn streamed<It, R>(chunks: It) -> Self
+    where
+        It: IntoIterator<Item = R>,
+        It::IntoIter: Send + 'static,
+        R: Renderable + 'static,
+    {
+        Self::with_content(Content::Streamed(Box::new(
+            chunks.into_iter().map(Renderable::render),
+        )))
+    }
+
+    fn with_content(content: Content) -> Self {
+        Self {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            content,
+        }
+    }
+
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Adds a header, values that aren't valid header values are left out.
+    pub fn header(mut self, name: header::HeaderName, value: impl AsRef<str>) -> Self {
+        if let Ok(value) = HeaderValue::from_str(value.as_ref()) {
+            self.headers.append(name, value);
+        }
+        self
+    }
+}
+
+/// Body of the responses of [`HtmlService`].
+pub struct HtmlBody {
+    kind: BodyKind,
+}
+
+enum BodyKind {
+    Full(Option<Bytes>),
+    Chunked(Box<dyn Iterator<Item = String> + Send>),
+}
+
+impl HtmlBody {
+    fn full(data: impl Into<Bytes>) -> Self {
+        Self {
+            kind: BodyKind::Full(Some(data.into())),
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            kind: BodyKind::Full(None),
+        }
+    }
+
+    fn chunked(chunks: Box<dyn Iterator<Item = String> + Send>) -> Self {
+        Self {
+            kind: BodyKind::Chunked(chunks),
+        }
+    }
+}
+
+impl Body for HtmlBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        let data = match &mut self.get_mut().kind {
+            BodyKind::Full(data) => data.take(),
+            BodyKind::Chunked(chunks) => chunks.next().map(Bytes::from),
+        };
+        Poll::Ready(data.map(|data| Ok(Frame::data(data))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        matches!(self.kind, BodyKind::Full(None))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.kind {
+            BodyKind::Full(data) => {
+                SizeHint::with_exact(data.as_ref().map_or(0, |data| data.len() as u64))
+            }
+            BodyKind::Chunked(_) => SizeHint::default(),
+        }
+    }
+}
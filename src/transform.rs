@@ -0,0 +1,114 @@
+use crate::core::Renderable;
+use crate::tree::{Element, RenderTree, Tree};
+
+/// Rewrites the elements of a [`Tree`], to apply a policy to a whole page instead of at every call site.
+///
+/// Closures taking a `&mut Element` are transforms, several can be combined with [`then`](Transform::then):
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let policy = lazy_images().then(|element: &mut lira::tree::Element| {
+///     if element.name == "table" {
+///         element.add_class("table");
+///     }
+/// });
+///
+/// let res = div()
+///     .child(img().src("/a.png"))
+///     .child(table().close())
+///     .render_transformed(&policy);
+///
+/// assert_eq!(
+///     "<div><img src=\"/a.png\" loading=\"lazy\" /><table class=\"table\"></table></div>",
+///     res
+/// );
+/// ```
+pub trait Transform {
+    /// Called with every element, parents before their children.
+    fn element(&self, element: &mut Element);
+
+    /// Applies `next` after this transform.
+    fn then<T>(self, next: T) -> Then<Self, T>
+    where
+        Self: Sized,
+        T: Transform,
+    {
+        Then(self, next)
+    }
+}
+
+impl<F> Transform for F
+where
+    F: Fn(&mut Element),
+{
+    fn element(&self, element: &mut Element) {
+        self(element)
+    }
+}
+
+/// Two transforms applied one after the other, see [`Transform::then`].
+pub struct Then<A, B>(A, B);
+
+impl<A, B> Transform for Then<A, B>
+where
+    A: Transform,
+    B: Transform,
+{
+    fn element(&self, element: &mut Element) {
+        self.0.element(element);
+        self.1.element(element);
+    }
+}
+
+impl Tree {
+    pub fn transform(&mut self, transform: &impl Transform) {
+        self.walk_mut(|element| transform.element(element));
+    }
+}
+
+/// Renders through a [`Tree`] so a [`Transform`] can rewrite it.
+///
+/// Parsing the output makes this considerably slower than a plain `render()`.
+pub trait RenderTransformed: RenderTree {
+    fn render_transformed(self, transform: &impl Transform) -> String {
+        let mut tree = self.render_tree();
+        tree.transform(transform);
+        tree.render()
+    }
+}
+
+impl<R> RenderTransformed for R where R: Renderable {}
+
+/// Adds `loading="lazy"` to every `<img>` and `<iframe>` that doesn't set `loading` itself.
+pub fn lazy_images() -> impl Transform {
+    |element: &mut Element| {
+        if matches!(element.name.as_str(), "img" | "iframe") && element.attr("loading").is_none() {
+            element.set_attr("loading", "lazy");
+        }
+    }
+}
+
+/// Adds `class` to every link with `rel="external"`.
+pub fn external_link_class(class: impl AsRef<str>) -> impl Transform {
+    let class = class.as_ref().to_string();
+    move |element: &mut Element| {
+        let external = element
+            .attr("rel")
+            .is_some_and(|rel| rel.split_ascii_whitespace().any(|rel| rel == "external"));
+        if element.name == "a" && external {
+            element.add_class(&class);
+        }
+    }
+}
+
+/// Adds `nonce` to every `<script>` and `<style>` that doesn't have one, e.g. for markup inserted with `raw()`
+/// that doesn't pick up the nonce of the render context.
+pub fn inject_nonce(nonce: impl AsRef<str>) -> impl Transform {
+    let nonce = nonce.as_ref().to_string();
+    move |element: &mut Element| {
+        if matches!(element.name.as_str(), "script" | "style") && element.attr("nonce").is_none() {
+            element.set_attr("nonce", &nonce);
+        }
+    }
+}
@@ -0,0 +1,495 @@
+use std::borrow::Cow;
+
+use crate::core::{self, Renderable};
+use crate::testing;
+use crate::tokenizer::{self, Token};
+
+pub use crate::tokenizer::Attribute;
+
+/// HTML as a tree that can be inspected and changed before it's serialized, see [`RenderTree`].
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let mut tree = ul()
+///     .child(li().child(a().href("https://example.com").text("Example")))
+///     .child(li().child(a().href("/about").text("About")))
+///     .render_tree();
+///
+/// tree.walk_mut(|element| {
+///     if element.name == "a" && element.attr("href").is_some_and(|href| href.starts_with("https://")) {
+///         element.set_attr("rel", "external");
+///     }
+/// });
+///
+/// assert_eq!(
+///     "<ul><li><a href=\"https://example.com\" rel=\"external\">Example</a></li>\
+///      <li><a href=\"/about\">About</a></li></ul>",
+///     tree.render()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tree {
+    pub nodes: Vec<TreeNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeNode {
+    /// The declaration without `<!` and `>`, e.g. `DOCTYPE html`.
+    Doctype(String),
+    Element(Element),
+    /// Decoded text, the content of `<script>` and `<style>` is kept as is.
+    Text(String),
+    Comment(String),
+}
+
+#[derive(Debug, Clone, Eq)]
+pub struct Element {
+    /// Lowercase like the names of attributes, apart from the SVG and MathML names whose case HTML parsers
+    /// restore, like `linearGradient` and `viewBox`.
+    pub name: String,
+    pub attrs: Vec<Attribute>,
+    pub children: Vec<TreeNode>,
+    offset: usize,
+}
+
+/// Elements are equal if their names, attributes and children are, no matter where they were parsed from.
+impl PartialEq for Element {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.attrs == other.attrs && self.children == other.children
+    }
+}
+
+impl Tree {
+    /// Parses already rendered HTML, unmatched end tags are ignored and unclosed elements end with their parent.
+    pub fn parse(html: &str) -> Self {
+        Tree { nodes: parse(html) }
+    }
+
+    /// Calls `fun` with every element, parents before their children.
+    pub fn walk(&self, mut fun: impl FnMut(&Element)) {
+        fn walk(nodes: &[TreeNode], fun: &mut impl FnMut(&Element)) {
+            for node in nodes {
+                if let TreeNode::Element(element) = node {
+                    fun(element);
+                    walk(&element.children, fun);
+                }
+            }
+        }
+
+        walk(&self.nodes, &mut fun);
+    }
+
+    /// Like [`walk`](Tree::walk) but allows changing the elements.
+    pub fn walk_mut(&mut self, mut fun: impl FnMut(&mut Element)) {
+        fn walk(nodes: &mut [TreeNode], fun: &mut impl FnMut(&mut Element)) {
+            for node in nodes {
+                if let TreeNode::Element(element) = node {
+                    fun(element);
+                    walk(&mut element.children, fun);
+                }
+            }
+        }
+
+        walk(&mut self.nodes, &mut fun);
+    }
+
+    /// Serializes the tree with every element on its own line, indented by its depth. Elements only containing
+    /// text stay on one line, the content of `<pre>`, `<textarea>`, `<script>` and `<style>` is kept as is.
+    pub fn pretty(&self) -> String {
+        let mut out = Vec::new();
+        for node in &self.nodes {
+            write_pretty(&mut out, node, 0);
+        }
+        String::from_utf8(out).expect("serialized from strings")
+    }
+
+    /// Line based diff of both trees if they differ in more than formatting, `-` marking lines only in `self` and
+    /// `+` lines only in `other`. Attribute order is ignored.
+    pub fn diff(&self, other: &Tree) -> Option<String> {
+        let a = testing::normalize_nodes(&self.nodes);
+        let b = testing::normalize_nodes(&other.nodes);
+        (a != b).then(|| testing::diff(&a, &b))
+    }
+}
+
+impl Renderable for Tree {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        for node in &self.nodes {
+            write_node(buf, node);
+        }
+    }
+
+    fn render(self) -> String {
+        let mut buf = Vec::new();
+        self.render_into(&mut buf);
+        core::finish(buf)
+    }
+
+    fn is_trusted(&self, _: core::sealed::Trusted) -> bool {
+        true
+    }
+}
+
+/// Writes `html` again with the attributes of every element sorted by name, see
+/// [`RenderContext::sorted_attributes`](crate::context::RenderContext::sorted_attributes).
+pub(crate) fn sort_attributes(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut written = 0;
+
+    for (_, token) in tokenizer::tokenize(html) {
+        let Token::StartTag { attrs, spans, .. } = token else {
+            continue;
+        };
+        if attrs.is_sorted_by(|a, b| a.name <= b.name) {
+            continue;
+        }
+
+        // the attributes are moved as they were written, so references and the case of names are kept
+        let mut order: Vec<usize> = (0..attrs.len()).collect();
+        // stable, so repeated attributes keep their order
+        order.sort_by(|&a, &b| attrs[a].name.cmp(&attrs[b].name));
+
+        out.push_str(&html[written..spans[0].start]);
+        for (n, &i) in order.iter().enumerate() {
+            if n > 0 {
+                out.push(' ');
+            }
+            out.push_str(&html[spans[i].clone()]);
+        }
+        written = spans[spans.len() - 1].end;
+    }
+
+    out.push_str(&html[written..]);
+    out
+}
+
+/// Renders into a [`Tree`] instead of a string.
+///
+/// The tree is parsed from the output of the usual write-through render, so this costs a parse on top and is
+/// meant for post-processing, not for the hot path.
+pub trait RenderTree: Renderable + Sized {
+    fn render_tree(self) -> Tree {
+        let mut buf = Vec::new();
+        core::render_child(self, &mut buf);
+        Tree::parse(std::str::from_utf8(&buf).expect("render_child writes valid UTF-8"))
+    }
+}
+
+impl<R> RenderTree for R where R: Renderable {}
+
+impl Element {
+    /// An element without attributes and children.
+    pub fn new(name: impl AsRef<str>) -> Self {
+        Element {
+            name: tokenizer::element_name(&clean_name(name.as_ref())),
+            attrs: Vec::new(),
+            children: Vec::new(),
+            offset: 0,
+        }
+    }
+
+    /// Byte offset of the start tag in the HTML the element was parsed from, 0 for elements created with
+    /// [`new`](Element::new).
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Value of the attribute `name`, `Some("")` for flags.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|attr| attr.name == name)
+            .map(|attr| attr.value.as_deref().unwrap_or(""))
+    }
+
+    /// Sets the attribute `name`, replacing its value if it's already there.
+    ///
+    /// The name gets the case the parser would give it and characters that would end it early, like spaces,
+    /// quotes, `=` and `>`, are dropped. Nothing is set if no character is left.
+    pub fn set_attr(&mut self, name: impl AsRef<str>, value: impl AsRef<str>) {
+        let name = clean_name(name.as_ref());
+        if name.is_empty() {
+            return;
+        }
+        let name = tokenizer::attribute_name(&name);
+        let value = Some(value.as_ref().to_string());
+
+        match self.attrs.iter_mut().find(|attr| attr.name == name) {
+            Some(attr) => attr.value = value,
+            None => self.attrs.push(Attribute {
+                name: name.to_string(),
+                value,
+            }),
+        }
+    }
+
+    pub fn remove_attr(&mut self, name: &str) {
+        self.attrs.retain(|attr| attr.name != name);
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .is_some_and(|classes| classes.split_ascii_whitespace().any(|c| c == class))
+    }
+
+    /// Adds `class` to the `class` attribute, unless it's already there.
+    pub fn add_class(&mut self, class: &str) {
+        if self.has_class(class) {
+            return;
+        }
+
+        let classes = match self.attr("class") {
+            Some(classes) if !classes.trim().is_empty() => format!("{} {}", classes.trim(), class),
+            _ => class.to_string(),
+        };
+        self.set_attr("class", classes);
+    }
+
+    /// Concatenated text of all descendants.
+    pub fn text_content(&self) -> String {
+        fn collect(nodes: &[TreeNode], out: &mut String) {
+            for node in nodes {
+                match node {
+                    TreeNode::Text(text) => out.push_str(text),
+                    TreeNode::Element(element) => collect(&element.children, out),
+                    TreeNode::Doctype(_) | TreeNode::Comment(_) => {}
+                }
+            }
+        }
+
+        let mut out = String::new();
+        collect(&self.children, &mut out);
+        out
+    }
+
+    fn is_raw_text(&self) -> bool {
+        matches!(self.name.as_str(), "script" | "style")
+    }
+}
+
+// `name` and `attrs` are public, so their names can have anything in them. Characters that would end a name
+// early are dropped and names with nothing left are skipped, an element without a name only writes its children.
+fn clean_name(name: &str) -> Cow<'_, str> {
+    if name.bytes().all(core::is_name_byte) {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(
+            name.chars()
+                .filter(|c| !c.is_ascii() || core::is_name_byte(*c as u8))
+                .collect(),
+        )
+    }
+}
+
+fn write_start_tag(buf: &mut Vec<u8>, element: &Element) {
+    let name = clean_name(&element.name);
+    if name.is_empty() {
+        return;
+    }
+
+    buf.push(b'<');
+    buf.extend_from_slice(name.as_bytes());
+    for attr in &element.attrs {
+        let attr_name = clean_name(&attr.name);
+        if attr_name.is_empty() {
+            continue;
+        }
+        buf.push(b' ');
+        buf.extend_from_slice(attr_name.as_bytes());
+        if let Some(value) = &attr.value {
+            buf.extend_from_slice(b"=\"");
+            core::write_escaped(buf, value);
+            buf.push(b'"');
+        }
+    }
+    if tokenizer::is_void(&element.name) {
+        buf.extend_from_slice(core::void_end());
+    } else {
+        buf.push(b'>');
+    }
+}
+
+fn write_end_tag(buf: &mut Vec<u8>, element: &Element) {
+    let name = clean_name(&element.name);
+    if name.is_empty() {
+        return;
+    }
+
+    buf.extend_from_slice(b"</");
+    buf.extend_from_slice(name.as_bytes());
+    buf.push(b'>');
+}
+
+fn write_node(buf: &mut Vec<u8>, node: &TreeNode) {
+    match node {
+        TreeNode::Doctype(doctype) => {
+            buf.extend_from_slice(b"<!");
+            // a `>` would end the declaration early
+            buf.extend(doctype.bytes().filter(|&b| b != b'>'));
+            buf.push(b'>');
+        }
+        TreeNode::Comment(comment) => write_comment(buf, comment),
+        TreeNode::Text(text) => core::write_escaped(buf, text),
+        TreeNode::Element(element) => {
+            write_start_tag(buf, element);
+            if tokenizer::is_void(&element.name) {
+                return;
+            }
+            if element.is_raw_text() {
+                let mut content = Vec::new();
+                for child in &element.children {
+                    match child {
+                        TreeNode::Text(text) => content.extend_from_slice(text.as_bytes()),
+                        child => write_node(&mut content, child),
+                    }
+                }
+                write_raw_text(buf, &content, &element.name);
+            } else {
+                for child in &element.children {
+                    write_node(buf, child);
+                }
+            }
+            write_end_tag(buf, element);
+        }
+    }
+}
+
+// the comment can't end before its `-->`, e.g. through a `-->` or `--!>` in the text
+fn write_comment(buf: &mut Vec<u8>, comment: &str) {
+    let comment = comment.replace("-->", "--&gt;").replace("--!>", "--!&gt;");
+    let comment = match comment.strip_prefix('>') {
+        Some(rest) => format!("&gt;{}", rest),
+        None => match comment.strip_prefix("->") {
+            Some(rest) => format!("-&gt;{}", rest),
+            None => comment,
+        },
+    };
+
+    buf.extend_from_slice(b"<!--");
+    buf.extend_from_slice(comment.as_bytes());
+    buf.extend_from_slice(b"-->");
+}
+
+// the content of `<script>` and `<style>` is written as it is, apart from `</script` or `</style`, which would end
+// the element early, the `/` is escaped with a backslash like JSON and CSS allow
+fn write_raw_text(buf: &mut Vec<u8>, content: &[u8], name: &str) {
+    let mut written = 0;
+    for i in 0..content.len() {
+        let end_tag = content[i..].starts_with(b"</")
+            && content[i + 2..]
+                .get(..name.len())
+                .is_some_and(|tag| tag.eq_ignore_ascii_case(name.as_bytes()));
+        if end_tag {
+            buf.extend_from_slice(&content[written..i]);
+            buf.extend_from_slice(b"<\\/");
+            written = i + 2;
+        }
+    }
+    buf.extend_from_slice(&content[written..]);
+}
+
+fn write_pretty(buf: &mut Vec<u8>, node: &TreeNode, depth: usize) {
+    let indent = |buf: &mut Vec<u8>| buf.extend(std::iter::repeat_n(b' ', depth * 2));
+
+    match node {
+        TreeNode::Text(text) => {
+            let text = text.trim();
+            if !text.is_empty() {
+                indent(buf);
+                core::write_escaped(buf, text);
+                buf.push(b'\n');
+            }
+        }
+        TreeNode::Element(element)
+            if !tokenizer::is_void(&element.name)
+                && !matches!(
+                    element.name.as_str(),
+                    "pre" | "textarea" | "script" | "style"
+                )
+                && !element
+                    .children
+                    .iter()
+                    .all(|child| matches!(child, TreeNode::Text(_))) =>
+        {
+            indent(buf);
+            write_start_tag(buf, element);
+            buf.push(b'\n');
+            for child in &element.children {
+                write_pretty(buf, child, depth + 1);
+            }
+            indent(buf);
+            write_end_tag(buf, element);
+            buf.push(b'\n');
+        }
+        node => {
+            indent(buf);
+            write_node(buf, node);
+            buf.push(b'\n');
+        }
+    }
+}
+
+// see `Tree::parse`
+pub(crate) fn parse(src: &str) -> Vec<TreeNode> {
+    let mut root = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+
+    fn push(root: &mut Vec<TreeNode>, stack: &mut [Element], node: TreeNode) {
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => root.push(node),
+        }
+    }
+
+    for (offset, token) in tokenizer::tokenize(src) {
+        match token {
+            Token::Doctype(doctype) => push(
+                &mut root,
+                &mut stack,
+                TreeNode::Doctype(doctype.to_string()),
+            ),
+            Token::StartTag {
+                name,
+                attrs,
+                self_closing,
+                ..
+            } => {
+                let element = Element {
+                    name,
+                    attrs,
+                    children: Vec::new(),
+                    offset,
+                };
+
+                if self_closing || tokenizer::is_void(&element.name) {
+                    push(&mut root, &mut stack, TreeNode::Element(element));
+                } else {
+                    stack.push(element);
+                }
+            }
+            Token::EndTag { name } => {
+                let Some(i) = stack.iter().rposition(|open| open.name == name) else {
+                    continue;
+                };
+
+                while stack.len() > i {
+                    let element = stack.pop().expect("element on stack");
+                    push(&mut root, &mut stack, TreeNode::Element(element));
+                }
+            }
+            Token::Text(text) => push(&mut root, &mut stack, TreeNode::Text(text.into_owned())),
+            Token::Comment(comment) => push(
+                &mut root,
+                &mut stack,
+                TreeNode::Comment(comment.to_string()),
+            ),
+        }
+    }
+
+    while let Some(element) = stack.pop() {
+        push(&mut root, &mut stack, TreeNode::Element(element));
+    }
+
+    root
+}
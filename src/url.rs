@@ -0,0 +1,282 @@
+use std::fmt;
+
+/// Appends percent-encoded query parameters to `path`, keeping a `#fragment` at the end and extending a query
+/// the path already has.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = a()
+///     .href(href_query("/search", [("q", "rust & html"), ("page", "2")]))
+///     .text("Next")
+///     .render();
+///
+/// assert_eq!("<a href=\"/search?q=rust%20%26%20html&amp;page=2\">Next</a>", res);
+/// ```
+pub fn href_query<It, K, V>(path: impl AsRef<str>, params: It) -> String
+where
+    It: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: fmt::Display,
+{
+    params
+        .into_iter()
+        .fold(QueryString::new(), |query, (key, value)| {
+            query.param(key, value)
+        })
+        .to(path)
+}
+
+/// Query parameters of a URL, written percent-encoded as `key=value&key=value`.
+///
+/// Parameters keep the order they were added in, adding a key twice repeats it, which is how most frameworks read
+/// lists.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let query = QueryString::new()
+///     .param("tag", "a+b")
+///     .param("tag", "c")
+///     .param_opt("sort", None::<&str>);
+///
+/// assert_eq!("tag=a%2Bb&tag=c", query.to_string());
+/// assert_eq!("/posts?tag=a%2Bb&tag=c", query.to("/posts"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryString {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryString {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the parameters from a struct or map, with the field names serde would use. `None` fields are left
+    /// out and sequences repeat their key.
+    ///
+    /// ```
+    /// use lira::prelude::*;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Filter {
+    ///     q: &'static str,
+    ///     page: u32,
+    ///     sort: Option<&'static str>,
+    ///     tags: Vec<&'static str>,
+    /// }
+    ///
+    /// let filter = Filter { q: "lira", page: 3, sort: None, tags: vec!["html", "dsl"] };
+    ///
+    /// assert_eq!(
+    ///     "/search?q=lira&page=3&tags=html&tags=dsl",
+    ///     QueryString::serialize(&filter).unwrap().to("/search")
+    /// );
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn serialize<T>(params: &T) -> Result<Self, QueryError>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        let fields = crate::ser::fields(params).map_err(|err| QueryError {
+            message: err.to_string(),
+        })?;
+
+        let mut query = Self::new();
+        for (key, values) in fields {
+            for value in values {
+                query.pairs.push((key.clone(), value));
+            }
+        }
+        Ok(query)
+    }
+
+    pub fn param(mut self, key: impl AsRef<str>, value: impl fmt::Display) -> Self {
+        self.pairs
+            .push((key.as_ref().to_string(), value.to_string()));
+        self
+    }
+
+    /// Adds the parameter only if there is a value.
+    pub fn param_opt(self, key: impl AsRef<str>, value: Option<impl fmt::Display>) -> Self {
+        match value {
+            Some(value) => self.param(key, value),
+            None => self,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// `path` with these parameters appended, see [`href_query`].
+    pub fn to(&self, path: impl AsRef<str>) -> String {
+        let path = path.as_ref();
+        if self.is_empty() {
+            return path.to_string();
+        }
+
+        let (path, fragment) = match path.find('#') {
+            Some(i) => path.split_at(i),
+            None => (path, ""),
+        };
+        let separator = match path.find('?') {
+            Some(i) if i + 1 < path.len() && !path.ends_with('&') => "&",
+            Some(_) => "",
+            None => "?",
+        };
+
+        format!("{}{}{}{}", path, separator, self, fragment)
+    }
+}
+
+impl fmt::Display for QueryString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.pairs.iter().enumerate() {
+            if i > 0 {
+                f.write_str("&")?;
+            }
+            write_encoded(f, key, b"")?;
+            f.write_str("=")?;
+            write_encoded(f, value, b"")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error of [`QueryString::serialize`], for values that don't map to query parameters like nested structs.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    message: String,
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "can't serialize query string: {}", self.message)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for QueryError {}
+
+// everything but the unreserved characters of RFC 3986 and `keep`, spaces become %20 rather than `+` so the
+// result is valid in paths and mailto: URLs as well
+fn write_encoded(out: &mut (impl fmt::Write + ?Sized), value: &str, keep: &[u8]) -> fmt::Result {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    for &b in value.as_bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') || keep.contains(&b)
+        {
+            out.write_char(b as char)?;
+        } else {
+            out.write_char('%')?;
+            out.write_char(HEX[(b >> 4) as usize] as char)?;
+            out.write_char(HEX[(b & 0xf) as usize] as char)?;
+        }
+    }
+    Ok(())
+}
+
+/// A `mailto:` link built by [`mailto`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mailto {
+    url: String,
+    has_query: bool,
+}
+
+/// Builds a `mailto:` URL, percent-encoding the address and the prefilled fields so spaces, `+`, `&` and line breaks
+/// arrive in the mail client as written.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = a()
+///     .href(mailto("support+web@example.com").subject("Order #42").body("Hi,\nmy order 1 + 1 & more"))
+///     .text("Contact us")
+///     .render();
+///
+/// assert_eq!(
+///     "<a href=\"mailto:support%2Bweb@example.com?subject=Order%20%2342\
+///      &amp;body=Hi%2C%0D%0Amy%20order%201%20%2B%201%20%26%20more\">Contact us</a>",
+///     res
+/// );
+/// ```
+pub fn mailto(address: impl AsRef<str>) -> Mailto {
+    let mut url = String::from("mailto:");
+    // `@` separates the parts of an address and `,` several addresses
+    let _ = write_encoded(&mut url, address.as_ref(), b"@,");
+    Mailto {
+        url,
+        has_query: false,
+    }
+}
+
+impl Mailto {
+    pub fn cc(self, address: impl AsRef<str>) -> Self {
+        self.field("cc", address.as_ref(), b"@,")
+    }
+
+    pub fn bcc(self, address: impl AsRef<str>) -> Self {
+        self.field("bcc", address.as_ref(), b"@,")
+    }
+
+    pub fn subject(self, value: impl AsRef<str>) -> Self {
+        self.field("subject", value.as_ref(), b"")
+    }
+
+    /// Prefilled body of the mail, line breaks are sent as CRLF like RFC 6068 requires.
+    pub fn body(self, value: impl AsRef<str>) -> Self {
+        let value = value.as_ref().replace("\r\n", "\n").replace('\n', "\r\n");
+        self.field("body", &value, b"")
+    }
+
+    fn field(mut self, name: &str, value: &str, keep: &[u8]) -> Self {
+        self.url.push(if self.has_query { '&' } else { '?' });
+        self.has_query = true;
+        self.url.push_str(name);
+        self.url.push('=');
+        let _ = write_encoded(&mut self.url, value, keep);
+        self
+    }
+}
+
+impl AsRef<str> for Mailto {
+    fn as_ref(&self) -> &str {
+        &self.url
+    }
+}
+
+impl fmt::Display for Mailto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.url)
+    }
+}
+
+impl From<Mailto> for String {
+    fn from(value: Mailto) -> Self {
+        value.url
+    }
+}
+
+/// Builds a `tel:` URL from a phone number as people write it. Whitespace is dropped, the visual separators of
+/// RFC 3966 are kept and anything else, like the `#` of extensions, is percent-encoded.
+///
+/// ```
+/// use lira::prelude::*;
+///
+/// let res = a().href(tel("+49 (30) 123-456")).text("Call us").render();
+///
+/// assert_eq!("<a href=\"tel:+49(30)123-456\">Call us</a>", res);
+/// ```
+pub fn tel(number: impl AsRef<str>) -> String {
+    let number: String = number
+        .as_ref()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let mut url = String::from("tel:");
+    let _ = write_encoded(&mut url, &number, b"+()*");
+    url
+}
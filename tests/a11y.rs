@@ -0,0 +1,76 @@
+use lira::prelude::*;
+
+#[test]
+fn test_icon_button() {
+    let res = icon_button(icon("/icons.svg", "trash"), "Delete").render();
+
+    assert_eq!(
+        "<button type=\"button\"><span aria-hidden=\"true\"><svg aria-hidden=\"true\" focusable=\"false\">\
+         <use href=\"/icons.svg#trash\" /></svg></span><span class=\"visually-hidden\">Delete</span></button>",
+        res
+    );
+}
+
+#[test]
+fn test_icon_label() {
+    let res = button()
+        .type_("submit")
+        .class("btn")
+        .icon_label(span().text("→"), "Next page")
+        .render();
+
+    assert_eq!(
+        "<button type=\"submit\" class=\"btn\"><span aria-hidden=\"true\"><span>→</span></span>\
+         <span class=\"visually-hidden\">Next page</span></button>",
+        res
+    );
+}
+
+#[test]
+fn test_a11y_styles() {
+    assert_eq!(
+        ".visually-hidden, .visually-hidden-focusable:not(:focus):not(:focus-within){position:absolute;width:1px;height:1px;padding:0;margin:-1px;overflow:hidden;\
+         clip:rect(0, 0, 0, 0);white-space:nowrap;border:0}",
+        a11y_styles().to_string()
+    );
+}
+
+#[cfg(feature = "a11y-audit")]
+#[test]
+fn test_icon_button_passes_audit() {
+    let (_, report) = icon_button(icon("", "x"), "Close").render_with_report();
+
+    assert!(report.is_empty(), "{}", report);
+}
+
+#[test]
+fn test_skip_link() {
+    assert_eq!(
+        "<a href=\"#main\" class=\"visually-hidden-focusable\">Skip to main content</a>",
+        skip_link("main").render()
+    );
+    assert_eq!(
+        "<a href=\"#main\" class=\"visually-hidden-focusable\">Zum Inhalt springen</a>",
+        skip_link_with("main", "Zum Inhalt springen").render()
+    );
+    assert_eq!(
+        "<main id=\"main\" tabindex=\"-1\" class=\"page\"></main>",
+        main_content("main").class("page").render()
+    );
+}
+
+#[test]
+fn test_live_regions() {
+    assert_eq!(
+        "<div aria-live=\"assertive\" aria-atomic=\"true\"></div>",
+        live_region(Politeness::Assertive).render()
+    );
+    assert_eq!(
+        "<div role=\"status\" aria-live=\"polite\" aria-atomic=\"true\">Saved</div>",
+        status().text("Saved").render()
+    );
+    assert_eq!(
+        "<div role=\"alert\" aria-live=\"assertive\" aria-atomic=\"true\" class=\"error\">Connection lost</div>",
+        alert().class("error").text("Connection lost").render()
+    );
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "askama")]
+
+use askama::Template;
+use lira::core::{Content, Node, Void};
+use lira::html5::{Hr, Nav};
+use lira::prelude::*;
+
+#[derive(Template)]
+#[template(
+    source = "<main>{{ nav }}<h1>{{ title }}</h1>{{ footer }}{{ rule }}</main>",
+    ext = "html"
+)]
+struct Page {
+    nav: Node<Nav, Content>,
+    title: &'static str,
+    footer: Fragment,
+    rule: Node<Hr, Void>,
+}
+
+#[test]
+fn test_nodes_are_not_escaped_again() {
+    let page = Page {
+        nav: nav().child(a().href("/?a=1&b=2").text("Home")),
+        title: "Tom & Jerry",
+        footer: fragment().text("a < b"),
+        rule: hr(),
+    };
+    assert_eq!(
+        "<main><nav><a href=\"/?a=1&amp;b=2\">Home</a></nav><h1>Tom &#38; Jerry</h1>a &lt; b<hr /></main>",
+        page.render().unwrap()
+    );
+}
+
+#[test]
+fn test_display() {
+    assert_eq!("<p>a &amp; b</p>", p().text("a & b").to_string());
+    assert_eq!("<hr />", hr().to_string());
+}
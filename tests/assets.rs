@@ -0,0 +1,67 @@
+use lira::prelude::*;
+
+const VITE_MANIFEST: &str = r#"{
+  "app.js": {
+    "file": "assets/app-4889e940.js",
+    "src": "app.js",
+    "isEntry": true,
+    "css": ["assets/app-5fa1d3c2.css"],
+    "integrity": "sha384-abc"
+  },
+  "app.css": { "file": "assets/app-5fa1d3c2.css", "src": "app.css" }
+}"#;
+
+#[test]
+fn test_vite_manifest() {
+    let assets = Assets::from_manifest(VITE_MANIFEST)
+        .unwrap()
+        .base("/static/");
+
+    let res = RenderContext::new().assets(assets).scope(|| {
+        head()
+            .child(asset_css("app.css"))
+            .child(asset_script("app.js"))
+            .render()
+    });
+
+    assert_eq!(
+        "<head><link rel=\"stylesheet\" href=\"/static/assets/app-5fa1d3c2.css\" /><script src=\"/static/assets/app-4889e940.js\" integrity=\"sha384-abc\" crossorigin=\"anonymous\"></script></head>",
+        res
+    );
+}
+
+#[test]
+fn test_webpack_manifest() {
+    let assets = Assets::from_manifest(
+        r#"{"app.js": "/dist/app.1a2b3c.js", "vendor.js": "https://cdn.example.com/v.js"}"#,
+    )
+    .unwrap();
+
+    assert_eq!("/dist/app.1a2b3c.js", assets.path("app.js"));
+    assert_eq!("https://cdn.example.com/v.js", assets.path("vendor.js"));
+    assert_eq!(None, assets.integrity("app.js"));
+}
+
+#[test]
+fn test_unknown_assets_fall_back_to_name() {
+    let res = asset_script("/app.js").render();
+    assert_eq!("<script src=\"/app.js\"></script>", res);
+
+    let res = RenderContext::new()
+        .assets(Assets::new())
+        .scope(|| asset_css("/app.css").render());
+    assert_eq!("<link rel=\"stylesheet\" href=\"/app.css\" />", res);
+}
+
+#[test]
+fn test_invalid_manifest() {
+    assert!(Assets::from_manifest("[]").is_err());
+    assert!(Assets::from_manifest("{\"app.js\": 1}").is_err());
+    assert!(Assets::from_manifest("{\"app.js\": {\"src\": \"app.js\"}}").is_err());
+
+    let err = Assets::from_manifest("{\"app.js\": ").unwrap_err();
+    assert_eq!(
+        "invalid asset manifest: unexpected end of input at offset 11",
+        err.to_string()
+    );
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "a11y-audit")]
+
+use lira::audit::{Issue, audit, audit_headings};
+use lira::prelude::*;
+
+fn issues(html: &str) -> Vec<Issue> {
+    audit(html)
+        .into_iter()
+        .map(|warning| warning.issue)
+        .collect()
+}
+
+#[test]
+fn test_render_with_report() {
+    let (html, report) = div()
+        .child(img().src("/a.png"))
+        .child(img().src("/b.png").alt(""))
+        .render_with_report();
+
+    assert_eq!(
+        "<div><img src=\"/a.png\" /><img src=\"/b.png\" alt=\"\" /></div>",
+        html
+    );
+    assert_eq!(1, report.len());
+    assert_eq!(Issue::MissingAlt, report.warnings()[0].issue);
+    assert_eq!(5, report.warnings()[0].offset);
+    assert_eq!(
+        "<img> at 1:6 (byte 5): image without alt attribute\n",
+        report.to_string()
+    );
+}
+
+#[test]
+fn test_clean_page_has_no_warnings() {
+    let (_, report) = form()
+        .child(label().attr("for", "email").text("Email"))
+        .child(input().id("email"))
+        .child(label().text("Name").child(input()))
+        .child(input().attr("aria-label", "Search"))
+        .child(input().attr("type", "hidden"))
+        .child(button().text("Send"))
+        .render_with_report();
+
+    assert!(report.is_empty(), "{}", report);
+}
+
+#[test]
+fn test_unlabeled_controls() {
+    assert_eq!(
+        vec![Issue::UnlabeledControl, Issue::UnlabeledControl],
+        issues("<input id=\"a\"><label for=\"b\">B</label><select id=\"c\"></select>")
+    );
+}
+
+#[test]
+fn test_unnamed_buttons() {
+    assert_eq!(
+        vec![Issue::UnnamedButton],
+        issues(
+            "<button> </button><button aria-label=\"Close\"></button><button><img alt=\"Menu\"></button>"
+        )
+    );
+}
+
+#[test]
+fn test_heading_level_jumps() {
+    assert_eq!(
+        vec![Issue::HeadingLevelJump { from: 2, to: 4 }],
+        issues("<h1>A</h1><h2>B</h2><h4>C</h4><h2>D</h2><h3>E</h3>")
+    );
+}
+
+#[test]
+fn test_multiple_h1() {
+    assert_eq!(
+        vec![Issue::MultipleH1, Issue::MultipleH1],
+        issues("<h1>A</h1><h2>B</h2><h1>C</h1><section><h1>D</h1></section>")
+    );
+}
+
+#[test]
+fn test_audit_headings_locations() {
+    let html = "<main>\n  <img src=\"a.png\">\n  <h1>Ä</h1><h4>B</h4>\n</main>";
+    let report = audit_headings(html);
+
+    assert_eq!(1, report.len());
+    let warning = &report.warnings()[0];
+    assert_eq!(Issue::HeadingLevelJump { from: 1, to: 4 }, warning.issue);
+    assert_eq!((3, 13), (warning.line, warning.column));
+    assert!(html[warning.offset..].starts_with("<h4>"));
+}
+
+#[test]
+fn test_positive_tabindex() {
+    assert_eq!(
+        vec![Issue::PositiveTabindex(3)],
+        issues("<a tabindex=\"0\">a</a><a tabindex=\"-1\">b</a><a tabindex=\"3\">c</a>")
+    );
+}
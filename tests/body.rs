@@ -0,0 +1,55 @@
+#![cfg(feature = "http-body")]
+
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use bytes::Bytes;
+use http_body::Body;
+use http_body_util::Full;
+use lira::prelude::*;
+
+// bodies of lira are always ready, so this doesn't need an executor
+fn frames(mut body: impl Body<Data = Bytes, Error = Infallible> + Unpin) -> Vec<Bytes> {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut frames = Vec::new();
+    while let Poll::Ready(Some(frame)) = Pin::new(&mut body).poll_frame(&mut cx) {
+        frames.push(frame.unwrap().into_data().unwrap());
+    }
+    frames
+}
+
+#[test]
+fn test_node_into_full() {
+    let body: Full<Bytes> = p().text("Hi").into();
+    assert_eq!(vec![Bytes::from("<p>Hi</p>")], frames(body));
+
+    let body: Full<Bytes> = hr().into();
+    assert_eq!(vec![Bytes::from("<hr />")], frames(body));
+
+    let body: Full<Bytes> = fragment().text("a").into();
+    assert_eq!(vec![Bytes::from("a")], frames(body));
+}
+
+#[test]
+fn test_node_into_bytes() {
+    let bytes: Bytes = div().into();
+    assert_eq!(Bytes::from("<div></div>"), bytes);
+}
+
+#[test]
+fn test_render_body_applies_context() {
+    let body = RenderContext::new()
+        .minify(true)
+        .scope(|| div().raw("\n  <p>x</p>\n").render_body());
+    assert_eq!(vec![Bytes::from("<div><p>x</p></div>")], frames(body));
+}
+
+#[test]
+fn test_chunked_body() {
+    let body = chunked_body(["a", "b"].into_iter().map(|item| li().text(item)));
+    assert_eq!(
+        vec![Bytes::from("<li>a</li>"), Bytes::from("<li>b</li>")],
+        frames(body)
+    );
+}
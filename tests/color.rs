@@ -0,0 +1,37 @@
+use lira::prelude::*;
+
+#[test]
+fn test_parse() {
+    assert_eq!(Ok(Color::rgb(0x12, 0xab, 0xef)), "#12ABef".parse());
+    assert_eq!(Ok(Color::rgb(0x11, 0x22, 0x33)), "123".parse());
+    assert_eq!(Ok(Color::WHITE), "#fff".parse());
+
+    for invalid in [
+        "", "#", "#12", "#12345", "#1234567", "#ggg", "red", "#+12345",
+    ] {
+        assert!(invalid.parse::<Color>().is_err(), "{}", invalid);
+    }
+    assert_eq!(
+        "invalid color: \"red\" isn't #rgb or #rrggbb",
+        "red".parse::<Color>().unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_format() {
+    assert_eq!("#000000", Color::BLACK.to_string());
+    assert_eq!("#0a0b0c", Color::rgb(10, 11, 12).to_string());
+    assert_eq!(0x0a0b0c, Color::from_hex(0xff0a0b0c).to_hex());
+}
+
+#[test]
+fn test_color_input() {
+    assert_eq!(
+        "<input type=\"color\" value=\"#ff8000\" id=\"bg\" />",
+        color_input((255, 128, 0)).id("bg").render()
+    );
+    assert_eq!(
+        "<input type=\"color\" value=\"#000000\" />",
+        color_input(Color::default()).render()
+    );
+}
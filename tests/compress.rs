@@ -0,0 +1,70 @@
+#![cfg(feature = "gzip")]
+
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+use lira::compress::gzip;
+use lira::prelude::*;
+
+// flate2 checks the CRC and the size at the end of the stream
+fn gunzip(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    GzDecoder::new(data)
+        .read_to_end(&mut out)
+        .expect("valid gzip stream");
+    out
+}
+
+#[test]
+fn test_render_gzip() {
+    let page = || {
+        html().child(body().child(ul().children(0..500, |i| {
+            li().class("item").text(format!("Item number {}", i))
+        })))
+    };
+
+    let compressed = page().render_gzip();
+    let html = page().render();
+
+    assert!(compressed.len() * 4 < html.len());
+    assert_eq!(html.as_bytes(), gunzip(&compressed));
+}
+
+#[test]
+fn test_render_gzip_applies_context() {
+    let page = || div().attr("b", "1").attr("a", "2").raw("  <p>x</p>  ");
+
+    let ctx = RenderContext::new().minify(true).sorted_attributes(true);
+    let html = ctx.clone().scope(|| page().render());
+    let compressed = ctx.scope(|| page().render_gzip());
+
+    assert_eq!("<div a=2 b=1><p>x</p></div>", html);
+    assert_eq!(html.as_bytes(), gunzip(&compressed));
+}
+
+#[test]
+fn test_gzip_roundtrip() {
+    let long_run = "a".repeat(1000);
+    let mixed: String = (0..5000u32)
+        .map(|i| char::from_u32(0x20 + (i * 7919) % 0x5f).unwrap())
+        .collect();
+    let far_repeat = format!("{}{}{}", "unique start ", mixed, "unique start ");
+
+    for input in ["", "x", "ab", "äöü €", &long_run, &mixed, &far_repeat] {
+        assert_eq!(
+            input.as_bytes(),
+            gunzip(&gzip(input.as_bytes())),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_gzip_crc() {
+    let compressed = gzip(b"123456789");
+    let crc = u32::from_le_bytes(compressed[compressed.len() - 8..][..4].try_into().unwrap());
+
+    // CRC-32 check value
+    assert_eq!(0xcbf43926, crc);
+}
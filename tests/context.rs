@@ -0,0 +1,297 @@
+use lira::prelude::*;
+
+#[test]
+fn test_nonce_is_stamped_on_scripts_and_styles() {
+    let res = RenderContext::new().nonce("abc123").scope(|| {
+        head()
+            .child(style().raw("body { margin: 0; }"))
+            .child(script().src("/app.js"))
+            .child(link().href("/app.css"))
+            .render()
+    });
+
+    assert_eq!(
+        "<head><style nonce=\"abc123\">body { margin: 0; }</style><script nonce=\"abc123\" src=\"/app.js\"></script><link href=\"/app.css\" /></head>",
+        res
+    );
+}
+
+#[test]
+fn test_nonce_provider() {
+    let provider = || String::from("from-provider");
+    let res = RenderContext::new()
+        .nonce_from(&provider)
+        .scope(|| script().render());
+
+    assert_eq!("<script nonce=\"from-provider\"></script>", res);
+}
+
+#[test]
+fn test_no_nonce_outside_of_scope() {
+    let inside = RenderContext::new()
+        .nonce("n")
+        .scope(|| RenderContext::new().scope(|| script().render()));
+    assert_eq!("<script></script>", inside);
+
+    RenderContext::new().nonce("n").scope(|| ());
+    assert_eq!("<script></script>", script().render());
+}
+
+#[test]
+fn test_base_path_prefixes_root_relative_urls() {
+    let res = RenderContext::new().base_path("/myapp/").scope(|| {
+        div()
+            .child(a().href("/about").text("About"))
+            .child(a().href("https://example.com/").text("Out"))
+            .child(a().href("#top").text("Top"))
+            .child(img().src("//cdn.example.com/a.png"))
+            .child(img().srcset("/a.png 1x, /a@2x.png 2x"))
+            .child(form().action("/login").close())
+            .render()
+    });
+
+    assert_eq!(
+        "<div><a href=\"/myapp/about\">About</a><a href=\"https://example.com/\">Out</a><a href=\"#top\">Top</a><img src=\"//cdn.example.com/a.png\" /><img srcset=\"/myapp/a.png 1x, /myapp/a@2x.png 2x\" /><form action=\"/myapp/login\"></form></div>",
+        res
+    );
+}
+
+#[test]
+fn test_base_path_attr_fmt() {
+    let res = RenderContext::new()
+        .base_path("/myapp")
+        .scope(|| a().attr_fmt("href", format_args!("/users/{}", 42)).render());
+
+    assert_eq!("<a href=\"/myapp/users/42\"></a>", res);
+}
+
+#[test]
+fn test_base_path_empty_is_ignored() {
+    let res = RenderContext::new()
+        .base_path("/")
+        .scope(|| a().href("/about").render());
+
+    assert_eq!("<a href=\"/about\"></a>", res);
+}
+
+#[test]
+fn test_void_style_html5() {
+    let page = || div().child(img().src("/a.png")).child(hr()).render();
+
+    assert_eq!("<div><img src=\"/a.png\" /><hr /></div>", page());
+
+    let res = RenderContext::new()
+        .void_style(VoidStyle::Html5)
+        .scope(page);
+    assert_eq!("<div><img src=\"/a.png\"><hr></div>", res);
+}
+
+#[test]
+fn test_numeric_entities() {
+    let page = || p().title("\"Tom\" & 'Jerry'").text("a < b > c €").render();
+
+    let res = RenderContext::new()
+        .entity_style(EntityStyle::Numeric)
+        .scope(page);
+    assert_eq!(
+        "<p title=\"&#34;Tom&#34; &#38; &#39;Jerry&#39;\">a &#60; b &#62; c €</p>",
+        res
+    );
+
+    let res = RenderContext::new()
+        .entity_style(EntityStyle::Numeric)
+        .escape_non_ascii(true)
+        .scope(page);
+    assert_eq!(
+        "<p title=\"&#34;Tom&#34; &#38; &#39;Jerry&#39;\">a &#60; b &#62; c &#8364;</p>",
+        res
+    );
+}
+
+#[test]
+fn test_sorted_attributes() {
+    let a = || {
+        div()
+            .id("main")
+            .class("box")
+            .data("x", "1")
+            .child(input().value("x").name("q"))
+            .render()
+    };
+    let b = || {
+        div()
+            .data("x", "1")
+            .class("box")
+            .id("main")
+            .child(input().name("q").value("x"))
+            .render()
+    };
+
+    let sorted = RenderContext::new().sorted_attributes(true);
+    let res = sorted.clone().scope(a);
+    assert_eq!(
+        "<div class=\"box\" data-x=\"1\" id=\"main\"><input name=\"q\" value=\"x\" /></div>",
+        res
+    );
+    assert_eq!(res, sorted.scope(b));
+    assert_ne!(a(), b());
+}
+
+#[test]
+fn test_sorted_attributes_keep_markup() {
+    let res = RenderContext::new().sorted_attributes(true).scope(|| {
+        div()
+            .raw("&hearts; &amp; &copy 2026 ")
+            .raw("<svg viewBox=\"0 0 2 2\" class=\"icon\"><linearGradient id=\"g\" gradientUnits=\"userSpaceOnUse\" />\
+                  </svg><a title='a &amp; b' href=/x>x</a>")
+            .render()
+    });
+
+    assert_eq!(
+        "<div>&hearts; &amp; &copy 2026 <svg class=\"icon\" viewBox=\"0 0 2 2\"><linearGradient gradientUnits=\"userSpaceOnUse\" id=\"g\" />\
+         </svg><a href=/x title='a &amp; b'>x</a></div>",
+        res
+    );
+}
+
+#[test]
+fn test_buffer_capacities() {
+    let capacities = BufferCapacities {
+        node: 4096,
+        small: 1024,
+        page: 1 << 16,
+    };
+    let (page, node, small) = RenderContext::new()
+        .buffer_capacities(capacities)
+        .scope(|| {
+            (
+                html().close().into_bytes(),
+                div().close().into_bytes(),
+                td().close().into_bytes(),
+            )
+        });
+
+    assert!(page.capacity() >= 1 << 16);
+    assert!(node.capacity() >= 4096);
+    assert!(small.capacity() >= 1024);
+    assert_eq!(b"<td></td>", small.as_slice());
+}
+
+#[test]
+fn test_buffer_capacities_default() {
+    assert_eq!(BufferCapacities::DEFAULT, BufferCapacities::default());
+    assert!(div().close().into_bytes().capacity() < 4096);
+}
+
+fn strict<R>(fun: impl FnOnce() -> R) -> R {
+    RenderContext::new().mode(RenderMode::Strict).scope(fun)
+}
+
+#[test]
+fn test_strict_blocks_script_urls() {
+    let res = strict(|| {
+        div()
+            .child(a().href("JavaScript:alert(1)").text("a"))
+            .child(a().href(" \tjava\nscript:alert(1)").text("b"))
+            .child(a().href("vbscript:msgbox").text("c"))
+            .child(img().src("data:image/svg+xml,<svg onload=alert(1)>"))
+            .child(iframe().src("data:text/html,<script>alert(1)</script>"))
+            .child(form().action("javascript:void(0)"))
+            .render()
+    });
+
+    assert_eq!(
+        "<div><a href=\"about:invalid\">a</a><a href=\"about:invalid\">b</a><a href=\"about:invalid\">c</a>\
+         <img src=\"about:invalid\" /><iframe src=\"about:invalid\"></iframe>\
+         <form action=\"about:invalid\"></form></div>",
+        res
+    );
+}
+
+#[test]
+fn test_strict_keeps_safe_urls() {
+    let urls = [
+        "/posts?page=2",
+        "https://example.com/a:b",
+        "mailto:hello@example.com",
+        "#top",
+        "./javascript:notes",
+        "data:image/png;base64,iVBORw0KGgo=",
+    ];
+    for url in urls {
+        let res = strict(|| a().href(url).text("x").render());
+        assert_eq!(
+            format!("<a href=\"{}\">x</a>", url.replace('&', "&amp;")),
+            res
+        );
+    }
+
+    let res = strict(|| {
+        a().attr_fmt("href", format_args!("javascript:{}", "x"))
+            .attr("title", "javascript:x")
+            .render()
+    });
+    assert_eq!("<a href=\"about:invalid\" title=\"javascript:x\"></a>", res);
+}
+
+#[test]
+fn test_strict_validates_attribute_names() {
+    let res = RenderContext::new()
+        .mode(RenderMode::Strict)
+        .validate_attribute_names(false)
+        .scope(|| div().flag("onload=alert(1)").render());
+    assert_eq!("<div onloadalert(1)></div>", res);
+}
+
+#[test]
+fn test_lenient_writes_everything() {
+    let res = RenderContext::new().mode(RenderMode::Lenient).scope(|| {
+        p().flag("onload=alert(1)")
+            .child(a().href("javascript:go()").child(div()))
+            .render()
+    });
+    assert_eq!(
+        "<p onload=alert(1)><a href=\"javascript:go()\"><div></div></a></p>",
+        res
+    );
+}
+
+#[test]
+fn test_strict_allows_valid_nesting() {
+    let res = strict(|| {
+        div()
+            .child(p().child(span().child(a().href("/").text("home"))))
+            .child(form().child(button().child(span().text("Go"))))
+            .render()
+    });
+    assert_eq!(
+        "<div><p><span><a href=\"/\">home</a></span></p><form><button><span>Go</span></button></form></div>",
+        res
+    );
+}
+
+#[test]
+#[should_panic(expected = "<p> can't contain <div>")]
+fn test_strict_panics_for_block_in_paragraph() {
+    strict(|| p().child(span().child(div())));
+}
+
+#[test]
+#[should_panic(expected = "<a> can't contain <a>")]
+fn test_strict_panics_for_nested_links() {
+    strict(|| a().href("/").child(fragment().child(a().href("/other"))));
+}
+
+#[test]
+#[should_panic(expected = "<button> can't contain <input>")]
+fn test_strict_panics_for_control_in_button() {
+    strict(|| button().children([1], |_| input()));
+}
+
+#[test]
+#[should_panic(expected = "<button> can't contain <select>")]
+fn test_strict_panics_for_deeply_nested_control() {
+    strict(|| {
+        button().child(span().child(span().child(fragment().raw("<b><select></select></b>"))))
+    });
+}
@@ -0,0 +1,80 @@
+use lira::prelude::*;
+
+const CSS: &str = "
+/* layout */
+@charset \"utf-8\";
+@font-face { font-family: Inter; src: url(/inter.woff2); }
+:root { --gap: 1rem; }
+body, .unused { margin: 0; }
+.nav a:hover, .sidebar { color: red; }
+#main > .card:not(.hidden) { padding: var(--gap); }
+table td { border: 0; }
+a[href$=\".pdf\"] { color: blue; }
+.md\\:grid { display: grid; }
+@media (min-width: 40rem) { .sidebar { width: 10rem; } .card { padding: 2rem; } }
+@keyframes spin { from { rotate: 0deg; } to { rotate: 360deg; } }
+";
+
+#[test]
+fn test_used_rules() {
+    let html = div()
+        .id("main")
+        .child(nav().class("nav").child(a().href("/").text("Home")))
+        .child(div().class("card md:grid"))
+        .render();
+
+    assert_eq!(
+        "@charset \"utf-8\";@font-face{font-family: Inter; src: url(/inter.woff2);}\
+         :root{--gap: 1rem;}body{margin: 0;}.nav a:hover{color: red;}\
+         #main > .card:not(.hidden){padding: var(--gap);}a[href$=\".pdf\"]{color: blue;}\
+         .md\\:grid{display: grid;}@media (min-width: 40rem){.card{padding: 2rem;}}\
+         @keyframes spin{from { rotate: 0deg; } to { rotate: 360deg; }}",
+        UsedRules.extract(CSS, &html)
+    );
+}
+
+#[test]
+fn test_document_critical_css() {
+    let res = document()
+        .stylesheet("/app.css", ".a{color:red}.b{color:blue}")
+        .stylesheet("/print.css", ".c{display:none}")
+        .critical_css(UsedRules)
+        .render(|| body().child(p().class("a")));
+
+    assert_eq!(
+        "<!DOCTYPE html><html><head><style>.a{color:red}</style>\
+         <link rel=\"stylesheet\" href=\"/app.css\" media=\"print\" onload=\"this.media=&#39;all&#39;\" />\
+         <link rel=\"stylesheet\" href=\"/print.css\" media=\"print\" onload=\"this.media=&#39;all&#39;\" />\
+         <noscript><link rel=\"stylesheet\" href=\"/app.css\" /><link rel=\"stylesheet\" href=\"/print.css\" /></noscript>\
+         </head><body><p class=\"a\"></p></body></html>",
+        res
+    );
+}
+
+#[test]
+fn test_document_critical_css_closure() {
+    let res = RenderContext::new().nonce("n0nce").scope(|| {
+        document()
+            .stylesheet("/app.css", "ignored")
+            .critical_css(|_: &str, html: &str| format!("/* {} bytes */", html.len()))
+            .render(|| body().close())
+    });
+
+    assert!(
+        res.contains("<style nonce=\"n0nce\">/* 13 bytes */</style>"),
+        "{}",
+        res
+    );
+}
+
+#[test]
+fn test_document_stylesheet_without_extractor() {
+    let res = document()
+        .stylesheet("/app.css", ".a{}")
+        .render(|| body().close());
+
+    assert_eq!(
+        "<!DOCTYPE html><html><head><link rel=\"stylesheet\" href=\"/app.css\" /></head><body></body></html>",
+        res
+    );
+}
@@ -0,0 +1,94 @@
+use lira::prelude::*;
+
+#[test]
+fn test_inline_style() {
+    let res = div()
+        .style(
+            css()
+                .display("flex")
+                .gap("1rem")
+                .color("#333")
+                .prop("Grid_Area", "main"),
+        )
+        .render();
+
+    assert_eq!(
+        "<div style=\"display:flex;gap:1rem;color:#333;grid-area:main\"></div>",
+        res
+    );
+}
+
+#[test]
+fn test_inline_style_escaping() {
+    let res = span()
+        .style(
+            css()
+                .color("red;background:url(\"x\")")
+                .prop("width}", "1px"),
+        )
+        .render();
+
+    assert_eq!(
+        r#"<span style="color:red\;background:url(&quot;x&quot;);width:1px"></span>"#,
+        res
+    );
+}
+
+#[test]
+fn test_inline_style_from_pairs() {
+    let style: InlineStyle = [("margin", "0 auto"), ("max_width", "40rem")]
+        .into_iter()
+        .collect();
+
+    assert_eq!("margin:0 auto;max-width:40rem", style.to_string());
+    assert!(css().is_empty());
+    assert_eq!(
+        "opacity:0.5",
+        css()
+            .prop_opt("color", None::<&str>)
+            .prop_opt("opacity", Some("0.5"))
+            .as_ref()
+    );
+}
+
+#[test]
+fn test_stylesheet() {
+    let sheet = stylesheet()
+        .rule(rule("body").margin("0").prop("font_family", "system-ui"))
+        .rules([
+            rule("h1, h2").font_weight("600"),
+            rule(".card > p").declarations(css().padding("1rem").color("#333")),
+        ])
+        .media(
+            media("screen")
+                .rule(rule("a").color("blue"))
+                .media(media("(prefers-color-scheme: dark)").rule(rule("a").color("skyblue"))),
+        );
+
+    assert_eq!(
+        "body{margin:0;font-family:system-ui}h1, h2{font-weight:600}.card > p{padding:1rem;color:#333}\
+         @media screen{a{color:blue}@media (prefers-color-scheme: dark){a{color:skyblue}}}",
+        sheet.to_string()
+    );
+}
+
+#[test]
+fn test_stylesheet_in_style_element() {
+    let res = RenderContext::new().nonce("abc").scope(|| {
+        style()
+            .child(stylesheet().rule(rule("p").color("red")))
+            .render()
+    });
+
+    assert_eq!("<style nonce=\"abc\">p{color:red}</style>", res);
+}
+
+#[test]
+fn test_stylesheet_escaping() {
+    let sheet = stylesheet().rule(rule("p{}").prop("content", "\"</style><script>\""));
+
+    assert_eq!(
+        r#"p\{\}{content:"\3c /style>\3c script>"}"#,
+        sheet.to_string()
+    );
+}
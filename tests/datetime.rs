@@ -0,0 +1,81 @@
+use lira::prelude::*;
+
+#[test]
+fn test_time_element_with_string() {
+    let res = time().datetime("2025-10-14").text("October 14th").render();
+    assert_eq!("<time datetime=\"2025-10-14\">October 14th</time>", res);
+}
+
+#[test]
+fn test_date_input_min_max() {
+    let res = input()
+        .input_type(InputType::Date)
+        .min("2025-01-01")
+        .max(String::from("2025-12-31"))
+        .render();
+    assert_eq!(
+        "<input type=\"date\" min=\"2025-01-01\" max=\"2025-12-31\" />",
+        res
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_values() {
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+    let date = NaiveDate::from_ymd_opt(2025, 3, 7).unwrap();
+    assert_eq!("2025-03-07", date.to_datetime_string());
+
+    let utc = Utc.with_ymd_and_hms(2025, 3, 7, 9, 5, 0).unwrap();
+    assert_eq!("2025-03-07T09:05:00Z", utc.to_datetime_string());
+
+    let offset = FixedOffset::east_opt(2 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2025, 3, 7, 9, 5, 0)
+        .unwrap()
+        + chrono::Duration::microseconds(123_456);
+    assert_eq!("2025-03-07T09:05:00.123+02:00", offset.to_datetime_string());
+
+    let res = time().datetime(utc).text("now").render();
+    assert_eq!("<time datetime=\"2025-03-07T09:05:00Z\">now</time>", res);
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_time_values() {
+    use time::macros::{date, datetime};
+
+    assert_eq!("2025-03-07", date!(2025 - 03 - 07).to_datetime_string());
+    assert_eq!(
+        "2025-03-07T09:05:00-05:30",
+        datetime!(2025-03-07 9:05 -5:30).to_datetime_string()
+    );
+
+    let res = input()
+        .input_type(InputType::DatetimeLocal)
+        .min(datetime!(2025-03-07 9:05:30.5))
+        .render();
+    assert_eq!(
+        "<input type=\"datetime-local\" min=\"2025-03-07T09:05:30.500\" />",
+        res
+    );
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn test_jiff_values() {
+    use jiff::civil::date;
+
+    assert_eq!("2025-03-07", date(2025, 3, 7).to_datetime_string());
+
+    let zoned = date(2025, 3, 7)
+        .at(9, 5, 0, 0)
+        .to_zoned(jiff::tz::TimeZone::fixed(jiff::tz::offset(1)))
+        .unwrap();
+    assert_eq!("2025-03-07T09:05:00+01:00", zoned.to_datetime_string());
+    assert_eq!(
+        "2025-03-07T08:05:00Z",
+        zoned.timestamp().to_datetime_string()
+    );
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "debug")]
+
+use std::time::{Duration, Instant};
+
+use lira::prelude::*;
+
+#[test]
+fn test_debug_panel() {
+    let ctx = RenderContext::new()
+        .mode(RenderMode::Lenient)
+        .nonce("abc")
+        .csrf_token("s3cret");
+
+    let res = debug_panel(&ctx).render();
+
+    assert!(
+        res.starts_with(
+            "<details class=\"lira-debug\"><summary>Debug</summary><style>.lira-debug{"
+        )
+    );
+    assert!(res.contains(
+        "<table><caption>Render context</caption><tr><th>mode</th><td>Lenient</td></tr>"
+    ));
+    assert!(res.contains("<tr><th>nonce</th><td>set</td></tr>"));
+    assert!(!res.contains("abc"));
+    assert!(res.contains("<tr><th>base_path</th><td>unset</td></tr>"));
+    assert!(res.contains("<tr><th>csrf_token</th><td>set</td></tr>"));
+    assert!(!res.contains("s3cret"));
+    assert!(!res.contains("<caption>Timing</caption>"));
+    assert!(!res.contains("<caption>Values</caption>"));
+    assert!(res.ends_with("</table></details>"));
+}
+
+#[test]
+fn test_debug_panel_timings_and_values() {
+    let res = debug_panel(&RenderContext::new())
+        .timing("db", Duration::from_micros(1500))
+        .started(Instant::now())
+        .value("user", "<ada>")
+        .value("items", 3)
+        .render();
+
+    assert!(res.contains(
+        "<table><caption>Timing</caption><tr><th>db</th><td>1.50ms</td></tr><tr><th>total</th>"
+    ));
+    assert!(res.contains(
+        "<table><caption>Values</caption><tr><th>user</th><td>&lt;ada&gt;</td></tr>\
+         <tr><th>items</th><td>3</td></tr></table>"
+    ));
+}
+
+#[test]
+fn test_debug_panel_open() {
+    let res = debug_panel(&RenderContext::new()).open(true).render();
+
+    assert!(res.starts_with("<details class=\"lira-debug\" open><summary>"));
+}
+
+#[test]
+fn test_debug_panel_nonce() {
+    let ctx = RenderContext::new().nonce("n0nce");
+
+    let res = ctx.clone().scope(|| debug_panel(&ctx).render());
+
+    assert!(res.contains("<style nonce=\"n0nce\">"));
+}
@@ -0,0 +1,255 @@
+#![cfg(feature = "derive")]
+
+use lira::assert_html_eq;
+use lira::css::check_classes;
+use lira::forms::Form;
+use lira::prelude::*;
+use serde::Deserialize;
+
+#[derive(Form, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Plan {
+    Free,
+    #[form(label = "Pro (monthly)")]
+    ProMonthly,
+    #[serde(rename = "team")]
+    Business,
+}
+
+#[derive(Form, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Signup {
+    #[form(type = "email", placeholder = "you@example.com", required)]
+    email_address: String,
+    #[serde(rename = "pw")]
+    #[form(type = "password", label = "Password")]
+    password: String,
+    #[form(textarea)]
+    about_me: Option<String>,
+    age: Option<u32>,
+    plan: Plan,
+    newsletter: bool,
+    #[form(skip)]
+    #[serde(skip)]
+    #[allow(dead_code)]
+    internal: u64,
+}
+
+#[test]
+fn test_empty_fields() {
+    assert_html_eq!(
+        Signup::empty_fields(),
+        r#"
+        <label for="emailAddress">Email address</label>
+        <input type="email" id="emailAddress" name="emailAddress" placeholder="you@example.com" required>
+        <label for="pw">Password</label>
+        <input type="password" id="pw" name="pw">
+        <label for="aboutMe">About me</label>
+        <textarea id="aboutMe" name="aboutMe"></textarea>
+        <label for="age">Age</label>
+        <input type="number" id="age" name="age">
+        <label for="plan">Plan</label>
+        <select id="plan" name="plan">
+            <option value="free">Free</option>
+            <option value="pro-monthly">Pro (monthly)</option>
+            <option value="team">Business</option>
+        </select>
+        <input type="checkbox" id="newsletter" name="newsletter" value="true">
+        <label for="newsletter">Newsletter</label>
+        "#
+    );
+}
+
+#[test]
+fn test_fields_hold_values() {
+    let signup = Signup {
+        email_address: "me@example.com".to_string(),
+        password: String::new(),
+        about_me: Some("Hello".to_string()),
+        age: Some(30),
+        plan: Plan::Business,
+        newsletter: true,
+        internal: 7,
+    };
+
+    let res = form().child(signup.fields()).render();
+
+    assert!(res.contains(
+        "name=\"emailAddress\" placeholder=\"you@example.com\" required value=\"me@example.com\""
+    ));
+    assert!(res.contains("<textarea id=\"aboutMe\" name=\"aboutMe\">Hello</textarea>"));
+    assert!(res.contains("name=\"age\" value=\"30\""));
+    assert!(res.contains("<option value=\"team\" selected>Business</option>"));
+    assert!(res.contains("value=\"true\" checked"));
+    assert!(!res.contains("internal"));
+}
+
+fn price_cell(cents: &u64) -> Node<Td, Content> {
+    td().class("num")
+        .text(format!("{}.{:02}", cents / 100, cents % 100))
+}
+
+#[derive(ToTableRow)]
+struct Order<'a> {
+    #[table(label = "#")]
+    id: u32,
+    customer: &'a str,
+    #[table(with = "price_cell")]
+    total_cents: u64,
+    #[table(skip)]
+    #[allow(dead_code)]
+    notes: String,
+}
+
+#[test]
+fn test_derive_to_table_row() {
+    let orders = [Order {
+        id: 7,
+        customer: "Ferris",
+        total_cents: 1250,
+        notes: String::new(),
+    }];
+
+    assert_html_eq!(
+        table_of(&orders),
+        r#"
+        <table>
+            <thead><tr><th scope="col">#</th><th scope="col">Customer</th><th scope="col">Total cents</th></tr></thead>
+            <tbody><tr><td>7</td><td>Ferris</td><td class="num">12.50</td></tr></tbody>
+        </table>
+        "#
+    );
+}
+
+#[check_classes("tests/fixtures/app.css", allow = ["js-*", "htmx-request"])]
+fn checked_card() -> impl Renderable {
+    div()
+        .class("card md:grid js-toggle")
+        .child(button().class("btn btn-disabled htmx-request").text("Go"))
+        .child(p().class(String::from("not-checked")))
+}
+
+#[test]
+fn test_check_classes() {
+    assert_eq!(
+        "<div class=\"card md:grid js-toggle\"><button class=\"btn btn-disabled htmx-request\">Go</button>\
+         <p class=\"not-checked\"></p></div>",
+        checked_card().render()
+    );
+}
+
+#[derive(lira::forms::FieldNames, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[allow(dead_code)]
+struct Search {
+    search_term: String,
+    #[serde(rename = "p")]
+    page: u32,
+    r#type: Option<String>,
+}
+
+#[test]
+fn test_field_names() {
+    assert_eq!("search-term", Search::SEARCH_TERM);
+    assert_eq!("p", Search::PAGE);
+    assert_eq!("type", Search::TYPE);
+
+    let res = form()
+        .child(input().name(Search::SEARCH_TERM))
+        .child(input().name(Search::PAGE))
+        .render();
+    assert_eq!(
+        "<form><input name=\"search-term\" /><input name=\"p\" /></form>",
+        res
+    );
+}
+
+fn yes_no(value: &bool) -> Node<Dd, Content> {
+    dd().text(if *value { "Yes" } else { "No" })
+}
+
+#[derive(lira::lists::ToDescriptionList)]
+struct Account<'a> {
+    #[dl(rename = "User name")]
+    name: &'a str,
+    email: Option<&'a str>,
+    #[dl(with = "yes_no")]
+    is_admin: bool,
+    login_count: u32,
+    #[dl(skip)]
+    #[allow(dead_code)]
+    password_hash: String,
+}
+
+#[test]
+fn test_derive_to_description_list() {
+    use lira::lists::ToDescriptionList;
+
+    let account = Account {
+        name: "Ferris & Co",
+        email: None,
+        is_admin: true,
+        login_count: 3,
+        password_hash: String::new(),
+    };
+
+    assert_html_eq!(
+        account.to_dl(),
+        r#"
+        <dl>
+            <dt>User name</dt><dd>Ferris &amp; Co</dd>
+            <dt>Email</dt><dd></dd>
+            <dt>Is admin</dt><dd>Yes</dd>
+            <dt>Login count</dt><dd>3</dd>
+        </dl>
+        "#
+    );
+    assert_eq!(
+        "<dl class=\"details\"><dt>User name</dt><dd>Ferris &amp; Co</dd><dt>Email</dt><dd></dd>\
+         <dt>Is admin</dt><dd>Yes</dd><dt>Login count</dt><dd>3</dd></dl>",
+        dl().class("details").child(account.entries()).render()
+    );
+}
+
+#[derive(lira::forms::SelectOptions, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Sort {
+    Newest,
+    #[form(label = "Most popular")]
+    MostPopular,
+    #[serde(rename = "az")]
+    Alphabetical,
+    #[form(skip)]
+    Legacy,
+}
+
+#[test]
+fn test_derive_select_options() {
+    use lira::forms::SelectOptions;
+
+    assert_eq!(
+        &[
+            ("newest", "Newest"),
+            ("most_popular", "Most popular"),
+            ("az", "Alphabetical")
+        ],
+        Sort::OPTIONS
+    );
+    assert_eq!("legacy", Sort::Legacy.value());
+    assert_html_eq!(
+        select().name("sort").child(Sort::MostPopular.selected()),
+        r#"
+        <select name="sort">
+            <option value="newest">Newest</option>
+            <option value="most_popular" selected>Most popular</option>
+            <option value="az">Alphabetical</option>
+        </select>
+        "#
+    );
+    assert_eq!(
+        "<option value=\"newest\">Newest</option><option value=\"most_popular\">Most popular</option>\
+         <option value=\"az\">Alphabetical</option>",
+        Sort::options().render()
+    );
+    assert_eq!(Sort::options().render(), Sort::Legacy.selected().render());
+}
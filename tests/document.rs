@@ -0,0 +1,106 @@
+use lira::prelude::*;
+
+fn chart() -> Node<Div, Open> {
+    require_stylesheet("/css/chart.css");
+    require_inline_script("initCharts()");
+    div().class("chart")
+}
+
+#[test]
+fn test_document() {
+    let res = RenderContext::new().nonce("n0nce").scope(|| {
+        document().head(meta().charset("utf-8")).render(|| {
+            body()
+                .child(chart())
+                .child(section().child(chart()))
+                .child(script().src("/js/app.js"))
+        })
+    });
+
+    assert_eq!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\" /><link rel=\"stylesheet\" href=\"/css/chart.css\" />\
+         <script nonce=\"n0nce\">initCharts()</script></head><body><div class=\"chart\"></div>\
+         <section><div class=\"chart\"></div></section><script nonce=\"n0nce\" src=\"/js/app.js\"></script></body></html>",
+        res
+    );
+}
+
+#[test]
+fn test_requirements_outside_document() {
+    require_meta("robots", "noindex");
+
+    assert_eq!("<div class=\"chart\"></div>", chart().render());
+}
+
+#[test]
+fn test_require_head() {
+    let res = document().render(|| {
+        require_meta("robots", "noindex");
+        require_head(link().rel(Rel::Icon).href("/favicon.svg"));
+        body().child(p().text("Hi"))
+    });
+
+    assert_eq!(
+        "<!DOCTYPE html><html><head><meta name=\"robots\" content=\"noindex\" />\
+         <link rel=\"icon\" href=\"/favicon.svg\" /></head><body><p>Hi</p></body></html>",
+        res
+    );
+}
+
+#[test]
+fn test_document_metadata_overrides() {
+    let res = document()
+        .title("Shop")
+        .canonical("https://shop.example/")
+        .og("og:image", "/default.png")
+        .og("og:type", "website")
+        .render(|| {
+            set_canonical("https://shop.example/tea");
+            set_og("og:image", "/tea.png");
+            set_description("Loose leaf tea");
+            body().child(p().text("..."))
+        });
+
+    assert_eq!(
+        "<!DOCTYPE html><html><head><title>Shop</title><link rel=\"canonical\" href=\"https://shop.example/tea\" />\
+         <meta property=\"og:image\" content=\"/tea.png\" /><meta property=\"og:type\" content=\"website\" />\
+         <meta name=\"description\" content=\"Loose leaf tea\" /></head><body><p>...</p></body></html>",
+        res
+    );
+}
+
+#[test]
+fn test_set_title_last_wins() {
+    let res = document().render(|| {
+        set_title("Draft");
+        set_head("robots", meta().name("robots").content("noindex"));
+        set_title("Final");
+        body().close()
+    });
+
+    assert_eq!(
+        "<!DOCTYPE html><html><head><title>Final</title><meta name=\"robots\" content=\"noindex\" /></head>\
+         <body></body></html>",
+        res
+    );
+}
+
+#[test]
+fn test_page() {
+    let res = page(
+        "Users & Groups",
+        link().rel(Rel::Stylesheet).href("/admin.css"),
+        fragment()
+            .child(h1().text("Users"))
+            .child(p().text("None yet")),
+    )
+    .render();
+
+    assert_eq!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\" />\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />\
+         <title>Users &amp; Groups</title><link rel=\"stylesheet\" href=\"/admin.css\" /></head>\
+         <body><h1>Users</h1><p>None yet</p></body></html>",
+        res
+    );
+}
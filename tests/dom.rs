@@ -0,0 +1,58 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use lira::dom::render_dom;
+use lira::prelude::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn document() -> web_sys::Document {
+    web_sys::window().unwrap().document().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_render_dom() {
+    let document = document();
+    let fragment = render_dom(
+        div()
+            .class("card")
+            .child(h1().text("Hello & welcome"))
+            .child(img().src("/icon.png")),
+        &document,
+    )
+    .unwrap();
+
+    let root = document.create_element("div").unwrap();
+    root.append_child(&fragment).unwrap();
+
+    assert_eq!(
+        "<div class=\"card\"><h1>Hello &amp; welcome</h1><img src=\"/icon.png\"></div>",
+        root.inner_html()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_render_dom_svg() {
+    let document = document();
+    let fragment = render_dom(
+        svg().viewbox("0 0 2 2").raw(
+            "<linearGradient id=\"g\" gradientUnits=\"userSpaceOnUse\"></linearGradient>&hearts;",
+        ),
+        &document,
+    )
+    .unwrap();
+
+    let root = document.create_element("div").unwrap();
+    root.append_child(&fragment).unwrap();
+
+    let svg = root.first_element_child().unwrap();
+    assert_eq!(Some("0 0 2 2"), svg.get_attribute("viewBox").as_deref());
+    assert_eq!(Some("\u{2665}"), svg.text_content().as_deref());
+
+    let gradient = svg.first_element_child().unwrap();
+    assert_eq!("linearGradient", gradient.local_name());
+    assert_eq!(
+        Some("userSpaceOnUse"),
+        gradient.get_attribute("gradientUnits").as_deref()
+    );
+}
@@ -0,0 +1,65 @@
+use lira::email::{column, container, mso, not_mso, row};
+use lira::prelude::*;
+
+#[test]
+fn test_mso_conditionals() {
+    let res = div()
+        .child(mso(table().child(tr().child(td().text("Outlook")))))
+        .child(not_mso(div().text("Everyone else")))
+        .render();
+
+    assert_eq!(
+        "<div><!--[if mso]><table><tr><td>Outlook</td></tr></table><![endif]--><!--[if !mso]><!--><div>Everyone else</div><!--<![endif]--></div>",
+        res
+    );
+}
+
+#[test]
+fn test_table_layout() {
+    let res = container(600)
+        .child(
+            row()
+                .child(column(300).text("Left"))
+                .child(column(300).text("Right")),
+        )
+        .render();
+
+    assert_eq!(
+        "<table role=\"presentation\" cellpadding=\"0\" cellspacing=\"0\" border=\"0\" align=\"center\" width=\"600\" style=\"width:100%;max-width:600px\"><tr><td width=\"300\" valign=\"top\">Left</td><td width=\"300\" valign=\"top\">Right</td></tr></table>",
+        res
+    );
+}
+
+#[test]
+fn test_escape_non_ascii() {
+    let res = RenderContext::new()
+        .escape_non_ascii(true)
+        .scope(|| p().title("Grüße").text("Café & 💩").render());
+    assert_eq!(
+        "<p title=\"Gr&#252;&#223;e\">Caf&#233; &amp; &#128169;</p>",
+        res
+    );
+
+    assert_eq!("<p>Café</p>", p().text("Café").render());
+}
+
+#[test]
+fn test_inline_styles() {
+    let res = RenderContext::new()
+        .inline_styles(|tag| match tag {
+            "p" => Some("margin:0;font-family:sans-serif".to_string()),
+            "a" => Some("color:#0366d6".to_string()),
+            _ => None,
+        })
+        .scope(|| {
+            div()
+                .child(p().text("Hello"))
+                .child(a().href("https://example.com").text("Link"))
+                .render()
+        });
+
+    assert_eq!(
+        "<div><p style=\"margin:0;font-family:sans-serif\">Hello</p><a style=\"color:#0366d6\" href=\"https://example.com\">Link</a></div>",
+        res
+    );
+}
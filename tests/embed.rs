@@ -0,0 +1,65 @@
+use lira::prelude::*;
+
+#[test]
+fn test_embed_as_is() {
+    let res = div()
+        .child(EmbedHtml::new("<p>from <b>elsewhere</b></p>"))
+        .render();
+    assert_eq!("<div><p>from <b>elsewhere</b></p></div>", res);
+}
+
+#[test]
+fn test_island_name_is_escaped() {
+    let res = EmbedHtml::new("x").island("a\"b").render();
+    assert_eq!("<div data-island=\"a&quot;b\">x</div>", res);
+}
+
+#[test]
+fn test_checked_accepts_balanced_markup() {
+    let html = "<!-- hydration --><ul><li>a<br></li><li><img src=\"x.png\" /></li></ul>text";
+    assert_eq!(html, EmbedHtml::checked(html).unwrap().as_str());
+}
+
+#[test]
+fn test_checked_rejects_closing_outer_elements() {
+    let err = EmbedHtml::checked("<p>a</p></main>").unwrap_err();
+    assert_eq!(8, err.offset);
+    assert_eq!(
+        "can't embed html: </main> closes an element outside of the markup at byte 8",
+        err.to_string()
+    );
+}
+
+#[test]
+fn test_checked_rejects_mismatched_and_unclosed() {
+    let err = EmbedHtml::checked("<div><span></div>").unwrap_err();
+    assert_eq!(
+        "can't embed html: </div> closes <span> at byte 11",
+        err.to_string()
+    );
+
+    let err = EmbedHtml::checked("<section><p>a</p>").unwrap_err();
+    assert_eq!(0, err.offset);
+    assert!(err.to_string().contains("<section> is never closed"));
+}
+
+#[test]
+fn test_checked_rejects_unfinished_markup() {
+    for (html, offset, message) in [
+        ("<!-- oops", 0, "the comment is never closed"),
+        ("<p>a</p><a href=\"x>oops", 8, "the tag is never finished"),
+        ("<div title='", 0, "the tag is never finished"),
+        ("<p>a</p></p", 8, "the tag is never finished"),
+        ("a <", 2, "the tag is never finished"),
+    ] {
+        let err = EmbedHtml::checked(html).unwrap_err();
+        assert_eq!(offset, err.offset, "{:?}", html);
+        assert_eq!(
+            format!("can't embed html: {} at byte {}", message, offset),
+            err.to_string()
+        );
+    }
+
+    let html = "<p>a < b, 1 <= 2</p><script>if (a <b) {}</script>";
+    assert_eq!(html, EmbedHtml::checked(html).unwrap().as_str());
+}
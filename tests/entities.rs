@@ -0,0 +1,39 @@
+use lira::prelude::*;
+
+#[test]
+fn test_entities() {
+    assert_eq!(
+        "<p>Loading&hellip;</p><p>1920&times;1080</p><p>2019&ndash;2024&thinsp;&middot;&thinsp;&copy;&nbsp;lira</p>",
+        fragment()
+            .child(p().text("Loading").child(hellip()))
+            .child(p().text("1920").child(times()).text("1080"))
+            .child(
+                p().text("2019")
+                    .child(ndash())
+                    .text("2024")
+                    .child(thinsp())
+                    .child(middot())
+                    .child(thinsp())
+                    .child(copy())
+                    .child(nbsp())
+                    .text("lira")
+            )
+            .render()
+    );
+}
+
+#[test]
+fn test_entity_value() {
+    assert_eq!("&mdash;", mdash().as_str());
+    assert_eq!("&laquo;", laquo().render());
+    assert_eq!(raquo(), raquo());
+}
+
+#[test]
+fn test_entity_in_attribute_text_is_escaped() {
+    // entities are markup, as text they are escaped like anything else
+    assert_eq!(
+        "<span title=\"&amp;nbsp;\"></span>",
+        span().title(nbsp().as_str()).render()
+    );
+}
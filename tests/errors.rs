@@ -0,0 +1,66 @@
+use lira::prelude::*;
+
+#[test]
+fn test_not_found() {
+    let page = not_found();
+
+    assert_eq!(404, page.status());
+    assert_eq!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\" />\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" /><title>404 Not Found</title>\
+         </head><body><main class=\"error-page\"><p class=\"error-page__status\">404</p><h1>Not Found</h1>\
+         <p>The page you are looking for doesn&#39;t exist or has been moved.</p><p><a href=\"/\">Home</a></p>\
+         </main></body></html>",
+        page.styles(stylesheet()).render()
+    );
+}
+
+#[test]
+fn test_presets() {
+    assert_eq!(500, internal_error().status());
+    assert_eq!(503, unavailable().status());
+
+    let res = unavailable().render();
+    assert!(res.contains("<title>503 Service Unavailable</title>"));
+    assert!(res.contains("<h1>Service Unavailable</h1>"));
+    assert!(!res.contains("<a href"));
+}
+
+#[test]
+fn test_default_styles() {
+    let res = internal_error().render();
+
+    assert!(res.contains("<style>body{margin:0;"));
+    assert!(res.contains(".error-page{max-width:36rem;"));
+    assert!(res.contains("@media (prefers-color-scheme: dark)"));
+}
+
+#[test]
+fn test_custom_page() {
+    let res = error_page(429, "Slow down, <please>.")
+        .title("Too many requests")
+        .lang("en")
+        .link("/status", "Status page")
+        .details("Request id", code().text("7f3a"))
+        .styles(stylesheet().rule(rule(".error-page").color("red")))
+        .render();
+
+    assert_eq!(
+        "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\" />\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />\
+         <title>429 Too many requests</title><style>.error-page{color:red}</style></head><body>\
+         <main class=\"error-page\"><p class=\"error-page__status\">429</p><h1>Too many requests</h1>\
+         <p>Slow down, &lt;please&gt;.</p><p><a href=\"/status\">Status page</a></p>\
+         <details class=\"error-page__details\"><summary>Request id</summary><div><code>7f3a</code></div>\
+         </details></main></body></html>",
+        res
+    );
+}
+
+#[test]
+fn test_unknown_status() {
+    let res = error_page(599, "Gone fishing").no_link().render();
+
+    assert!(res.contains("<title>599 Error</title>"));
+    assert!(res.contains("<h1>Error</h1><p>Gone fishing</p>"));
+}
@@ -0,0 +1,26 @@
+use lira::prelude::*;
+
+#[test]
+fn test_render_hashed() {
+    let (html, hash) = div().child(p().text("abc")).render_hashed();
+
+    assert_eq!("<div><p>abc</p></div>", html);
+    assert_eq!(ContentHash::of(&html), hash);
+    assert_ne!(ContentHash::of("<div><p>abd</p></div>"), hash);
+}
+
+#[test]
+fn test_content_hash_digest() {
+    // SHA-256 test vector from FIPS 180-2
+    let hash = ContentHash::of("abc");
+
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        hash.to_string()
+    );
+    assert_eq!(
+        "\"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\"",
+        hash.etag()
+    );
+    assert_eq!(0xba, hash.as_bytes()[0]);
+}
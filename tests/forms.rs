@@ -0,0 +1,490 @@
+use lira::forms::{Field, FieldErrors, FormField, error_summary, select_field};
+use lira::prelude::*;
+
+#[test]
+fn test_text_field() {
+    let field = Field {
+        name: "email",
+        label: "E-Mail",
+        input_type: Some("email"),
+        placeholder: Some("you@example.com"),
+        required: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        "<label for=\"email\">E-Mail</label><input type=\"email\" id=\"email\" name=\"email\" placeholder=\"you@example.com\" required value=\"a&lt;b\" />",
+        String::render_field(&field, Some(&"a<b".to_string())).render()
+    );
+}
+
+#[test]
+fn test_textarea_field() {
+    let field = Field {
+        name: "bio",
+        label: "Bio",
+        textarea: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        "<label for=\"bio\">Bio</label><textarea id=\"bio\" name=\"bio\">Hi</textarea>",
+        str::render_field(&field, Some("Hi")).render()
+    );
+}
+
+#[test]
+fn test_checkbox_field() {
+    let field = Field {
+        name: "terms",
+        label: "Accept terms",
+        ..Default::default()
+    };
+
+    assert_eq!(
+        "<input type=\"checkbox\" id=\"terms\" name=\"terms\" value=\"true\" checked /><label for=\"terms\">Accept terms</label>",
+        bool::render_field(&field, Some(&true)).render()
+    );
+}
+
+#[test]
+fn test_number_fields() {
+    let field = Field {
+        name: "age",
+        label: "Age",
+        ..Default::default()
+    };
+
+    assert_eq!(
+        "<label for=\"age\">Age</label><input type=\"number\" id=\"age\" name=\"age\" value=\"42\" />",
+        u8::render_field(&field, Some(&42)).render()
+    );
+    assert_eq!(
+        "<label for=\"age\">Age</label><input type=\"number\" id=\"age\" name=\"age\" step=\"any\" />",
+        Option::<f64>::render_field(&field, Some(&None)).render()
+    );
+}
+
+#[test]
+fn test_select_field() {
+    let field = Field {
+        name: "size",
+        label: "Size",
+        ..Default::default()
+    };
+
+    assert_eq!(
+        "<label for=\"size\">Size</label><select id=\"size\" name=\"size\"><option value=\"s\">Small</option><option value=\"m\" selected>Medium</option></select>",
+        select_field(&field, &[("s", "Small"), ("m", "Medium")], Some("m")).render()
+    );
+}
+
+#[test]
+fn test_select_options() {
+    struct Country {
+        code: &'static str,
+        name: &'static str,
+    }
+
+    let countries = vec![
+        Country {
+            code: "de",
+            name: "Germany",
+        },
+        Country {
+            code: "jp",
+            name: "Japan",
+        },
+    ];
+
+    assert_eq!(
+        "<select name=\"country\"><option value=\"de\">Germany</option><option value=\"jp\" selected>Japan</option></select>",
+        select()
+            .name("country")
+            .child(select_options(
+                &countries,
+                |country| (country.code, country.name),
+                Some("jp")
+            ))
+            .render()
+    );
+
+    assert_eq!(
+        "<option value=\"1\">One</option><option value=\"2\">Two</option>",
+        select_options(
+            [(1, "One"), (2, "Two")],
+            |(id, label)| (id.to_string(), label),
+            None
+        )
+        .render()
+    );
+}
+
+#[test]
+fn test_checkbox() {
+    assert_eq!(
+        "<input type=\"checkbox\" id=\"news\" name=\"news\" value=\"true\" /><label for=\"news\">Newsletter</label>",
+        checkbox("news", "Newsletter", false).render()
+    );
+}
+
+#[test]
+fn test_radio_group() {
+    let res = form()
+        .child(radio_group(
+            "shipping",
+            vec![
+                ("standard".to_string(), "Standard"),
+                ("next day".to_string(), "Next day"),
+            ],
+            Some("next day"),
+        ))
+        .render();
+
+    assert_eq!(
+        "<form><input type=\"radio\" id=\"shipping\" name=\"shipping\" value=\"standard\" /><label for=\"shipping\">Standard</label><input type=\"radio\" id=\"shipping-next-day\" name=\"shipping\" value=\"next day\" checked /><label for=\"shipping-next-day\">Next day</label></form>",
+        res
+    );
+}
+
+#[test]
+fn test_repeated_controls_get_unique_ids() {
+    let res = RenderContext::new().scope(|| {
+        fragment()
+            .child(checkbox("news", "Newsletter", false))
+            .child(checkbox("news", "Newsletter", true))
+            .child(radio_group("size", [("s", "Small")], None))
+            .child(radio_group("size", [("s", "Small")], Some("s")))
+            .render()
+    });
+
+    assert_eq!(
+        "<input type=\"checkbox\" id=\"news\" name=\"news\" value=\"true\" /><label for=\"news\">Newsletter</label>\
+         <input type=\"checkbox\" id=\"news-2\" name=\"news\" value=\"true\" checked /><label for=\"news-2\">Newsletter</label>\
+         <input type=\"radio\" id=\"size\" name=\"size\" value=\"s\" /><label for=\"size\">Small</label>\
+         <input type=\"radio\" id=\"size-2\" name=\"size\" value=\"s\" checked /><label for=\"size-2\">Small</label>",
+        res
+    );
+}
+
+#[test]
+fn test_csrf_field() {
+    assert_eq!(
+        "<input type=\"hidden\" name=\"csrf_token\" value=\"abc\" />",
+        csrf_field("abc").render()
+    );
+}
+
+#[test]
+fn test_csrf_from_context() {
+    let res = RenderContext::new()
+        .csrf_token("abc")
+        .csrf_field_name("authenticity_token")
+        .scope(|| form().method(FormMethod::Post).child(csrf()).render());
+
+    assert_eq!(
+        "<form method=\"POST\"><input type=\"hidden\" name=\"authenticity_token\" value=\"abc\" /></form>",
+        res
+    );
+}
+
+#[test]
+#[should_panic(expected = "without a CSRF token")]
+fn test_csrf_without_token() {
+    form().child(csrf()).render();
+}
+
+#[test]
+fn test_labeled_input() {
+    let res = RenderContext::new().scope(|| {
+        form()
+            .child(labeled_input("Name", |input| input.name("name").required()))
+            .child(labeled_input("Age", |input| {
+                input.input_type(InputType::Number).name("age")
+            }))
+            .child(labeled_input("Note", |input| input))
+            .render()
+    });
+
+    assert_eq!(
+        "<form><label for=\"name\">Name</label><input name=\"name\" required id=\"name\" />\
+         <label for=\"age\">Age</label><input type=\"number\" name=\"age\" id=\"age\" />\
+         <label for=\"input-1\">Note</label><input id=\"input-1\" /></form>",
+        res
+    );
+}
+
+#[test]
+fn test_labeled_input_keeps_id() {
+    let res = RenderContext::new()
+        .field_errors(FieldErrors::new().add("email", "Enter an email address"))
+        .scope(|| {
+            fragment()
+                .child(labeled_input("E-Mail", |input| {
+                    input.id("login-email").name("email")
+                }))
+                .child(labeled_input("Note", |input| input.id("note")))
+                .render()
+        });
+
+    assert_eq!(
+        "<label for=\"login-email\">E-Mail</label><input id=\"login-email\" name=\"email\" aria-invalid=\"true\" \
+         aria-describedby=\"login-email-error-1\" /><p id=\"login-email-error-1\" class=\"field-error\">\
+         Enter an email address</p><label for=\"note\">Note</label><input id=\"note\" />",
+        res
+    );
+}
+
+#[test]
+fn test_field_errors() {
+    let errors: FieldErrors = [
+        ("password", "Too short"),
+        ("terms", "Required"),
+        ("password", "Needs a digit"),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(["Too short", "Needs a digit"], errors.get("password"));
+    assert!(errors.get("email").is_empty());
+
+    let password = Field {
+        name: "password",
+        label: "Password",
+        input_type: Some("password"),
+        required: true,
+        ..Default::default()
+    };
+    let terms = Field {
+        name: "terms",
+        label: "Accept terms",
+        ..Default::default()
+    };
+
+    let res = RenderContext::new().field_errors(errors).scope(|| {
+        fragment()
+            .child(str::render_field(&password, None))
+            .child(bool::render_field(&terms, Some(&false)))
+            .render()
+    });
+
+    assert_eq!(
+        "<label for=\"password\">Password</label><input type=\"password\" id=\"password\" name=\"password\" \
+         aria-invalid=\"true\" aria-describedby=\"password-error-1 password-error-2\" required />\
+         <p id=\"password-error-1\" class=\"field-error\">Too short</p>\
+         <p id=\"password-error-2\" class=\"field-error\">Needs a digit</p>\
+         <input type=\"checkbox\" id=\"terms\" name=\"terms\" aria-invalid=\"true\" aria-describedby=\"terms-error-1\" \
+         value=\"true\" /><label for=\"terms\">Accept terms</label><p id=\"terms-error-1\" class=\"field-error\">Required</p>",
+        res
+    );
+}
+
+#[test]
+fn test_select_field_errors() {
+    let field = Field {
+        name: "size",
+        label: "Size",
+        ..Default::default()
+    };
+
+    let res = RenderContext::new()
+        .field_errors(FieldErrors::new().add("size", "Pick a size"))
+        .scope(|| select_field(&field, &[("s", "Small")], None).render());
+
+    assert_eq!(
+        "<label for=\"size\">Size</label><select id=\"size\" name=\"size\" aria-invalid=\"true\" \
+         aria-describedby=\"size-error-1\"><option value=\"s\">Small</option></select>\
+         <p id=\"size-error-1\" class=\"field-error\">Pick a size</p>",
+        res
+    );
+}
+
+#[test]
+fn test_error_summary() {
+    let errors = FieldErrors::new()
+        .add("email", "Enter an email address")
+        .add("age", "Must be a number");
+
+    assert_eq!(
+        "<div role=\"alert\" aria-live=\"assertive\" aria-atomic=\"true\" class=\"error-summary\" tabindex=\"-1\">\
+         <h2>Please fix</h2><ul><li><a href=\"#email\">Enter an email address</a></li>\
+         <li><a href=\"#age\">Must be a number</a></li></ul></div>",
+        error_summary(&errors, "Please fix").render()
+    );
+    assert_eq!(
+        "",
+        error_summary(&FieldErrors::new(), "Please fix").render()
+    );
+}
+
+#[test]
+fn test_radio_group_errors() {
+    let res = RenderContext::new()
+        .field_errors(FieldErrors::new().add("size", "Pick a size"))
+        .scope(|| radio_group("size", [("s", "Small"), ("m", "Medium")], None).render());
+
+    assert_eq!(
+        "<input type=\"radio\" id=\"size\" name=\"size\" value=\"s\" aria-invalid=\"true\" \
+         aria-describedby=\"size-error-1\" /><label for=\"size\">Small</label>\
+         <input type=\"radio\" id=\"size-m\" name=\"size\" value=\"m\" aria-invalid=\"true\" \
+         aria-describedby=\"size-error-1\" /><label for=\"size-m\">Medium</label>\
+         <p id=\"size-error-1\" class=\"field-error\">Pick a size</p>",
+        res
+    );
+}
+
+#[test]
+fn test_error_summary_links_to_controls() {
+    let errors = FieldErrors::new()
+        .add("email", "Enter an email address")
+        .add("terms", "Accept the terms")
+        .add("size", "Pick a size")
+        .add("age", "Must be a number");
+    let age = Field {
+        name: "age",
+        label: "Age",
+        ..Default::default()
+    };
+
+    let page = RenderContext::new().field_errors(errors.clone()).scope(|| {
+        form()
+            .child(error_summary(&errors, "Please fix"))
+            .child(labeled_input("E-Mail", |input| input.name("email")))
+            .child(checkbox("terms", "Accept terms", false))
+            .child(radio_group("size", [("s", "Small"), ("m", "Medium")], None))
+            .child(u8::render_field(&age, None))
+            .render()
+    });
+
+    let tree = Tree::parse(&page);
+    let mut hrefs = Vec::new();
+    let mut invalid = Vec::new();
+    tree.walk(|element| {
+        if let Some(href) = element.attr("href") {
+            hrefs.push(href.trim_start_matches('#').to_string());
+        }
+        if element.attr("aria-invalid").is_some() {
+            invalid.extend(element.attr("id").map(str::to_string));
+        }
+    });
+
+    assert_eq!(4, hrefs.len());
+    for href in hrefs {
+        assert!(
+            invalid.contains(&href),
+            "no invalid control with the id {}",
+            href
+        );
+    }
+}
+
+#[test]
+fn test_error_summary_ignores_heading_slugs() {
+    let errors = FieldErrors::new().add("email", "Enter an email address");
+
+    let page = RenderContext::new().field_errors(errors.clone()).scope(|| {
+        fragment()
+            .child(heading_anchored(2, "Email"))
+            .child(error_summary(&errors, "Please fix"))
+            .child(labeled_input("E-Mail", |input| input.name("email")))
+            .child(heading_anchored(2, "Email"))
+            .render()
+    });
+
+    assert!(page.contains("<a href=\"#email\">Enter an email address</a>"));
+    assert!(page.contains("<input name=\"email\" id=\"email\""));
+    assert!(page.contains("<h2 id=\"email-2\">"));
+}
+
+#[test]
+fn test_error_summary_links_to_numbered_controls() {
+    let errors = FieldErrors::new().add("email", "Enter an email address");
+
+    let page = RenderContext::new().field_errors(errors.clone()).scope(|| {
+        fragment()
+            .child(labeled_input("Name", |input| input.name("email-error-1")))
+            .child(labeled_input("E-Mail", |input| input.name("email")))
+            .child(labeled_input("E-Mail", |input| input.name("email")))
+            .child(error_summary(&errors, "Please fix"))
+            .render()
+    });
+
+    assert_eq!(
+        "<label for=\"email-error-1\">Name</label><input name=\"email-error-1\" id=\"email-error-1\" />\
+         <label for=\"email\">E-Mail</label><input name=\"email\" id=\"email\" aria-invalid=\"true\" \
+         aria-describedby=\"email-error-1-2\" /><p id=\"email-error-1-2\" class=\"field-error\">Enter an email address</p>\
+         <label for=\"email-2\">E-Mail</label><input name=\"email\" id=\"email-2\" aria-invalid=\"true\" \
+         aria-describedby=\"email-2-error-1\" /><p id=\"email-2-error-1\" class=\"field-error\">Enter an email address</p>\
+         <div role=\"alert\" aria-live=\"assertive\" aria-atomic=\"true\" class=\"error-summary\" tabindex=\"-1\">\
+         <h2>Please fix</h2><ul><li><a href=\"#email\">Enter an email address</a></li></ul></div>",
+        page
+    );
+}
+
+#[test]
+fn test_range_input() {
+    assert_eq!(
+        "<input type=\"range\" min=\"-10\" max=\"10\" step=\"2\" value=\"0\" />",
+        range_input(-10i8, 10, 2, 0).render()
+    );
+    assert_eq!(
+        "<input type=\"range\" min=\"0\" max=\"1.5\" step=\"0.1\" value=\"1.25\" />",
+        range_input(0.0f32, 1.5, 0.1, 1.25).render()
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "value 11 is outside of 0..=10")]
+fn test_range_input_out_of_bounds() {
+    range_input(0, 10, 1, 11);
+}
+
+#[test]
+fn test_number_input() {
+    assert_eq!(
+        "<input type=\"number\" min=\"0\" max=\"99.99\" step=\"0.01\" name=\"price\" required />",
+        number_input(0.0, 99.99, 0.01, None)
+            .name("price")
+            .required()
+            .render()
+    );
+    assert_eq!(
+        "<input type=\"number\" min=\"0\" max=\"100\" step=\"5\" value=\"25\" />",
+        number_input(0u64, 100, 5, Some(25)).render()
+    );
+}
+
+#[test]
+#[should_panic(expected = "value of a numeric input can't be NaN")]
+fn test_number_input_nan() {
+    number_input(0.0, 1.0, 0.1, Some(f64::NAN));
+}
+
+#[test]
+#[should_panic(expected = "max of a numeric input can't be inf")]
+fn test_number_input_infinite() {
+    number_input(0.0, f32::INFINITY, 1.0, None);
+}
+
+#[test]
+fn test_range_with_output() {
+    assert_eq!(
+        "<input type=\"range\" min=\"0\" max=\"100\" step=\"10\" value=\"50\" id=\"zoom\" name=\"zoom\" \
+         oninput=\"this.nextElementSibling.value=this.value\" /><output for=\"zoom\">50</output>",
+        range_with_output("zoom", 0, 100, 10, 50).render()
+    );
+}
+
+#[test]
+fn test_repeated_range_with_output() {
+    let res = RenderContext::new().scope(|| {
+        fragment()
+            .child(checkbox("zoom", "Zoom", false))
+            .child(range_with_output("zoom", 0, 100, 10, 50))
+            .render()
+    });
+
+    assert!(res.contains("id=\"zoom-2\""));
+    assert!(res.contains("<output for=\"zoom-2\">50</output>"));
+}
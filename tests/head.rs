@@ -0,0 +1,227 @@
+use lira::prelude::*;
+
+#[test]
+fn test_preload_font() {
+    let res = preload("/fonts/inter.woff2", LinkAs::Font).render();
+    assert_eq!(
+        "<link rel=\"preload\" href=\"/fonts/inter.woff2\" as=\"font\" type=\"font/woff2\" crossorigin=\"anonymous\" />",
+        res
+    );
+}
+
+#[test]
+fn test_preload_style_and_image() {
+    let res = preload("/app.css", LinkAs::Style).render();
+    assert_eq!(
+        "<link rel=\"preload\" href=\"/app.css\" as=\"style\" />",
+        res
+    );
+
+    let res = preload("/hero.avif?v=2", LinkAs::Image).render();
+    assert_eq!(
+        "<link rel=\"preload\" href=\"/hero.avif?v=2\" as=\"image\" type=\"image/avif\" />",
+        res
+    );
+}
+
+#[test]
+fn test_connection_hints() {
+    let res = preconnect("https://fonts.gstatic.com")
+        .crossorigin(CrossOrigin::Anonymous)
+        .render();
+    assert_eq!(
+        "<link rel=\"preconnect\" href=\"https://fonts.gstatic.com\" crossorigin=\"anonymous\" />",
+        res
+    );
+
+    let res = dns_prefetch("https://cdn.example.com").render();
+    assert_eq!(
+        "<link rel=\"dns-prefetch\" href=\"https://cdn.example.com\" />",
+        res
+    );
+
+    let res = modulepreload("/main.js").render();
+    assert_eq!("<link rel=\"modulepreload\" href=\"/main.js\" />", res);
+}
+
+#[test]
+fn test_meta_refresh() {
+    assert_eq!(
+        "<meta http-equiv=\"refresh\" content=\"30\" />",
+        meta_refresh(30, None).render()
+    );
+    assert_eq!(
+        "<meta http-equiv=\"refresh\" content=\"0; url=https://example.com/?a=1&amp;b=2\" />",
+        meta_refresh(0, Some("https://example.com/?a=1&b=2")).render()
+    );
+}
+
+#[test]
+fn test_theme_color() {
+    assert_eq!(
+        "<meta name=\"theme-color\" content=\"#4285f4\" />",
+        theme_color("#4285f4").render()
+    );
+    assert_eq!(
+        "<meta name=\"theme-color\" content=\"white\" media=\"(prefers-color-scheme: light)\" />\
+         <meta name=\"theme-color\" content=\"black\" media=\"(prefers-color-scheme: dark)\" />",
+        theme_colors("white", "black").render()
+    );
+}
+
+#[test]
+fn test_color_scheme() {
+    assert_eq!(
+        "<meta name=\"color-scheme\" content=\"light dark\" />",
+        color_scheme("light dark").render()
+    );
+}
+
+#[test]
+fn test_importmap() {
+    let res = RenderContext::new().nonce("n").scope(|| {
+        importmap()
+            .import("lit", "https://cdn.example.com/lit@3/index.js")
+            .import("app/", "/js/app/")
+            .integrity("https://cdn.example.com/lit@3/index.js", "sha384-abc")
+            .render()
+    });
+
+    assert_eq!(
+        "<script nonce=\"n\" type=\"importmap\">{\"imports\":{\"lit\":\"https://cdn.example.com/lit@3/index.js\",\
+         \"app/\":\"/js/app/\"},\"integrity\":{\"https://cdn.example.com/lit@3/index.js\":\"sha384-abc\"}}</script>",
+        res
+    );
+}
+
+#[test]
+fn test_importmap_escaping() {
+    let res = importmap().import("x", "/a.js?</script>").render();
+
+    assert_eq!(
+        "<script type=\"importmap\">{\"imports\":{\"x\":\"/a.js?\\u003c/script\\u003e\"}}</script>",
+        res
+    );
+}
+
+#[test]
+fn test_view_transitions() {
+    assert_eq!(
+        "<meta name=\"view-transition\" content=\"same-origin\" />",
+        view_transition().render()
+    );
+    assert_eq!(
+        "@view-transition{navigation:auto}",
+        stylesheet().rule(view_transition_rule()).to_string()
+    );
+    assert_eq!(
+        "<img src=\"/hero.jpg\" style=\"view-transition-name:hero-42\" />",
+        img()
+            .src("/hero.jpg")
+            .view_transition_name("hero-42")
+            .render()
+    );
+    assert_eq!(
+        "<h1 style=\"view-transition-name:title;color:red\"></h1>",
+        h1().style(css().view_transition_name("title").color("red"))
+            .render()
+    );
+}
+
+#[test]
+fn test_deferred_script() {
+    let res = deferred_script("/js/a.js", [("key", "1 & 2")]).render();
+    assert_eq!(
+        "<script src=\"/js/a.js\" defer data-key=\"1 &amp; 2\"></script>",
+        res
+    );
+
+    let res = deferred_script("/js/a.js", Vec::<(&str, &str)>::new())
+        .async_()
+        .render();
+    assert_eq!("<script src=\"/js/a.js\" defer async></script>", res);
+}
+
+#[test]
+fn test_analytics_presets() {
+    assert_eq!(
+        "<script src=\"https://plausible.io/js/script.js\" defer data-domain=\"example.com\"></script>",
+        plausible("example.com").render()
+    );
+    assert_eq!(
+        "<script src=\"https://stats.example.com/script.js\" defer data-website-id=\"94db1cb1\"></script>",
+        umami("https://stats.example.com/script.js", "94db1cb1").render()
+    );
+    assert_eq!(
+        "<script src=\"https://cdn.usefathom.com/script.js\" defer data-site=\"ABCDEF\"></script>",
+        fathom("ABCDEF").render()
+    );
+}
+
+#[test]
+fn test_analytics_nonce() {
+    let res = RenderContext::new()
+        .nonce("r4nd")
+        .scope(|| plausible("example.com").render());
+    assert_eq!(
+        "<script nonce=\"r4nd\" src=\"https://plausible.io/js/script.js\" defer data-domain=\"example.com\"></script>",
+        res
+    );
+}
+
+#[test]
+fn test_pwa_icons() {
+    let res = pwa(
+        "/manifest.json",
+        "#fff",
+        [("/icon-152.png", 152), ("/icon-180.png", 180)],
+    )
+    .render();
+    assert!(res.starts_with("<link rel=\"manifest\" href=\"/manifest.json\" />"));
+    assert!(res.ends_with(
+        "<link rel=\"apple-touch-icon\" sizes=\"152x152\" href=\"/icon-152.png\" />\
+         <link rel=\"apple-touch-icon\" sizes=\"180x180\" href=\"/icon-180.png\" />"
+    ));
+}
+
+#[test]
+fn test_pwa_without_icons() {
+    let res = pwa("/manifest.json", "#fff", Vec::<(&str, u32)>::new()).render();
+    assert!(!res.contains("apple-touch-icon"));
+    assert!(res.contains("<meta name=\"theme-color\" content=\"#fff\" />"));
+}
+
+#[test]
+#[should_panic(expected = "without a size")]
+#[cfg(debug_assertions)]
+fn test_pwa_icon_without_size() {
+    pwa("/manifest.json", "#fff", [("/icon.png", 0)]);
+}
+
+#[test]
+fn test_feed_autodiscovery() {
+    let res = head()
+        .child(rss_autodiscovery("/rss.xml", "News & updates"))
+        .child(atom_autodiscovery("/atom.xml", "News"))
+        .child(json_feed_autodiscovery("/feed.json", "News"))
+        .render();
+
+    assert_eq!(
+        "<head><link rel=\"alternate\" type=\"application/rss+xml\" title=\"News &amp; updates\" href=\"/rss.xml\" />\
+         <link rel=\"alternate\" type=\"application/atom+xml\" title=\"News\" href=\"/atom.xml\" />\
+         <link rel=\"alternate\" type=\"application/feed+json\" title=\"News\" href=\"/feed.json\" /></head>",
+        res
+    );
+}
+
+#[test]
+fn test_feed_autodiscovery_base_path() {
+    let res = RenderContext::new()
+        .base_path("/blog")
+        .scope(|| atom_autodiscovery("/atom.xml", "Blog").render());
+
+    assert_eq!(
+        "<link rel=\"alternate\" type=\"application/atom+xml\" title=\"Blog\" href=\"/blog/atom.xml\" />",
+        res
+    );
+}
@@ -126,7 +126,47 @@ fn test_data_attribute_case_normalization() {
 #[test]
 fn test_flag_with_unsafe_chars() {
     let res = div().flag("onload=alert(1)").render();
-    assert_eq!("<div onload=alert(1)></div>", res);
+    assert_eq!("<div onloadalert(1)></div>", res);
+}
+
+#[test]
+fn test_attribute_name_injection() {
+    let res = div()
+        .attr("x onmouseover=alert(1) y", "v")
+        .attr("a\"b'c<d>e/f", "v")
+        .attr("\ttab\nnew\0line", "v")
+        .render();
+    assert_eq!(
+        "<div xonmouseoveralert(1)y=\"v\" abcdef=\"v\" tabnewline=\"v\"></div>",
+        res
+    );
+
+    let res = div()
+        .attr_int("><script", 1)
+        .attr_fmt("a b", format_args!("{}", 2))
+        .render();
+    assert_eq!("<div script=\"1\" ab=\"2\"></div>", res);
+}
+
+#[test]
+fn test_attribute_name_empty_after_validation() {
+    let res = div()
+        .attr("", "x")
+        .attr(" = ", "y")
+        .flag("\"")
+        .attr_int("/", 1)
+        .id("a")
+        .render();
+    assert_eq!("<div id=\"a\"></div>", res);
+}
+
+#[test]
+fn test_attribute_name_validation_off() {
+    let res = RenderContext::new()
+        .mode(RenderMode::Standard)
+        .validate_attribute_names(false)
+        .scope(|| div().flag("onload=alert(1)").attr("A_b c", "v").render());
+    assert_eq!("<div onload=alert(1) a-b c=\"v\"></div>", res);
 }
 
 #[test]
@@ -436,7 +476,7 @@ fn test_into_string() {
 #[test]
 fn test_layout_wrapping() {
     let layout = |content| div().id("layout").child(content);
-    let res = div().text("Content").map(|n| layout(n)).render();
+    let res = div().text("Content").map(layout).render();
     assert_eq!("<div id=\"layout\"><div>Content</div></div>", res);
 }
 
@@ -497,3 +537,402 @@ fn test_mixed_table_and_ul() {
         res
     );
 }
+
+#[test]
+fn test_key() {
+    let res = li().key("item-1").text("One").render();
+    assert_eq!("<li id=\"item-1\">One</li>", res);
+
+    let res = RenderContext::new()
+        .key_attribute("data-key")
+        .scope(|| li().key("item-1").text("One").render());
+    assert_eq!("<li data-key=\"item-1\">One</li>", res);
+}
+
+#[test]
+fn test_children_keyed() {
+    let items = [(3, "Three"), (1, "One")];
+
+    let res = ul()
+        .class("list")
+        .children_keyed(
+            items,
+            |(id, _)| format!("todo-{}", id),
+            |(_, label)| li().class("todo").child(span().text(label)),
+        )
+        .render();
+
+    assert_eq!(
+        "<ul class=\"list\"><li id=\"todo-3\" class=\"todo\"><span>Three</span></li><li id=\"todo-1\" class=\"todo\"><span>One</span></li></ul>",
+        res
+    );
+
+    let res = RenderContext::new().key_attribute("data-key").scope(|| {
+        div()
+            .children_keyed(["a\"b"], |k| *k, |_| img().src("/x.png"))
+            .render()
+    });
+    assert_eq!(
+        "<div><img data-key=\"a&quot;b\" src=\"/x.png\" /></div>",
+        res
+    );
+}
+
+#[test]
+fn test_child_with() {
+    let res = table()
+        .class("grid")
+        .child_with(|w| {
+            w.tbody(|tbody| {
+                tbody.child_with(|w| {
+                    for row in 1..=2 {
+                        w.tr(|tr| {
+                            tr.map_when(row % 2 == 0, |n| n.class("even"))
+                                .child_with(|w| {
+                                    for col in 1..=2 {
+                                        w.td(|td| td.text(format!("{}x{}", row, col)));
+                                    }
+                                })
+                        });
+                    }
+                })
+            });
+        })
+        .render();
+
+    assert_eq!(
+        "<table class=\"grid\"><tbody><tr><td>1x1</td><td>1x2</td></tr><tr class=\"even\"><td>2x1</td><td>2x2</td></tr></tbody></table>",
+        res
+    );
+}
+
+#[test]
+fn test_child_with_mixed_children() {
+    let res = RenderContext::new().nonce("n").scope(|| {
+        div()
+            .child(span().text("a"))
+            .child_with(|w| {
+                w.img(|img| img.src("/x.png"))
+                    .child(b().text("b"))
+                    .script(|script| script.raw("x()"))
+                    .element(|p: Node<Paragraph, Open>| p);
+            })
+            .render()
+    });
+
+    assert_eq!(
+        "<div><span>a</span><img src=\"/x.png\" /><b>b</b><script nonce=\"n\">x()</script><p></p></div>",
+        res
+    );
+}
+
+#[test]
+fn test_small_elements_grow() {
+    let text = "lorem ipsum ".repeat(20);
+    let res = td()
+        .class("a-class-name-longer-than-the-initial-buffer")
+        .text(&text)
+        .render();
+
+    assert_eq!(
+        format!(
+            "<td class=\"a-class-name-longer-than-the-initial-buffer\">{}</td>",
+            text
+        ),
+        res
+    );
+}
+
+struct Bytes(&'static [u8]);
+
+impl Renderable for Bytes {
+    fn render_into(self, buf: &mut Vec<u8>) {
+        buf.clear();
+        buf.extend_from_slice(self.0);
+    }
+
+    fn render(self) -> String {
+        String::from_utf8_lossy(self.0).into_owned()
+    }
+}
+
+#[test]
+fn test_foreign_renderable() {
+    let res = div().child(Bytes("<b>ü</b>".as_bytes())).render();
+
+    assert_eq!("<div><b>ü</b></div>", res);
+}
+
+#[test]
+#[should_panic(expected = "Renderable wrote invalid UTF-8")]
+fn test_foreign_renderable_invalid_utf8() {
+    div().child(Bytes(&[b'a', 0xc3])).render();
+}
+
+#[test]
+fn test_with_buffer() {
+    let res = Node::<Div, Open>::with_buffer("div", "<p>ü</p>".as_bytes().to_vec())
+        .text("after")
+        .render();
+
+    assert_eq!("<p>ü</p><div>after</div>", res);
+}
+
+#[test]
+#[should_panic(expected = "buffer handed to a node isn't valid UTF-8")]
+fn test_with_buffer_invalid_utf8() {
+    Node::<Div, Open>::with_buffer("div", vec![0xff, 0xfe]).render();
+}
+
+#[test]
+#[should_panic(expected = "buffer handed to a node isn't valid UTF-8")]
+fn test_with_buffer_self_closing_invalid_utf8() {
+    Node::<Hr, Void>::with_buffer_self_closing("hr", vec![b'a', 0xc3]).render();
+}
+
+#[test]
+fn test_numeric_attributes() {
+    let res = div()
+        .attr_int("data_count", 42u64)
+        .attr_int("data-offset", -7)
+        .attr_float("data-ratio", 0.25)
+        .attr_float("data-small", 1.5f32)
+        .tabindex(-1)
+        .render();
+
+    assert_eq!(
+        "<div data-count=\"42\" data-offset=\"-7\" data-ratio=\"0.25\" data-small=\"1.5\" tabindex=\"-1\"></div>",
+        res
+    );
+}
+
+#[test]
+fn test_text_fmt() {
+    let (row, col) = (3, 4);
+    let res = tr()
+        .child(td().text_fmt(format_args!("{}x{}", row, col)))
+        .child(td().text_fmt(format_args!("{} < {}", "<a>", col)))
+        .child(td().text_fmt(format_args!("plain & simple")))
+        .render();
+
+    assert_eq!(
+        "<tr><td>3x4</td><td>&lt;a&gt; &lt; 4</td><td>plain &amp; simple</td></tr>",
+        res
+    );
+}
+
+#[test]
+fn test_attr_fmt() {
+    let id = 7;
+    let res = a()
+        .attr_fmt("href", format_args!("/users/{}?tab={}", id, "\"posts\""))
+        .text_fmt(format_args!("User #{}", id))
+        .render();
+
+    assert_eq!(
+        "<a href=\"/users/7?tab=&quot;posts&quot;\">User #7</a>",
+        res
+    );
+}
+
+#[test]
+fn test_heading_levels() {
+    let res = section()
+        .child(h(HeadingLevel::H2).class("title").text("Section"))
+        .child(h(HeadingLevel::H2.next()).text("Subsection"))
+        .render();
+
+    assert_eq!(
+        "<section><h2 class=\"title\">Section</h2><h3>Subsection</h3></section>",
+        res
+    );
+
+    assert_eq!(HeadingLevel::H1, HeadingLevel::new(0));
+    assert_eq!(HeadingLevel::H4, HeadingLevel::from(4));
+    assert_eq!(HeadingLevel::H6, HeadingLevel::H6.next());
+    assert_eq!(5, HeadingLevel::H5.get());
+}
+
+#[test]
+fn test_buffer_introspection() {
+    let node = div().class("a").child(p().text("Hi"));
+    let len = node.len();
+    assert_eq!(b"<div class=\"a\"><p>Hi</p>", node.as_bytes());
+    assert!(!node.is_empty());
+    let bytes = node.into_bytes();
+    assert_eq!(len, bytes.len());
+    assert_eq!(b"<div class=\"a\"><p>Hi</p></div>", &bytes[..]);
+
+    let node = img().src("a.png");
+    assert_eq!(node.len(), node.render().len());
+
+    let empty = fragment();
+    assert!(empty.is_empty());
+    let frag = fragment().text("a & b");
+    assert_eq!(9, frag.len());
+    assert_eq!(b"a &amp; b", frag.as_bytes());
+    assert_eq!(b"a &amp; b".to_vec(), frag.into_bytes());
+}
+
+#[test]
+fn test_render_fmt() {
+    let mut out = String::from("<!-- header -->");
+    p().text("a < b").render_fmt(&mut out).unwrap();
+    fragment().child(hr()).render_fmt(&mut out).unwrap();
+
+    assert_eq!("<!-- header --><p>a &lt; b</p><hr />", out);
+}
+
+#[test]
+fn test_empty() {
+    assert_eq!("", empty().render());
+    assert_eq!("<div></div>", div().child(empty()).render());
+
+    let items = ["a", "b"].map(|item| {
+        if item == "a" {
+            li().text(item).into_any()
+        } else {
+            empty().into_any()
+        }
+    });
+    assert_eq!(
+        "<ul><li>a</li></ul>",
+        ul().children(items, |item| item).render()
+    );
+}
+
+#[test]
+fn test_either() {
+    let cell = |value: Option<u32>| {
+        td().child(either(
+            value.is_some(),
+            || strong().text(value.unwrap().to_string()),
+            || span().class("muted").text("n/a"),
+        ))
+    };
+
+    assert_eq!("<td><strong>3</strong></td>", cell(Some(3)).render());
+    assert_eq!(
+        "<td><span class=\"muted\">n/a</span></td>",
+        cell(None).render()
+    );
+
+    let items: Vec<Either<_, _>> = vec![Either::Left(li().text("a")), Either::Right(empty())];
+    assert_eq!(
+        "<ul><li>a</li></ul>",
+        ul().children(items, |item| item).render()
+    );
+}
+
+#[test]
+fn test_iframe_srcdoc() {
+    let preview = html().child(
+        body().child(
+            p().title("\"quoted\"")
+                .text("1 < 2 & 'single'")
+                .child(script().raw("if (a && b) {}")),
+        ),
+    );
+    let res = iframe()
+        .sandbox("allow-scripts")
+        .title("Preview")
+        .srcdoc(preview)
+        .render();
+
+    assert_eq!(
+        "<iframe sandbox=\"allow-scripts\" title=\"Preview\" srcdoc=\"&lt;!DOCTYPE html&gt;&lt;html&gt;&lt;body&gt;\
+         &lt;p title=&quot;&amp;quot;quoted&amp;quot;&quot;&gt;1 &amp;lt; 2 &amp;amp; &amp;#39;single&amp;#39;\
+         &lt;script&gt;if (a &amp;&amp; b) {}&lt;/script&gt;&lt;/p&gt;&lt;/body&gt;&lt;/html&gt;\"></iframe>",
+        res
+    );
+
+    let tree = Tree::parse(&res);
+    let mut srcdoc = String::new();
+    tree.walk(|element| srcdoc = element.attr("srcdoc").unwrap_or_default().to_string());
+    assert!(srcdoc.starts_with("<!DOCTYPE html><html><body><p title=\"&quot;quoted&quot;\">"));
+}
+
+#[test]
+fn test_finished_nodes_compare_by_output() {
+    assert_eq!(p().text("Hi"), p().text("Hi"));
+    assert_ne!(p().text("Hi"), p().text("Ho"));
+    assert_ne!(div().close(), span().close());
+    assert_eq!(hr(), hr());
+    assert_ne!(hr().class("a"), hr());
+
+    assert_eq!(p().text("a < b"), "<p>a &lt; b</p>");
+    assert_ne!(p().text("Hi"), "<p>Hi</p>x");
+    assert_ne!(p().text("Hi"), "<p>Hi</div>");
+    assert_eq!(hr().class("a"), "<hr class=\"a\" />");
+}
+
+#[test]
+fn test_fragments_compare_by_output() {
+    let a = fragment().text("a").child(hr());
+    assert_eq!(a, "a<hr />");
+    assert_eq!(a, fragment().raw("a<hr />"));
+    assert_ne!(a, fragment());
+}
+
+#[test]
+fn test_node_debug_shows_markup() {
+    assert_eq!("Node(\"<p>Hi</p>\")", format!("{:?}", p().text("Hi")));
+    assert_eq!("Node(\"<hr />\")", format!("{:?}", hr()));
+    assert_eq!("Fragment(\"x\")", format!("{:?}", fragment().text("x")));
+}
+
+#[test]
+fn test_void_compare_follows_void_style() {
+    let res = RenderContext::new()
+        .void_style(VoidStyle::Html5)
+        .scope(|| hr() == "<hr>");
+    assert!(res);
+}
+
+#[test]
+fn test_escaped_text() {
+    let name = EscapedText::new("<Tom & \"Jerry\">");
+    assert_eq!("&lt;Tom &amp; &quot;Jerry&quot;&gt;", name.as_str());
+    assert_eq!(
+        "<b>&lt;Tom &amp; &quot;Jerry&quot;&gt;</b>",
+        b().child(&name).render()
+    );
+    assert_eq!(name.as_str(), name.clone().render());
+}
+
+#[test]
+fn test_escaped_text_uses_context_at_creation() {
+    let name = RenderContext::new()
+        .escape_non_ascii(true)
+        .scope(|| EscapedText::new("Zoë"));
+    assert_eq!("<p>Zo&#235;</p>", p().child(name).render());
+}
+
+#[test]
+fn test_repeat() {
+    assert_eq!(
+        "<ul><li>0</li><li>1</li><li>2</li></ul>",
+        ul().child(repeat(3, |i| li().text(i.to_string()))).render()
+    );
+    assert_eq!("", repeat(0, |_| div()).render());
+    assert_eq!("<hr /><hr />", repeat(2, |_| hr()).render());
+}
+
+#[test]
+fn test_repeat_child() {
+    assert_eq!(
+        "<div class=\"grid\"><div class=\"skeleton\"></div><div class=\"skeleton\"></div></div>",
+        div()
+            .class("grid")
+            .repeat_child(2, |_| div().class("skeleton"))
+            .render()
+    );
+    assert_eq!(
+        "<p>a<i></i><i></i></p>",
+        p().text("a").repeat_child(2, |_| i()).render()
+    );
+    assert_eq!(
+        "<b>*</b><b>*</b>",
+        fragment().repeat_child(2, |_| b().text("*")).render()
+    );
+}
@@ -0,0 +1,25 @@
+use lira::prelude::*;
+
+#[test]
+fn test_ws_message() {
+    let res = ws_message()
+        .fragment(div().id("status").text("Connected"))
+        .fragment(span().id("count").text("12"))
+        .swap(OobSwap::AfterBegin, "feed", article().text("Newest post"))
+        .swap(OobSwap::Delete, "toast", fragment())
+        .render();
+
+    assert_eq!(
+        "<div id=\"status\">Connected</div><span id=\"count\">12</span>\
+         <div hx-swap-oob=\"afterbegin:#feed\"><article>Newest post</article></div>\
+         <div hx-swap-oob=\"delete:#toast\"></div>",
+        res
+    );
+}
+
+#[test]
+fn test_ws_message_empty() {
+    assert!(ws_message().is_empty());
+    assert!(!ws_message().fragment(p().text("x")).is_empty());
+    assert_eq!("", ws_message().render());
+}
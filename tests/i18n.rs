@@ -0,0 +1,52 @@
+#![cfg(feature = "fluent")]
+
+use lira::prelude::*;
+use unic_langid::LanguageIdentifier;
+
+fn bundle() -> Bundle {
+    let lang: LanguageIdentifier = "de-DE".parse().unwrap();
+    let mut bundle = Bundle::new_concurrent(vec![lang]);
+    bundle.set_use_isolating(false);
+
+    let resource = FluentResource::try_new(
+        "greeting = Hallo, Welt!\nwelcome = Willkommen, { $name }!\nwarning = <b>Achtung</b>\n"
+            .to_string(),
+    )
+    .unwrap();
+    bundle.add_resource(resource).unwrap();
+    bundle
+}
+
+#[test]
+fn test_translated_text() {
+    let res = RenderContext::new()
+        .bundle(bundle())
+        .scope(|| h1().t("greeting").render());
+
+    assert_eq!("<h1>Hallo, Welt!</h1>", res);
+}
+
+#[test]
+fn test_translated_text_with_args() {
+    let mut args = FluentArgs::new();
+    args.set("name", "Chris");
+
+    let res = RenderContext::new().bundle(bundle()).scope(|| {
+        div()
+            .attr("title", t("greeting", None))
+            .t_with("welcome", &args)
+            .render()
+    });
+
+    assert_eq!("<div title=\"Hallo, Welt!\">Willkommen, Chris!</div>", res);
+}
+
+#[test]
+fn test_translations_are_escaped_and_fall_back_to_key() {
+    let res = RenderContext::new()
+        .bundle(bundle())
+        .scope(|| p().t("warning").text(" ").t("missing-key").render());
+    assert_eq!("<p>&lt;b&gt;Achtung&lt;/b&gt; missing-key</p>", res);
+
+    assert_eq!("<p>greeting</p>", p().t("greeting").render());
+}
@@ -0,0 +1,56 @@
+use lira::prelude::*;
+
+#[test]
+fn test_ids_per_render_context() {
+    let render = || {
+        RenderContext::new().scope(|| {
+            let fields = IdGen::new("field");
+            let hints = IdGen::new("hint");
+            vec![fields.next(), hints.next(), fields.next()]
+        })
+    };
+
+    let expected = vec![Id::new("field-1"), Id::new("hint-2"), Id::new("field-3")];
+    assert_eq!(expected, render());
+    assert_eq!(expected, render());
+}
+
+#[test]
+fn test_ids_reused_context() {
+    let ctx = RenderContext::new().nonce("n");
+    let render = || ctx.clone().scope(|| unique_id("m"));
+
+    assert_eq!(Id::new("m-1"), render());
+    assert_eq!(Id::new("m-1"), render());
+}
+
+#[test]
+fn test_ids_without_context_are_unique() {
+    let a = unique_id("x");
+    let b = unique_id("x");
+
+    assert_ne!(a, b);
+    assert!(a.as_str().starts_with("x-"));
+}
+
+#[test]
+fn test_id_references() {
+    let res = RenderContext::new().scope(|| {
+        let hint = unique_id("hint");
+        let title = Id::new("title");
+
+        div()
+            .aria_labelledby(&title)
+            .child(h2().id(&title).text("Title"))
+            .child(input().name("q").aria_describedby(&hint))
+            .child(p().id(&hint).text("Search"))
+            .child(a().href_fragment(&title).text("Top"))
+            .render()
+    });
+
+    assert_eq!(
+        "<div aria-labelledby=\"title\"><h2 id=\"title\">Title</h2><input name=\"q\" aria-describedby=\"hint-1\" />\
+         <p id=\"hint-1\">Search</p><a href=\"#title\">Top</a></div>",
+        res
+    );
+}
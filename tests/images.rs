@@ -0,0 +1,55 @@
+use lira::prelude::*;
+
+#[test]
+fn test_responsive_img_defaults() {
+    let res = responsive_img("/photos/cat.png", "A cat").render();
+
+    assert_eq!(
+        "<picture><source type=\"image/avif\" srcset=\"/photos/cat.avif\" />\
+         <source type=\"image/webp\" srcset=\"/photos/cat.webp\" />\
+         <img src=\"/photos/cat.png\" alt=\"A cat\" loading=\"lazy\" decoding=\"async\" /></picture>",
+        res
+    );
+}
+
+#[test]
+fn test_responsive_img_widths_and_sizes() {
+    let res = responsive_img("/img/hero.jpg?v=3", "Hero")
+        .widths([400, 800])
+        .formats([ImageFormat::Avif])
+        .sizes("(max-width: 600px) 100vw, 50vw")
+        .size(800, 450)
+        .eager()
+        .render();
+
+    assert_eq!(
+        "<picture><source type=\"image/avif\" srcset=\"/img/hero-400w.avif?v=3 400w, /img/hero-800w.avif?v=3 800w\" \
+         sizes=\"(max-width: 600px) 100vw, 50vw\" />\
+         <img src=\"/img/hero.jpg?v=3\" srcset=\"/img/hero-400w.jpg?v=3 400w, /img/hero-800w.jpg?v=3 800w\" \
+         sizes=\"(max-width: 600px) 100vw, 50vw\" alt=\"Hero\" width=\"800\" height=\"450\" /></picture>",
+        res
+    );
+}
+
+#[test]
+fn test_responsive_img_url_with() {
+    let res = responsive_img("cat", "Cat")
+        .widths([100])
+        .formats([ImageFormat::Webp])
+        .url_with(|src, width, format| {
+            format!(
+                "/resize?src={}&w={}&f={}",
+                src,
+                width.unwrap_or_default(),
+                format.map_or("orig", ImageFormat::extension)
+            )
+        })
+        .render();
+
+    assert_eq!(
+        "<picture><source type=\"image/webp\" srcset=\"/resize?src=cat&amp;w=100&amp;f=webp 100w\" />\
+         <img src=\"cat\" srcset=\"/resize?src=cat&amp;w=100&amp;f=orig 100w\" alt=\"Cat\" loading=\"lazy\" \
+         decoding=\"async\" /></picture>",
+        res
+    );
+}
@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+
+use lira::prelude::*;
+
+#[test]
+fn test_dl_of() {
+    let settings = BTreeMap::from([("Theme", "Dark"), ("Language", "<en>")]);
+
+    assert_eq!(
+        "<dl><dt>Language</dt><dd>&lt;en&gt;</dd><dt>Theme</dt><dd>Dark</dd></dl>",
+        dl_of(settings).render()
+    );
+}
+
+#[test]
+fn test_entries_of() {
+    assert_eq!(
+        "<dl class=\"meta\"><dt>Size</dt><dd>42</dd></dl>",
+        dl().class("meta")
+            .entries_of(vec![("Size".to_string(), 42.to_string())])
+            .render()
+    );
+    assert_eq!("<dl></dl>", dl_of(Vec::<(&str, &str)>::new()).render());
+}
+
+#[test]
+fn test_ul_of() {
+    assert_eq!(
+        "<ul><li>1 &lt; 2</li><li>3</li></ul>",
+        ul_of(["1 < 2", "3"], |item| fragment().text(item)).render()
+    );
+    assert_eq!(
+        "<ul></ul>",
+        ul_of(Vec::<u32>::new(), |n| fragment().text(n.to_string())).render()
+    );
+}
+
+#[test]
+fn test_items_of() {
+    assert_eq!(
+        "<ol class=\"steps\"><li><span>1</span></li><li><span>2</span></li></ol>",
+        ol().class("steps")
+            .items_of(1..=2, |n| span().text(n.to_string()))
+            .render()
+    );
+    assert_eq!(
+        "<ul id=\"nav\"><li><a href=\"/\">Home</a></li></ul>",
+        ul().id("nav")
+            .items_of([("/", "Home")], |(href, label)| a().href(href).text(label))
+            .render()
+    );
+}
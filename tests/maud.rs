@@ -0,0 +1,39 @@
+#![cfg(feature = "maud")]
+
+use lira::prelude::*;
+use maud::{PreEscaped, html};
+
+#[test]
+fn test_maud_markup_as_child() {
+    let markup = html! { span { "a < b" } };
+    let res = div().child(markup).render();
+    assert_eq!("<div><span>a &lt; b</span></div>", res);
+}
+
+#[test]
+fn test_pre_escaped_str() {
+    let res = div().child(PreEscaped("<b>x</b>")).render();
+    assert_eq!("<div><b>x</b></div>", res);
+}
+
+#[test]
+fn test_lira_nodes_in_maud() {
+    let content = p().text("a & b");
+    let rule = hr().class("sep");
+    let items = fragment().child(li().text("1")).child(li().text("2"));
+
+    let page = html! {
+        main { (content) (rule) ul { (items) } }
+    };
+    assert_eq!(
+        "<main><p>a &amp; b</p><hr class=\"sep\" /><ul><li>1</li><li>2</li></ul></main>",
+        page.into_string()
+    );
+}
+
+#[test]
+fn test_mixed_round_trip() {
+    let inner = html! { (span().text("lira")) " inside maud" };
+    let res = p().child(inner).render();
+    assert_eq!("<p><span>lira</span> inside maud</p>", res);
+}
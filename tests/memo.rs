@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use lira::prelude::*;
+
+fn card(calls: &AtomicUsize, name: &str) -> Memo {
+    memo(format!("card:{}", name), Duration::from_secs(60), || {
+        calls.fetch_add(1, Ordering::Relaxed);
+        article().child(h2().text(name))
+    })
+}
+
+#[test]
+fn test_memo_reuses_cached_html() {
+    let cache = Arc::new(MemoryCache::new());
+    let calls = AtomicUsize::new(0);
+
+    for _ in 0..3 {
+        let res = RenderContext::new()
+            .fragment_cache(cache.clone())
+            .scope(|| {
+                div()
+                    .child(card(&calls, "Tea"))
+                    .child(card(&calls, "Cake"))
+                    .render()
+            });
+
+        assert_eq!(
+            "<div><article><h2>Tea</h2></article><article><h2>Cake</h2></article></div>",
+            res
+        );
+    }
+
+    assert_eq!(2, calls.load(Ordering::Relaxed));
+    assert_eq!(2, cache.len());
+
+    cache.remove("card:Tea");
+    RenderContext::new()
+        .fragment_cache(cache.clone())
+        .scope(|| card(&calls, "Tea").render());
+    assert_eq!(3, calls.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_memo_expires() {
+    let cache = Arc::new(MemoryCache::new());
+    let calls = AtomicUsize::new(0);
+
+    for _ in 0..2 {
+        RenderContext::new()
+            .fragment_cache(cache.clone())
+            .scope(|| {
+                memo("clock", Duration::ZERO, || {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    span().text("now")
+                })
+                .render()
+            });
+    }
+
+    assert_eq!(2, calls.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_memo_without_cache() {
+    let calls = AtomicUsize::new(0);
+
+    assert_eq!(
+        "<article><h2>Tea</h2></article>",
+        card(&calls, "Tea").render()
+    );
+    assert_eq!(
+        "<article><h2>Tea</h2></article>",
+        card(&calls, "Tea").render()
+    );
+    assert_eq!(2, calls.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_memo_skips_per_request_partials() {
+    let cache = Arc::new(MemoryCache::new());
+
+    let render = |nonce: &str| {
+        RenderContext::new()
+            .nonce(nonce)
+            .fragment_cache(cache.clone())
+            .scope(|| {
+                div()
+                    .child(memo("widget", Duration::from_secs(60), || {
+                        script().raw("init()")
+                    }))
+                    .child(memo("field", Duration::from_secs(60), || {
+                        let id = unique_id("hint");
+                        p().id(&id).text("Hint")
+                    }))
+                    .render()
+            })
+    };
+
+    assert_eq!(
+        "<div><script nonce=\"first\">init()</script><p id=\"hint-1\">Hint</p></div>",
+        render("first")
+    );
+    assert_eq!(
+        "<div><script nonce=\"second\">init()</script><p id=\"hint-1\">Hint</p></div>",
+        render("second")
+    );
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,87 @@
+use lira::minify::minify;
+use lira::prelude::*;
+
+#[test]
+fn test_minify_whitespace() {
+    let html = "<div>\n    <p>\n        Hello   <b>big</b>\n        world\n    </p>\n</div>";
+    assert_eq!("<div><p>Hello <b>big</b> world</p></div>", minify(html));
+}
+
+#[test]
+fn test_minify_inline_whitespace_kept() {
+    assert_eq!(
+        "<span>a</span> <span>b</span>",
+        minify("<span>a</span>\n\n<span>b</span>")
+    );
+}
+
+#[test]
+fn test_minify_comments() {
+    let html = "<div><!-- note --><!--[if mso]><table><![endif]--><p>x</p></div>";
+    assert_eq!(
+        "<div><!--[if mso]><table><![endif]--><p>x</p></div>",
+        minify(html)
+    );
+}
+
+#[test]
+fn test_minify_attributes() {
+    let html =
+        "<input type=\"text\" value=\"a b\" data-x=\"\" required /><a href=\"/?a=1&amp;b=2\">x</a>";
+    assert_eq!(
+        "<input type=text value=\"a b\" data-x=\"\" required><a href=\"/?a=1&amp;b=2\">x</a>",
+        minify(html)
+    );
+}
+
+#[test]
+fn test_minify_preformatted() {
+    let html = "<pre>\n  a\n    b\n</pre><script>if (a  <  b) {\n  go();\n}</script><textarea>  x  </textarea>";
+    assert_eq!(html, minify(html));
+}
+
+#[test]
+fn test_minify_context() {
+    let page = || div().raw("\n  <p>\n    Hi\n  </p>\n");
+
+    assert_eq!("<div>\n  <p>\n    Hi\n  </p>\n</div>", page().render());
+
+    let res = RenderContext::new().minify(true).scope(|| page().render());
+    assert_eq!("<div><p>Hi</p></div>", res);
+}
+
+#[test]
+fn test_minify_keeps_character_references() {
+    assert_eq!(
+        "<p title='say \"hi\" &hearts;'>&hearts; &nope; &lt;b&gt;</p>",
+        minify("<p title='say \"hi\" &hearts;'>\n  &hearts;  &nope;   &lt;b&gt;\n</p>")
+    );
+
+    let res = RenderContext::new()
+        .minify(true)
+        .scope(|| div().raw("&hearts;  x").render());
+    assert_eq!("<div>&hearts; x</div>", res);
+}
+
+#[test]
+fn test_minify_keeps_spaces_around_inline_elements() {
+    for (html, minified) in [
+        (
+            "<p>Hello <script>track()</script> world</p>",
+            "<p>Hello <script>track()</script>world</p>",
+        ),
+        (
+            "<p>Pick <select>\n  <option>a</option>\n</select> now</p>",
+            "<p>Pick <select> <option>a</option> </select> now</p>",
+        ),
+        ("<p>Hello <!-- note --> world</p>", "<p>Hello world</p>"),
+        ("<p>a <br>\n b</p>", "<p>a <br> b</p>"),
+        (
+            "<head>\n  <meta charset=\"utf-8\">\n  <link rel=\"icon\" href=\"/i.png\">\n  <title>x</title>\n</head>",
+            "<head><meta charset=utf-8><link rel=icon href=/i.png><title>x</title></head>",
+        ),
+    ] {
+        assert_eq!(minified, minify(html));
+        assert_eq!(minified, minify(minified));
+    }
+}
@@ -0,0 +1,75 @@
+use lira::prelude::*;
+
+#[test]
+fn test_breadcrumbs() {
+    let res = breadcrumbs(vec![
+        ("Home".to_string(), "/"),
+        ("Docs & Guides".to_string(), "/docs"),
+        ("Install".to_string(), "/docs/install"),
+    ])
+    .label("You are here")
+    .render();
+
+    assert_eq!(
+        "<nav aria-label=\"You are here\"><ol><li><a href=\"/\">Home</a></li><li><a href=\"/docs\">Docs &amp; Guides</a></li><li><a href=\"/docs/install\" aria-current=\"page\">Install</a></li></ol></nav>",
+        res
+    );
+}
+
+#[test]
+fn test_breadcrumbs_json_ld() {
+    let res = div()
+        .child(
+            breadcrumbs([
+                ("Home", "https://example.com/"),
+                ("</script>", "https://example.com/x"),
+            ])
+            .json_ld(),
+        )
+        .render();
+
+    assert_eq!(
+        "<div><nav aria-label=\"Breadcrumb\"><ol><li><a href=\"https://example.com/\">Home</a></li><li><a href=\"https://example.com/x\" aria-current=\"page\">&lt;/script&gt;</a></li></ol></nav><script type=\"application/ld+json\">{\"@context\":\"https://schema.org\",\"@type\":\"BreadcrumbList\",\"itemListElement\":[{\"@type\":\"ListItem\",\"position\":1,\"name\":\"Home\",\"item\":\"https://example.com/\"},{\"@type\":\"ListItem\",\"position\":2,\"name\":\"\\u003c/script\\u003e\",\"item\":\"https://example.com/x\"}]}</script></div>",
+        res
+    );
+}
+
+fn menu() -> Vec<MenuItem> {
+    vec![
+        MenuItem::link("Home", "/"),
+        MenuItem::link("Blog", "/blog"),
+        MenuItem::group(
+            "Docs",
+            vec![
+                MenuItem::link("Install", "/docs/install"),
+                MenuItem::group("API", vec![MenuItem::link("Nodes", "/docs/api/nodes")]),
+            ],
+        ),
+    ]
+}
+
+#[test]
+fn test_nav_menu() {
+    let res = nav_menu(menu(), "/docs/api/nodes/")
+        .label("Main")
+        .active_class("is-active")
+        .render();
+
+    assert_eq!(
+        "<nav aria-label=\"Main\"><ul><li><a href=\"/\">Home</a></li><li><a href=\"/blog\">Blog</a></li><li><details open><summary>Docs</summary><ul><li><a href=\"/docs/install\">Install</a></li><li><details open><summary>API</summary><ul><li><a href=\"/docs/api/nodes\" aria-current=\"page\" class=\"is-active\">Nodes</a></li></ul></details></li></ul></details></li></ul></nav>",
+        res
+    );
+}
+
+#[test]
+fn test_nav_menu_match_prefix() {
+    let exact = nav_menu(menu(), "/blog/hello?ref=feed").render();
+    assert!(!exact.contains("aria-current"));
+
+    let prefix = nav_menu(menu(), "/blog/hello?ref=feed")
+        .match_prefix()
+        .render();
+    assert!(prefix.contains("<a href=\"/blog\" aria-current=\"page\">Blog</a>"));
+    assert!(prefix.contains("<a href=\"/\">Home</a>"));
+    assert!(!prefix.contains("<details open>"));
+}
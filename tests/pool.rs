@@ -0,0 +1,79 @@
+use lira::prelude::*;
+
+fn page() -> String {
+    div()
+        .class("list")
+        .child(ul().children(1..=3, |i| li().child(a().href("#").text(i.to_string()))))
+        .child(hr())
+        .render()
+}
+
+#[test]
+fn test_pool_renders_the_same() {
+    let mut pool = BufferPool::new();
+
+    assert_eq!(page(), pool.scope(page));
+    assert_eq!(page(), pool.scope(page));
+}
+
+#[test]
+fn test_pool_keeps_buffers() {
+    let mut pool = BufferPool::new();
+    assert!(pool.is_empty());
+
+    pool.scope(page);
+    let spare = pool.len();
+    assert!(spare > 0);
+
+    // a warmed up pool has enough buffers for the same page again
+    pool.scope(page);
+    assert_eq!(spare, pool.len());
+}
+
+#[test]
+fn test_pool_with_buffers() {
+    let mut pool = BufferPool::with_buffers(4, 256);
+    assert_eq!(4, pool.len());
+
+    // the buffer of the root turns into the rendered string, the one of the child goes back
+    pool.scope(|| div().child(span().text("x")).render());
+    assert_eq!(3, pool.len());
+}
+
+#[test]
+fn test_pool_clear() {
+    let mut pool = BufferPool::new();
+    pool.scope(page);
+    assert!(!pool.is_empty());
+
+    pool.clear();
+    assert!(pool.is_empty());
+    assert_eq!(page(), pool.scope(page));
+}
+
+#[test]
+fn test_pool_nested_scopes() {
+    let mut outer = BufferPool::new();
+    let mut inner = BufferPool::new();
+
+    outer.scope(|| {
+        inner.scope(page);
+        page()
+    });
+
+    assert!(!outer.is_empty());
+    assert!(!inner.is_empty());
+}
+
+#[test]
+fn test_pool_with_render_context() {
+    let mut pool = BufferPool::new();
+
+    let res = pool.scope(|| {
+        RenderContext::new()
+            .nonce("abc")
+            .scope(|| div().child(script().raw("x()")).render())
+    });
+
+    assert_eq!("<div><script nonce=\"abc\">x()</script></div>", res);
+}
@@ -0,0 +1,49 @@
+mod html_only {
+    use lira::prelude::html::*;
+
+    #[test]
+    fn test_html_prelude() {
+        let res = select()
+            .child(opt().value("a").text("A"))
+            .child(option().value("b").text("B"))
+            .render();
+        assert_eq!(
+            "<select><option value=\"a\">A</option><option value=\"b\">B</option></select>",
+            res
+        );
+    }
+}
+
+mod attrs_only {
+    use lira::html5::{a, link};
+    use lira::prelude::attrs::*;
+
+    #[test]
+    fn test_attrs_prelude() {
+        let res = link().rel(Rel::Stylesheet).href("/app.css").class("theme");
+        assert_eq!(
+            "<link rel=\"stylesheet\" href=\"/app.css\" class=\"theme\" />",
+            lira::core::Renderable::render(res)
+        );
+
+        let res = a().href("/").id("home");
+        assert_eq!(
+            "<a href=\"/\" id=\"home\"></a>",
+            lira::core::Renderable::render(res)
+        );
+    }
+}
+
+mod svg_only {
+    use lira::prelude::svg::*;
+
+    #[test]
+    fn test_svg_prelude() {
+        let res = svg()
+            .class("icon")
+            .child(use_().attr("href", "#x"))
+            .render();
+        assert!(res.starts_with("<svg"));
+        assert!(res.contains("<use href=\"#x\" />"));
+    }
+}
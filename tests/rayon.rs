@@ -0,0 +1,65 @@
+#![cfg(feature = "rayon")]
+
+use lira::prelude::*;
+
+#[test]
+fn test_children_par_keeps_order() {
+    let rows: Vec<u32> = (1..=500).collect();
+
+    let res = tbody()
+        .children_par(rows.clone(), |row| tr().child(td().text(row.to_string())))
+        .render();
+    let expected = tbody()
+        .children(rows, |row| tr().child(td().text(row.to_string())))
+        .render();
+
+    assert_eq!(expected, res);
+}
+
+#[test]
+fn test_children_par_borrowed_items() {
+    let items = vec!["a", "b", "c"];
+
+    let res = ul()
+        .class("list")
+        .children_par(&items, |item| li().text(item))
+        .render();
+
+    assert_eq!(
+        "<ul class=\"list\"><li>a</li><li>b</li><li>c</li></ul>",
+        res
+    );
+}
+
+#[test]
+fn test_children_par_uses_render_context() {
+    let res = RenderContext::new().nonce("n0nce").scope(|| {
+        div()
+            .children_par(0..2, |i| script().raw(format!("f({})", i)))
+            .render()
+    });
+
+    assert_eq!(
+        "<div><script nonce=\"n0nce\">f(0)</script><script nonce=\"n0nce\">f(1)</script></div>",
+        res
+    );
+}
+
+#[test]
+fn test_children_par_ids_stay_unique() {
+    let mut ids = RenderContext::new().scope(|| {
+        let ids = std::sync::Mutex::new(Vec::new());
+        div()
+            .children_par(0..50, |_| {
+                let id = unique_id("row");
+                ids.lock().unwrap().push(id.as_str().to_string());
+                span().id(&id)
+            })
+            .render();
+        ids.into_inner().unwrap()
+    });
+    ids.sort();
+    ids.dedup();
+
+    assert_eq!(50, ids.len());
+}
@@ -0,0 +1,107 @@
+use std::io::{self, IoSlice, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+use lira::prelude::*;
+
+// accepts at most `limit` bytes per call and counts the calls
+struct Limited {
+    out: Vec<u8>,
+    limit: usize,
+    calls: usize,
+}
+
+impl Write for Limited {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_vectored(&[IoSlice::new(buf)])
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.calls += 1;
+        let mut written = 0;
+        for buf in bufs {
+            let n = buf.len().min(self.limit - written);
+            self.out.extend_from_slice(&buf[..n]);
+            written += n;
+            if written == self.limit {
+                break;
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn page() -> Segments {
+    segments()
+        .static_html("<main>")
+        .child(h1().text("Title"))
+        .text(" & ")
+        .shared_html(Arc::from("<footer>shared</footer>"))
+        .static_html("</main>")
+}
+
+#[test]
+fn test_segments_render() {
+    assert_eq!(
+        "<main><h1>Title</h1> &amp; <footer>shared</footer></main>",
+        page().render()
+    );
+    assert_eq!(page().render().len(), page().len());
+}
+
+#[test]
+fn test_dynamic_parts_share_a_buffer() {
+    let slices: Vec<usize> = page().io_slices().iter().map(|s| s.len()).collect();
+    assert_eq!(vec![6, 21, 23, 7], slices);
+}
+
+#[test]
+fn test_write_to_single_call() {
+    let mut out = Limited {
+        out: Vec::new(),
+        limit: usize::MAX,
+        calls: 0,
+    };
+    page().write_to(&mut out).unwrap();
+    assert_eq!(1, out.calls);
+    assert_eq!(page().render().as_bytes(), out.out.as_slice());
+}
+
+#[test]
+fn test_write_to_partial_writes() {
+    let mut out = Limited {
+        out: Vec::new(),
+        limit: 5,
+        calls: 0,
+    };
+    page().write_to(&mut out).unwrap();
+    assert_eq!(page().render().as_bytes(), out.out.as_slice());
+    assert_eq!(page().len().div_ceil(5), out.calls);
+}
+
+#[test]
+fn test_write_zero_fails() {
+    let mut out = Limited {
+        out: Vec::new(),
+        limit: 0,
+        calls: 0,
+    };
+    let err = page().write_to(&mut out).unwrap_err();
+    assert_eq!(io::ErrorKind::WriteZero, err.kind());
+}
+
+#[test]
+fn test_memo_segment() {
+    let cache = Arc::new(MemoryCache::new());
+    let res = RenderContext::new().fragment_cache(cache).scope(|| {
+        segments()
+            .memo(memo("nav", Duration::from_secs(60), || nav().text("x")))
+            .render()
+    });
+    assert_eq!("<nav>x</nav>", res);
+    assert!(segments().static_html("").is_empty());
+}
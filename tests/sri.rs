@@ -0,0 +1,69 @@
+use lira::prelude::*;
+use lira::sri::{Algo, Integrity, integrity_from_bytes, link_with_sri, script_with_sri};
+
+#[test]
+fn test_integrity_known_values() {
+    assert_eq!(
+        "sha256-ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=",
+        integrity_from_bytes(b"abc", Algo::Sha256)
+    );
+    assert_eq!(
+        "sha384-ywB1P0WjXou1oD1pmsZQBycsMqsO3tFjGotgWkP/W+2AhgcroefMI1i67KE0yCWn",
+        integrity_from_bytes(b"abc", Algo::Sha384)
+    );
+    assert_eq!(
+        "sha512-3a81oZNherrMQXNJriBBMRLm+k6JqX6iCp7u5ktV05ohkpkqJ0/BqDa6PCOj/uu9RU1EI2Q86A4qmslPpUyknw==",
+        integrity_from_bytes(b"abc", Algo::Sha512)
+    );
+    assert_eq!(
+        "sha384-OLBgp1GsljhM2TJ+sbHjaiH9txEUvgdDTAzHv2P24donTt6/529l+9Ua0vFImLlb",
+        integrity_from_bytes(b"", Algo::Sha384)
+    );
+}
+
+#[test]
+fn test_integrity_padding_boundaries() {
+    let data = [b'a'; 200];
+
+    assert_eq!(
+        "sha256-s1Q5pKxvCUi21vnjxq8PX1kM4g8b3nCQ73lwaG7Gc4o=",
+        integrity_from_bytes(&data[..56], Algo::Sha256)
+    );
+    assert_eq!(
+        "sha512-+pEhx7MrngFzPQNM/HjL9n+SbH7YPoIgDvhoGBlpIXYLS+/0hATfgRuVOCgnRGFnPGjQTil7DreytNYPxrVmog==",
+        integrity_from_bytes(&data[..111], Algo::Sha512)
+    );
+    assert_eq!(
+        "sha384-GH1OB8swYQPGmWe/VE0N++kEJXdZnHPDMKvAy2TGEjbV7VZe4ZEZ2MMXeaOPeR/N",
+        integrity_from_bytes(&data[..112], Algo::Sha384)
+    );
+    assert_eq!(
+        "sha256-wqkI2Y9d+Yet5BtfziEwZ++8wh7yJAISpB5UtefCiuU=",
+        integrity_from_bytes(&data, Algo::Sha256)
+    );
+}
+
+#[test]
+fn test_const_integrity() {
+    const SRI: Integrity = Integrity::new(b"console.log('hello');", Algo::Sha384);
+
+    assert_eq!(
+        "sha384-v393mDht/MNBowq0Z9UpetDvKE6u6EdCihklP1GZs66vL1YCFm1Z4Q4wJtb94rY9",
+        SRI.as_str()
+    );
+}
+
+#[test]
+fn test_elements_with_sri() {
+    let res = script_with_sri("https://cdn.example.com/app.js", b"abc").render();
+    assert_eq!(
+        "<script src=\"https://cdn.example.com/app.js\" integrity=\"sha384-ywB1P0WjXou1oD1pmsZQBycsMqsO3tFjGotgWkP/W+2AhgcroefMI1i67KE0yCWn\" crossorigin=\"anonymous\"></script>",
+        res
+    );
+
+    let res = link_with_sri("/app.css", b"abc").render();
+    assert_eq!(
+        "<link rel=\"stylesheet\" href=\"/app.css\" integrity=\"sha384-ywB1P0WjXou1oD1pmsZQBycsMqsO3tFjGotgWkP/W+2AhgcroefMI1i67KE0yCWn\" crossorigin=\"anonymous\" />",
+        res
+    );
+}
@@ -0,0 +1,29 @@
+use lira::prelude::*;
+
+#[test]
+fn test_sse_multiline_data() {
+    let event = sse(pre().text("line 1\nline 2\r\nline 3\rline 4"));
+
+    assert_eq!(
+        "data: <pre>line 1\ndata: line 2\ndata: line 3\ndata: line 4</pre>\n\n",
+        event.to_string()
+    );
+}
+
+#[test]
+fn test_sse_fields() {
+    let event = sse(div().id("count").text("3"))
+        .event("update\ncount")
+        .id("7\0\r8")
+        .retry(5000);
+
+    assert_eq!(
+        "event: update count\nid: 7 8\nretry: 5000\ndata: <div id=\"count\">3</div>\n\n",
+        event.to_string()
+    );
+}
+
+#[test]
+fn test_sse_empty() {
+    assert_eq!("data: \n\n", sse(fragment()).to_string());
+}
@@ -0,0 +1,90 @@
+use std::sync::{Arc, Mutex};
+
+use lira::prelude::*;
+
+#[test]
+fn test_render_with_stats() {
+    let (html, stats) = div()
+        .child(h1().text("Title"))
+        .child(p().text("Some ").child(b().text("bold")).text(" text"))
+        .child(hr())
+        .render_with_stats();
+
+    assert_eq!(
+        RenderStats {
+            bytes: html.len(),
+            elements: 5,
+            text_nodes: 4,
+            max_depth: 3,
+        },
+        stats
+    );
+}
+
+#[test]
+fn test_stats_of() {
+    assert_eq!(RenderStats::default(), RenderStats::of(""));
+    assert_eq!(
+        RenderStats {
+            bytes: 38,
+            elements: 2,
+            text_nodes: 1,
+            max_depth: 1,
+        },
+        RenderStats::of("<!DOCTYPE html><p>Hi</p><!-- c --><hr>")
+    );
+}
+
+#[test]
+fn test_on_render_hook() {
+    let reported = Arc::new(Mutex::new(Vec::new()));
+
+    let hook = reported.clone();
+    let html = RenderContext::new()
+        .on_render(move |stats| hook.lock().unwrap().push(*stats))
+        .scope(|| div().child(span().text("a")).render());
+
+    assert_eq!(
+        vec![RenderStats {
+            bytes: html.len(),
+            elements: 2,
+            text_nodes: 1,
+            max_depth: 2,
+        }],
+        *reported.lock().unwrap()
+    );
+
+    // no hook outside of the scope
+    div().render();
+    assert_eq!(1, reported.lock().unwrap().len());
+}
+
+struct Badge(u32);
+
+impl std::fmt::Display for Badge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        span().class("badge").text(self.0.to_string()).render_fmt(f)
+    }
+}
+
+#[test]
+fn test_on_render_hook_once_per_page() {
+    let reported = Arc::new(Mutex::new(Vec::new()));
+
+    let hook = reported.clone();
+    let html = RenderContext::new()
+        .on_render(move |stats| hook.lock().unwrap().push(*stats))
+        .scope(|| {
+            document().render(|| {
+                main().raw(Badge(3).to_string()).child(memo(
+                    "sidebar",
+                    std::time::Duration::from_secs(60),
+                    || aside().raw(p().text("Popular").render()),
+                ))
+            })
+        });
+
+    let reported = reported.lock().unwrap();
+    assert_eq!(1, reported.len());
+    assert_eq!(html.len(), reported[0].bytes);
+}
@@ -0,0 +1,52 @@
+use lira::prelude::*;
+
+#[test]
+fn test_icon() {
+    assert_eq!(
+        "<svg aria-hidden=\"true\" focusable=\"false\"><use href=\"/static/sprite.svg#close\" /></svg>",
+        icon("/static/sprite.svg", "close").render()
+    );
+}
+
+#[test]
+fn test_icon_options() {
+    let res = button()
+        .child(icon("", "trash").size(24).class("icon icon-danger"))
+        .render();
+
+    assert_eq!(
+        "<button><svg aria-hidden=\"true\" focusable=\"false\" class=\"icon icon-danger\" width=\"24\" height=\"24\">\
+         <use href=\"#trash\" /></svg></button>",
+        res
+    );
+}
+
+#[test]
+fn test_sprite() {
+    let res = sprite()
+        .symbol("a", "0 0 24 24", "<path d=\"M0 0h24\"/>")
+        .symbol("b", "0 0 16 16", "<rect width=\"16\" height=\"16\"/>")
+        .render();
+
+    assert_eq!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" hidden>\
+         <symbol id=\"a\" viewbox=\"0 0 24 24\"><path d=\"M0 0h24\"/></symbol>\
+         <symbol id=\"b\" viewbox=\"0 0 16 16\"><rect width=\"16\" height=\"16\"/></symbol></svg>",
+        res
+    );
+}
+
+#[test]
+fn test_svg_elements() {
+    let res = svg()
+        .viewbox("0 0 10 10")
+        .width(10)
+        .height(10)
+        .child(use_().href("#x"))
+        .render();
+
+    assert_eq!(
+        "<svg viewbox=\"0 0 10 10\" width=\"10\" height=\"10\"><use href=\"#x\" /></svg>",
+        res
+    );
+}
@@ -0,0 +1,207 @@
+use std::borrow::Cow;
+
+use lira::prelude::*;
+use lira::table::cells;
+
+struct Product {
+    name: &'static str,
+    price: f64,
+    stock: Option<u32>,
+}
+
+impl ToTableRow for Product {
+    fn columns() -> Vec<Cow<'static, str>> {
+        vec!["Product".into(), "Price".into(), "Stock".into()]
+    }
+
+    fn cells(&self) -> Fragment {
+        cells([&self.name, &self.price, &self.stock])
+    }
+}
+
+fn products() -> Vec<Product> {
+    vec![
+        Product {
+            name: "Tea",
+            price: 3.5,
+            stock: Some(12),
+        },
+        Product {
+            name: "Cake & Co",
+            price: 4.0,
+            stock: None,
+        },
+    ]
+}
+
+#[test]
+fn test_table_of() {
+    assert_eq!(
+        "<table><thead><tr><th scope=\"col\">Product</th><th scope=\"col\">Price</th><th scope=\"col\">Stock</th></tr></thead><tbody><tr><td>Tea</td><td>3.5</td><td>12</td></tr><tr><td>Cake &amp; Co</td><td>4</td><td></td></tr></tbody></table>",
+        table_of(products()).render()
+    );
+}
+
+#[test]
+fn test_rows_of() {
+    assert_eq!(
+        "<table class=\"striped\"><thead><tr><th scope=\"col\">Product</th><th scope=\"col\">Price</th><th scope=\"col\">Stock</th></tr></thead><tbody></tbody></table>",
+        table()
+            .class("striped")
+            .rows_of(Vec::<&Product>::new())
+            .render()
+    );
+}
+
+#[test]
+fn test_data_table() {
+    let res = data_table(["Name", "Role"], vec![vec!["Ferris", "Mascot"]])
+        .caption("Team & friends")
+        .render();
+
+    assert_eq!(
+        "<table><caption>Team &amp; friends</caption><thead><tr><th scope=\"col\">Name</th>\
+         <th scope=\"col\">Role</th></tr></thead><tbody><tr><td>Ferris</td><td>Mascot</td></tr></tbody></table>",
+        res
+    );
+}
+
+#[test]
+fn test_data_table_headers() {
+    let res = RenderContext::new().scope(|| {
+        data_table(["", "Q1", "Q2"], [["Sales", "10", "12"]])
+            .row_headers()
+            .headers()
+            .render()
+    });
+
+    assert_eq!(
+        "<table><thead><tr><th scope=\"col\" id=\"col-1\"></th><th scope=\"col\" id=\"col-2\">Q1</th>\
+         <th scope=\"col\" id=\"col-3\">Q2</th></tr></thead><tbody><tr><th scope=\"row\" id=\"row-4\">Sales</th>\
+         <td headers=\"col-2 row-4\">10</td><td headers=\"col-3 row-4\">12</td></tr></tbody></table>",
+        res
+    );
+}
+
+#[test]
+fn test_csv_table() {
+    let csv = "name,quote\r\nFerris,\"says \"\"hi\"\", then\nleaves\"\nGhost\n";
+
+    assert_eq!(
+        "<table><thead><tr><th scope=\"col\">name</th><th scope=\"col\">quote</th></tr></thead><tbody><tr><td>Ferris</td><td>says &quot;hi&quot;, then\nleaves</td></tr><tr><td>Ghost</td><td></td></tr></tbody></table>",
+        csv_table(csv).render()
+    );
+}
+
+#[test]
+fn test_csv_table_empty() {
+    assert_eq!(
+        "<table><thead><tr></tr></thead><tbody></tbody></table>",
+        csv_table("").render()
+    );
+}
+
+#[test]
+fn test_format_column() {
+    let res = data_table(["Name", "Score"], [["Ferris", "10"], ["Corro", "7"]])
+        .row_headers()
+        .format_column("Name", |name| strong().text(name))
+        .format_column("Score", |score| span().class("num").text(score))
+        .format_column("Unknown", |value| span().text(value))
+        .render();
+
+    assert_eq!(
+        "<table><thead><tr><th scope=\"col\">Name</th><th scope=\"col\">Score</th></tr></thead><tbody><tr><th scope=\"row\"><strong>Ferris</strong></th><td><span class=\"num\">10</span></td></tr><tr><th scope=\"row\"><strong>Corro</strong></th><td><span class=\"num\">7</span></td></tr></tbody></table>",
+        res
+    );
+}
+
+#[test]
+fn test_format_column_escapes_text() {
+    let res = data_table(["A"], [["<b>"]])
+        .format_column("A", |value| fragment().text(value))
+        .render();
+
+    assert_eq!(
+        "<table><thead><tr><th scope=\"col\">A</th></tr></thead><tbody><tr><td>&lt;b&gt;</td></tr></tbody></table>",
+        res
+    );
+}
+
+#[cfg(feature = "serde")]
+mod serde_rows {
+    use std::collections::BTreeMap;
+
+    use lira::prelude::*;
+
+    #[derive(serde::Serialize)]
+    struct Order {
+        id: u32,
+        #[serde(rename = "customer")]
+        name: &'static str,
+        items: Vec<&'static str>,
+        note: Option<&'static str>,
+    }
+
+    #[test]
+    fn test_serialize() {
+        let orders = vec![
+            Order {
+                id: 1,
+                name: "Ferris",
+                items: vec!["tea", "cake"],
+                note: None,
+            },
+            Order {
+                id: 2,
+                name: "Corro & Co",
+                items: vec![],
+                note: Some("fragile"),
+            },
+        ];
+
+        let res = DataTable::serialize(&orders)
+            .unwrap()
+            .format_column("id", |id| a().href(format!("/orders/{}", id)).text(id))
+            .render();
+
+        assert_eq!(
+            "<table><thead><tr><th scope=\"col\">id</th><th scope=\"col\">customer</th><th scope=\"col\">items</th><th scope=\"col\">note</th></tr></thead><tbody><tr><td><a href=\"/orders/1\">1</a></td><td>Ferris</td><td>tea, cake</td><td></td></tr><tr><td><a href=\"/orders/2\">2</a></td><td>Corro &amp; Co</td><td></td><td>fragile</td></tr></tbody></table>",
+            res
+        );
+    }
+
+    #[test]
+    fn test_serialize_maps() {
+        let rows = vec![
+            BTreeMap::from([("a", "1"), ("b", "2")]),
+            BTreeMap::from([("b", "3"), ("c", "4")]),
+        ];
+
+        assert_eq!(
+            "<table><thead><tr><th scope=\"col\">a</th><th scope=\"col\">b</th></tr></thead><tbody><tr><td>1</td><td>2</td></tr><tr><td></td><td>3</td></tr></tbody></table>",
+            DataTable::serialize(rows).unwrap().render()
+        );
+    }
+
+    #[test]
+    fn test_serialize_nested() {
+        #[derive(serde::Serialize)]
+        struct Inner {
+            x: u32,
+        }
+
+        #[derive(serde::Serialize)]
+        struct Outer {
+            inner: Inner,
+        }
+
+        assert!(
+            DataTable::serialize([Outer {
+                inner: Inner { x: 1 }
+            }])
+            .is_err()
+        );
+        assert!(DataTable::serialize([1, 2]).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "tailwind")]
+
+use lira::prelude::*;
+
+#[test]
+fn test_tw_merge_later_wins() {
+    assert_eq!("p-4", tw_merge(["p-2", "p-4"]));
+    assert_eq!("h-8 w-full", tw_merge(["w-4 h-8", "w-full"]));
+    assert_eq!("bg-[#fff]", tw_merge(["bg-red-500", "bg-[#fff]"]));
+    assert_eq!(
+        "font-bold font-mono",
+        tw_merge(["font-light font-sans", "font-bold font-mono"])
+    );
+    assert_eq!("absolute", tw_merge(["relative absolute"]));
+}
+
+#[test]
+fn test_tw_merge_groups_sharing_a_prefix() {
+    assert_eq!(
+        "text-lg text-center text-blue-600",
+        tw_merge([
+            "text-sm text-left text-gray-900",
+            "text-lg text-center text-blue-600"
+        ])
+    );
+    assert_eq!(
+        "border-2 border-red-500 border-dashed",
+        tw_merge([
+            "border border-gray-200 border-solid",
+            "border-2 border-red-500 border-dashed"
+        ])
+    );
+    assert_eq!(
+        "flex flex-col flex-wrap",
+        tw_merge(["flex flex-row", "flex-col flex-wrap"])
+    );
+    assert_eq!(
+        "shadow-lg shadow-black",
+        tw_merge(["shadow shadow-red-500", "shadow-lg shadow-black"])
+    );
+}
+
+#[test]
+fn test_tw_merge_shorthands() {
+    assert_eq!("p-4", tw_merge(["px-2 pt-1", "p-4"]));
+    assert_eq!("p-4 px-2", tw_merge(["p-4", "px-2"]));
+    assert_eq!("m-0 -mt-2", tw_merge(["mt-4", "m-0 -mt-2"]));
+    assert_eq!(
+        "rounded-lg",
+        tw_merge(["rounded-t-none rounded-bl", "rounded-lg"])
+    );
+    assert_eq!("border-x-4", tw_merge(["border-l-2", "border-x-4"]));
+}
+
+#[test]
+fn test_tw_merge_variants_and_important() {
+    assert_eq!("p-2 md:p-6", tw_merge(["p-2 md:p-4", "md:p-6"]));
+    assert_eq!(
+        "hover:bg-red-500 bg-blue-500",
+        tw_merge(["hover:bg-red-500 bg-white", "bg-blue-500"])
+    );
+    assert_eq!("!p-2 p-4", tw_merge(["!p-2", "p-4"]));
+    assert_eq!("p-4!", tw_merge(["!p-2", "p-4!"]));
+    assert_eq!(
+        "[mask-type:alpha] bg-[url(a:b)]",
+        tw_merge(["[mask-type:luminance]", "[mask-type:alpha] bg-[url(a:b)]"])
+    );
+}
+
+#[test]
+fn test_tw_merge_keeps_other_classes() {
+    assert_eq!(
+        "btn btn-primary card",
+        tw_merge(["card btn", "btn-primary  card"])
+    );
+    assert_eq!("btn card", tw_merge(["card btn", "card"]));
+    assert_eq!("", tw_merge([""; 0]));
+}
+
+#[test]
+fn test_classes_tw() {
+    let res = div()
+        .classes_tw(["rounded p-4 bg-white", "bg-gray-50 p-2"])
+        .text("Card")
+        .render();
+
+    assert_eq!("<div class=\"rounded bg-gray-50 p-2\">Card</div>", res);
+}
@@ -0,0 +1,160 @@
+use lira::prelude::*;
+use lira::testing::{normalize, select_all_text, select_attr, select_count, select_text};
+use lira::{assert_html_eq, assert_no_selector, assert_selector};
+
+#[test]
+fn test_assert_html_eq_ignores_formatting() {
+    assert_html_eq!(
+        div()
+            .id("main")
+            .class("card  primary")
+            .child(h1().text("Title"))
+            .child(img().src("/a.png").alt("A")),
+        r#"
+        <div class="card primary" id="main">
+            <h1>  Title </h1>
+            <img alt="A" src="/a.png">
+        </div>
+        "#
+    );
+}
+
+#[test]
+fn test_assert_html_eq_decodes_entities() {
+    assert_html_eq!(p().text("a < b"), "<p>a &#60; b</p>");
+}
+
+#[test]
+fn test_normalize() {
+    let res = normalize("<ul><li b=\"2\" a=\"1\">One</li><li>\n  Two\n</li></ul><br>");
+    assert_eq!(
+        "<ul>\n  <li a=\"1\" b=\"2\">\n    \"One\"\n  </li>\n  <li>\n    \"Two\"\n  </li>\n</ul>\n<br>\n",
+        res
+    );
+}
+
+#[test]
+fn test_pre_keeps_whitespace() {
+    assert_eq!(
+        "<pre>\n  \"  a\\n  b\"\n</pre>\n",
+        normalize("<pre>  a\n  b</pre>")
+    );
+}
+
+#[test]
+fn test_assert_html_eq_failure_shows_diff() {
+    let err = std::panic::catch_unwind(|| {
+        assert_html_eq!(
+            div().class("a").text("Hello"),
+            "<div class=\"b\">Hello</div>"
+        );
+    })
+    .unwrap_err();
+
+    let message = err.downcast_ref::<String>().unwrap();
+    assert_eq!(
+        "HTML is not equal (- expected, + actual):\n- <div class=\"b\">\n+ <div class=\"a\">\n    \"Hello\"\n  </div>\n",
+        message
+    );
+}
+
+#[test]
+fn test_assert_html_eq_diff_of_large_documents() {
+    let list = |changed: usize| {
+        ul().children(0..3_000, move |i| {
+            li().text(if i == changed {
+                "changed".to_string()
+            } else {
+                i.to_string()
+            })
+        })
+    };
+
+    let err = std::panic::catch_unwind(|| assert_html_eq!(list(2_000), list(1_000).render()))
+        .unwrap_err();
+
+    let message = err.downcast_ref::<String>().unwrap();
+    let changes: Vec<_> = message
+        .lines()
+        .filter(|line| line.starts_with('-') || line.starts_with('+'))
+        .collect();
+    assert_eq!(
+        vec![
+            "-     \"changed\"",
+            "+     \"1000\"",
+            "-     \"2000\"",
+            "+     \"changed\""
+        ],
+        changes
+    );
+}
+
+fn login_form() -> Node<Form, Content> {
+    form()
+        .id("login")
+        .class("form wide")
+        .child(h1().text("Sign  in"))
+        .child(label().child(input().attr("name", "email").attr("type", "email")))
+        .child(input().attr("name", "password").attr("type", "password"))
+        .child(button().attr("data-action", "submit-login").text("Go"))
+}
+
+#[test]
+fn test_assert_selector() {
+    assert_selector!(login_form(), "form#login input[name=email]");
+    assert_selector!(login_form(), "form.wide.form > input[type='password']");
+    assert_selector!(login_form(), "button[data-action^=submit], nav");
+    assert_selector!(login_form(), "[data-action$=login]");
+    assert_selector!(login_form(), "[class~=wide] *[data-action*=mit-lo]");
+    assert_no_selector!(login_form(), "form > input[name=email]");
+    assert_no_selector!(login_form(), "form#signup");
+}
+
+#[test]
+#[should_panic(expected = "no element matches \"form#signup\"")]
+fn test_assert_selector_fails() {
+    assert_selector!(login_form(), "form#signup");
+}
+
+#[test]
+#[should_panic(expected = "invalid selector \"form >\"")]
+fn test_invalid_selector() {
+    select_count(login_form(), "form >");
+}
+
+#[test]
+fn test_selector_list_with_commas_in_values() {
+    let html = "<p data-tags=\"a,b\">Tagged</p><p title='x, y'>Titled</p>";
+
+    assert_eq!(1, select_count(html, "p[data-tags=\"a,b\"]"));
+    assert_eq!(
+        2,
+        select_count(html, "[data-tags='a,b'], p[title=\"x, y\"]")
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid selector \":is(h1, nav)\": unexpected character")]
+fn test_selector_pseudo_class_arguments() {
+    select_count(login_form(), ":is(h1, nav)");
+}
+
+#[test]
+fn test_select_text() {
+    assert_eq!(Some("Sign in".to_string()), select_text(login_form(), "h1"));
+    assert_eq!(None, select_text(login_form(), "h2"));
+    assert_eq!(
+        vec!["One".to_string(), "Two and more".to_string()],
+        select_all_text("<ul><li>One</li><li>Two <b>and</b> more</li></ul>", "ul li")
+    );
+}
+
+#[test]
+fn test_select_count_and_attr() {
+    assert_eq!(2, select_count(login_form(), "form input"));
+    assert_eq!(
+        Some("password".to_string()),
+        select_attr(login_form(), "input[type=password]", "name")
+    );
+    assert_eq!(None, select_attr(login_form(), "h1", "id"));
+}
@@ -0,0 +1,108 @@
+use lira::prelude::*;
+
+#[test]
+fn test_render_text_blocks() {
+    let res = html()
+        .child(
+            head()
+                .child(title().text("Ignored"))
+                .child(style().text("p{}")),
+        )
+        .child(
+            body()
+                .child(h1().text("Welcome"))
+                .child(p().text("  Lots   of\n   whitespace  "))
+                .child(div().text("First").child(span().text(" inline")))
+                .child(div().text("Second"))
+                .child(p().text("Above the rule"))
+                .child(hr())
+                .child(p().text("Below the rule"))
+                .child(p().raw("Line one<br>Line two"))
+                .child(script().raw("track()")),
+        )
+        .render_text();
+
+    assert_eq!(
+        "Welcome\n\nLots of whitespace\n\nFirst inline\nSecond\n\nAbove the rule\n\n---\n\nBelow the rule\n\n\
+         Line one\nLine two",
+        res
+    );
+}
+
+#[test]
+fn test_render_text_lists() {
+    let res = div()
+        .child(
+            ul().child(li().text("Tea")).child(
+                li().text("Cake")
+                    .child(ol().child(li().text("Flour")).child(li().text("Sugar"))),
+            ),
+        )
+        .child(
+            ol().attr("start", "9")
+                .child(li().text("Nine"))
+                .child(li().text("Ten")),
+        )
+        .render_text();
+
+    assert_eq!(
+        "- Tea\n- Cake\n  1. Flour\n  2. Sugar\n\n9. Nine\n10. Ten",
+        res
+    );
+}
+
+#[test]
+fn test_render_text_links() {
+    let res = p()
+        .child(a().href("https://example.com/docs").text("the docs"))
+        .text(", ")
+        .child(a().href("https://example.com").text("example.com"))
+        .text(", ")
+        .child(a().href("mailto:hi@example.com").text("hi@example.com"))
+        .text(", ")
+        .child(a().href("#top").text("top"))
+        .render_text();
+
+    assert_eq!(
+        "the docs (https://example.com/docs), example.com, hi@example.com, top",
+        res
+    );
+}
+
+#[test]
+fn test_render_text_quotes_tables_and_pre() {
+    let res = div()
+        .raw("<blockquote><p>Quoted</p><p>Twice</p></blockquote>")
+        .child(
+            table()
+                .child(tr().child(th().text("Item")).child(th().text("Price")))
+                .child(tr().child(td().text("Tea")).child(td().text("3 €"))),
+        )
+        .child(pre().text("fn main() {\n    run();\n}"))
+        .render_text();
+
+    assert_eq!(
+        "> Quoted\n\n> Twice\n\nItem | Price\nTea | 3 €\n\nfn main() {\n    run();\n}",
+        res
+    );
+}
+
+#[test]
+fn test_render_text_skips_hidden() {
+    let res = RenderContext::new().scope(|| {
+        div()
+            .child(heading_anchored(2, "Usage"))
+            .child(
+                p().child(span().attr("aria-hidden", "true").text("★"))
+                    .text("Starred"),
+            )
+            .child(p().flag("hidden").text("Hidden"))
+            .child(
+                p().child(img().src("/logo.png").alt("Logo"))
+                    .child(input().name("q")),
+            )
+            .render_text()
+    });
+
+    assert_eq!("Usage\n\nStarred\n\nLogo", res);
+}
@@ -0,0 +1,136 @@
+use lira::prelude::*;
+
+#[test]
+fn test_with_toc_nesting() {
+    let (_, toc) = div()
+        .child(h3().text("Before"))
+        .child(h2().text("One"))
+        .child(h4().text("Deep"))
+        .child(h3().text("Two"))
+        .child(h2().text("Three"))
+        .with_toc();
+
+    let levels: Vec<_> = toc
+        .entries()
+        .iter()
+        .map(|entry| (entry.level, entry.text.as_str(), entry.children.len()))
+        .collect();
+    assert_eq!(
+        vec![(3, "Before", 0), (2, "One", 2), (2, "Three", 0)],
+        levels
+    );
+
+    assert_eq!(
+        "<ol><li><a href=\"#before\">Before</a></li><li><a href=\"#one\">One</a><ol>\
+         <li><a href=\"#deep\">Deep</a></li><li><a href=\"#two\">Two</a></li></ol></li>\
+         <li><a href=\"#three\">Three</a></li></ol>",
+        toc.render()
+    );
+}
+
+#[test]
+fn test_with_toc_ids() {
+    let (content, toc) = div()
+        .child(p().id("faq").text("Taken"))
+        .child(h2().text("FAQ"))
+        .child(h2().text("FAQ"))
+        .child(h2().text("What's  <new>?"))
+        .child(h2().text("!!!"))
+        .child(h2().id("custom").text("Kept"))
+        .with_toc();
+
+    assert_eq!(
+        "<div><p id=\"faq\">Taken</p><h2 id=\"faq-2\">FAQ</h2><h2 id=\"faq-3\">FAQ</h2>\
+         <h2 id=\"whats-new\">What&#39;s  &lt;new&gt;?</h2><h2 id=\"section\">!!!</h2>\
+         <h2 id=\"custom\">Kept</h2></div>",
+        content.render()
+    );
+    let ids: Vec<_> = toc
+        .entries()
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect();
+    assert_eq!(
+        vec!["faq-2", "faq-3", "whats-new", "section", "custom"],
+        ids
+    );
+    assert_eq!("What's <new>?", toc.entries()[2].text);
+}
+
+#[test]
+fn test_with_toc_ignores_other_headings() {
+    let (_, toc) = div()
+        .child(h1().text("Title"))
+        .child(h5().text("Small"))
+        .child(
+            h2().child(span().attr("aria-hidden", "true").text("§"))
+                .text(" Usage"),
+        )
+        .with_toc();
+
+    assert_eq!(1, toc.entries().len());
+    assert_eq!("Usage", toc.entries()[0].text);
+}
+
+#[test]
+fn test_empty_toc() {
+    let (_, toc) = p().text("No headings").with_toc();
+
+    assert!(toc.is_empty());
+    assert_eq!("", toc.render());
+}
+
+#[test]
+fn test_heading_anchored() {
+    let res = heading_anchored(HeadingLevel::H3, "Config & <env>").render();
+
+    assert_eq!(
+        "<h3 id=\"config-env\">Config &amp; &lt;env&gt;<a href=\"#config-env\" class=\"heading-anchor\">\
+         <span aria-hidden=\"true\">#</span><span class=\"visually-hidden\">Link to this section</span></a></h3>",
+        res
+    );
+}
+
+#[test]
+fn test_heading_anchored_collisions() {
+    let ids = RenderContext::new().scope(|| {
+        ["Usage", "Usage", "usage!", "", "?"]
+            .map(|text| heading_anchored(2, text).render())
+            .map(|html| html[8..html.find("\">").unwrap()].to_string())
+    });
+
+    assert_eq!(["usage", "usage-2", "usage-3", "section", "section-2"], ids);
+
+    // without a context there is nothing to compare with
+    let res = heading_anchored(2, "Usage").render();
+    assert!(res.starts_with("<h2 id=\"usage\">"));
+}
+
+#[test]
+fn test_heading_anchored_reused_context() {
+    let ctx = RenderContext::new();
+    let render = || ctx.clone().scope(|| heading_anchored(2, "Intro").render());
+
+    assert!(render().starts_with("<h2 id=\"intro\">"));
+    assert!(render().starts_with("<h2 id=\"intro\">"));
+}
+
+#[test]
+fn test_heading_anchored_in_toc() {
+    let (_, toc) = RenderContext::new().scope(|| {
+        div()
+            .child(heading_anchored(2, "Install"))
+            .child(h2().text("Install"))
+            .with_toc()
+    });
+    let entries: Vec<_> = toc
+        .entries()
+        .iter()
+        .map(|entry| (entry.id.as_str(), entry.text.as_str()))
+        .collect();
+
+    assert_eq!(
+        vec![("install", "Install"), ("install-2", "Install")],
+        entries
+    );
+}
@@ -0,0 +1,237 @@
+#![cfg(feature = "tower")]
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::{Pin, pin};
+use std::task::{Context, Poll, Waker};
+
+use bytes::Bytes;
+use http::{Request, Response, StatusCode, header};
+use http_body::Body;
+use lira::prelude::*;
+use lira::tower::{HtmlBody, HtmlLayer, HtmlResponse};
+use tower::{Layer, ServiceExt, service_fn};
+
+// the services here never wait, so this doesn't need an executor
+fn ready<F: Future>(future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+    match pin!(future).poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future isn't ready"),
+    }
+}
+
+fn frames(mut body: HtmlBody) -> Vec<Bytes> {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut frames = Vec::new();
+    while let Poll::Ready(Some(frame)) = Pin::new(&mut body).poll_frame(&mut cx) {
+        frames.push(frame.unwrap().into_data().unwrap());
+    }
+    frames
+}
+
+fn call<R>(layer: HtmlLayer, request: Request<()>, page: fn() -> R) -> Response<HtmlBody>
+where
+    R: lira::tower::IntoHtmlResponse,
+{
+    let service = layer.layer(service_fn(move |_: Request<()>| async move {
+        Ok::<_, Infallible>(page())
+    }));
+    ready(service.oneshot(request)).unwrap()
+}
+
+fn get() -> http::request::Builder {
+    Request::builder().uri("/")
+}
+
+#[test]
+fn test_renderable_response() {
+    let response = call(HtmlLayer::new(), get().body(()).unwrap(), || {
+        p().text("Hello")
+    });
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        "text/html; charset=utf-8",
+        response.headers()[header::CONTENT_TYPE]
+    );
+    assert_eq!(
+        ContentHash::of("<p>Hello</p>").etag(),
+        response.headers()[header::ETAG]
+    );
+    assert_eq!("accept-encoding", response.headers()[header::VARY]);
+    assert_eq!(Some(12), response.body().size_hint().exact());
+    assert_eq!(
+        vec![Bytes::from("<p>Hello</p>")],
+        frames(response.into_body())
+    );
+}
+
+#[test]
+fn test_not_modified() {
+    let etag = ContentHash::of("<p>Hello</p>").etag();
+    for if_none_match in [
+        etag.clone(),
+        format!("W/{}", etag),
+        format!("\"old\", {}", etag),
+    ] {
+        let request = get()
+            .header(header::IF_NONE_MATCH, if_none_match)
+            .body(())
+            .unwrap();
+        let response = call(HtmlLayer::new(), request, || p().text("Hello"));
+
+        assert_eq!(StatusCode::NOT_MODIFIED, response.status());
+        assert_eq!(etag, response.headers()[header::ETAG]);
+        assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+        assert!(response.body().is_end_stream());
+        assert!(frames(response.into_body()).is_empty());
+    }
+
+    let request = get()
+        .header(header::IF_NONE_MATCH, "\"other\"")
+        .body(())
+        .unwrap();
+    let response = call(HtmlLayer::new(), request, || p().text("Hello"));
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+fn large_page() -> impl Renderable {
+    ul().children(0..200, |i| li().text(format!("Item {}", i)))
+}
+
+#[test]
+fn test_gzip_negotiation() {
+    let request = get()
+        .header(header::ACCEPT_ENCODING, "br;q=1.0, gzip;q=0.8")
+        .body(())
+        .unwrap();
+    let response = call(HtmlLayer::new(), request, large_page);
+
+    assert_eq!("gzip", response.headers()[header::CONTENT_ENCODING]);
+    let body = frames(response.into_body()).concat();
+    assert_eq!([0x1f, 0x8b], body[..2]);
+    assert!(body.len() < large_page().render().len());
+
+    for accept_encoding in ["*", "GZIP", "br;q=0, *"] {
+        let request = get()
+            .header(header::ACCEPT_ENCODING, accept_encoding)
+            .body(())
+            .unwrap();
+        let response = call(HtmlLayer::new(), request, large_page);
+        assert_eq!("gzip", response.headers()[header::CONTENT_ENCODING]);
+    }
+
+    for accept_encoding in [
+        "br",
+        "gzip;q=0",
+        "identity",
+        "*, gzip;q=0",
+        "gzip;q=0, *;q=1",
+    ] {
+        let request = get()
+            .header(header::ACCEPT_ENCODING, accept_encoding)
+            .body(())
+            .unwrap();
+        let response = call(HtmlLayer::new(), request, large_page);
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    }
+}
+
+#[test]
+fn test_gzip_etag() {
+    let request = |if_none_match: Option<&str>| {
+        let request = get().header(header::ACCEPT_ENCODING, "gzip");
+        match if_none_match {
+            Some(value) => request.header(header::IF_NONE_MATCH, value),
+            None => request,
+        }
+        .body(())
+        .unwrap()
+    };
+
+    let hash = ContentHash::of(&large_page().render());
+    let etag = format!("\"{}-gzip\"", hash);
+
+    let response = call(HtmlLayer::new(), request(None), large_page);
+    assert_eq!("gzip", response.headers()[header::CONTENT_ENCODING]);
+    assert_eq!(etag, response.headers()[header::ETAG]);
+
+    let response = call(HtmlLayer::new(), request(Some(&etag)), large_page);
+    assert_eq!(StatusCode::NOT_MODIFIED, response.status());
+    assert_eq!(etag, response.headers()[header::ETAG]);
+
+    // the tag of the uncompressed page is another representation
+    let response = call(HtmlLayer::new(), request(Some(&hash.etag())), large_page);
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[test]
+fn test_gzip_thresholds_and_config() {
+    let request = || {
+        get()
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .body(())
+            .unwrap()
+    };
+
+    // small pages aren't worth it
+    let response = call(HtmlLayer::new(), request(), || p().text("Hi"));
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+
+    let response = call(HtmlLayer::new().gzip_min_len(0), request(), || {
+        p().text("Hi")
+    });
+    assert_eq!("gzip", response.headers()[header::CONTENT_ENCODING]);
+
+    let response = call(
+        HtmlLayer::new().gzip(false).etag(false),
+        request(),
+        large_page,
+    );
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    assert!(response.headers().get(header::VARY).is_none());
+    assert!(response.headers().get(header::ETAG).is_none());
+}
+
+#[test]
+fn test_html_response() {
+    let response = call(HtmlLayer::new(), get().body(()).unwrap(), || {
+        HtmlResponse::new(h1().text("Not found"))
+            .status(StatusCode::NOT_FOUND)
+            .header(header::CACHE_CONTROL, "no-store")
+            .header(header::SET_COOKIE, "bad\nvalue")
+    });
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!("no-store", response.headers()[header::CACHE_CONTROL]);
+    assert!(response.headers().get(header::SET_COOKIE).is_none());
+    // error pages aren't cached by their ETag
+    assert!(response.headers().get(header::ETAG).is_none());
+    assert_eq!(
+        vec![Bytes::from("<h1>Not found</h1>")],
+        frames(response.into_body())
+    );
+}
+
+#[test]
+fn test_streamed_response() {
+    let request = get()
+        .header(header::ACCEPT_ENCODING, "gzip")
+        .body(())
+        .unwrap();
+    let response = call(HtmlLayer::new().gzip_min_len(0), request, || {
+        HtmlResponse::streamed(["a", "b"].map(|text| p().text(text)))
+    });
+
+    assert_eq!(
+        "text/html; charset=utf-8",
+        response.headers()[header::CONTENT_TYPE]
+    );
+    assert!(response.headers().get(header::ETAG).is_none());
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    assert_eq!(
+        vec![Bytes::from("<p>a</p>"), Bytes::from("<p>b</p>")],
+        frames(response.into_body())
+    );
+}
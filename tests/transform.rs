@@ -0,0 +1,46 @@
+use lira::prelude::*;
+use lira::tree::Element;
+
+#[test]
+fn test_presets() {
+    let page = || {
+        div()
+            .child(img().src("/a.png"))
+            .child(img().src("/b.png").loading(Loading::Eager))
+            .child(
+                a().href("https://example.com")
+                    .attr("rel", "external")
+                    .text("Out"),
+            )
+            .child(a().href("/in").text("In"))
+            .raw("<script>track()</script><style nonce=\"keep\">p{}</style>")
+    };
+
+    let res = page().render_transformed(
+        &lazy_images()
+            .then(external_link_class("external"))
+            .then(inject_nonce("abc")),
+    );
+
+    assert_eq!(
+        "<div><img src=\"/a.png\" loading=\"lazy\" /><img src=\"/b.png\" loading=\"eager\" />\
+         <a href=\"https://example.com\" rel=\"external\" class=\"external\">Out</a><a href=\"/in\">In</a>\
+         <script nonce=\"abc\">track()</script><style nonce=\"keep\">p{}</style></div>",
+        res
+    );
+}
+
+#[test]
+fn test_closure_transform_on_tree() {
+    let mut tree = Tree::parse("<ul><li>a</li><li>b</li></ul>");
+    tree.transform(&|element: &mut Element| {
+        if element.name == "li" {
+            element.set_attr("role", "listitem");
+        }
+    });
+
+    assert_eq!(
+        "<ul><li role=\"listitem\">a</li><li role=\"listitem\">b</li></ul>",
+        tree.render()
+    );
+}
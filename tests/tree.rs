@@ -0,0 +1,188 @@
+use lira::prelude::*;
+use lira::tree::{Attribute, Element, TreeNode};
+
+#[test]
+fn test_render_tree_roundtrip() {
+    let page = || {
+        html().child(
+            body()
+                .child(h1().class("title").text("Fish & Chips"))
+                .child(input().name("q").required())
+                .child(script().raw("if (a < b) {}"))
+                .child(p().attr("title", "\"quoted\"").text("<b>not bold</b>")),
+        )
+    };
+
+    let tree = page().render_tree();
+
+    assert_eq!(page().render(), tree.render());
+}
+
+#[test]
+fn test_tree_inspection() {
+    let tree = div()
+        .id("main")
+        .child(p().text("Hello "))
+        .child(p().child(strong().text("World")))
+        .render_tree();
+
+    let TreeNode::Element(root) = &tree.nodes[0] else {
+        panic!("expected an element");
+    };
+    assert_eq!("div", root.name);
+    assert_eq!(Some("main"), root.attr("id"));
+    assert_eq!("Hello World", root.text_content());
+
+    let mut names = Vec::new();
+    tree.walk(|element| names.push(element.name.clone()));
+    assert_eq!(["div", "p", "p", "strong"], names.as_slice());
+}
+
+#[test]
+fn test_tree_editing() {
+    let mut tree = Tree::parse("<p class=\"lead\">Hi</p><img src=\"/a.png\">");
+
+    tree.walk_mut(|element| match element.name.as_str() {
+        "p" => {
+            element.add_class("lead");
+            element.add_class("big");
+            element.children.push(TreeNode::Element(Element::new("BR")));
+        }
+        "img" => {
+            element.set_attr("src", "/b.png");
+            element.set_attr("alt", "");
+        }
+        _ => {}
+    });
+
+    assert_eq!(
+        "<p class=\"lead big\">Hi<br /></p><img src=\"/b.png\" alt=\"\" />",
+        tree.clone().render()
+    );
+
+    tree.walk_mut(|element| element.remove_attr("class"));
+    assert_eq!(
+        "<p>Hi<br /></p><img src=\"/b.png\" alt=\"\" />",
+        tree.render()
+    );
+}
+
+#[test]
+fn test_tree_pretty() {
+    let tree = nav()
+        .child(
+            ul().child(li().text("One"))
+                .child(li().child(a().href("/").text("Two"))),
+        )
+        .child(pre().text("  keep\n  this"))
+        .render_tree();
+
+    assert_eq!(
+        "<nav>\n  <ul>\n    <li>One</li>\n    <li>\n      <a href=\"/\">Two</a>\n    </li>\n  </ul>\n  \
+         <pre>  keep\n  this</pre>\n</nav>\n",
+        tree.pretty()
+    );
+}
+
+#[test]
+fn test_tree_diff() {
+    let a = Tree::parse("<div id=\"a\" class=\"x\">\n  <p>Hi</p>\n</div>");
+    let b = Tree::parse("<div class=\"x\" id=\"a\"><p>Hi</p></div>");
+    let c = Tree::parse("<div class=\"x\" id=\"a\"><p>Bye</p></div>");
+
+    assert_eq!(None, a.diff(&b));
+    assert_eq!(
+        Some("  <div class=\"x\" id=\"a\">\n    <p>\n-     \"Hi\"\n+     \"Bye\"\n    </p>\n  </div>\n".to_string()),
+        a.diff(&c)
+    );
+}
+
+#[test]
+fn test_tree_character_references() {
+    let tree = Tree::parse(
+        "<p title=\"?a=1&copy=2 &copy;\">&hearts; &copy 2026 &#x1F600; &#150; &NotNestedGreaterGreater; &nope;</p>",
+    );
+
+    let TreeNode::Element(p) = &tree.nodes[0] else {
+        panic!("expected an element");
+    };
+    assert_eq!(Some("?a=1&copy=2 \u{a9}"), p.attr("title"));
+    assert_eq!(
+        "\u{2665} \u{a9} 2026 \u{1f600} \u{2013} \u{2aa2}\u{338} &nope;",
+        p.text_content()
+    );
+    assert_eq!(
+        "<p title=\"?a=1&amp;copy=2 \u{a9}\">\u{2665} \u{a9} 2026 \u{1f600} \u{2013} \u{2aa2}\u{338} &amp;nope;</p>",
+        tree.render()
+    );
+}
+
+#[test]
+fn test_tree_svg_names_keep_their_case() {
+    let html = "<svg viewBox=\"0 0 2 2\"><linearGradient id=\"g\" gradientUnits=\"userSpaceOnUse\"></linearGradient>\
+                <circle cx=\"1\" cy=\"1\" r=\"1\"></circle></svg>";
+    let mut tree = Tree::parse(html);
+
+    let mut names = Vec::new();
+    tree.walk(|element| names.push(element.name.clone()));
+    assert_eq!(["svg", "linearGradient", "circle"], names.as_slice());
+    assert_eq!(html, tree.clone().render());
+
+    tree.walk_mut(|element| {
+        if element.name == "svg" {
+            element.set_attr("VIEWBOX", "0 0 4 4");
+            element
+                .children
+                .push(TreeNode::Element(Element::new("CLIPPATH")));
+        }
+    });
+    assert_eq!(
+        "<svg viewBox=\"0 0 4 4\"><linearGradient id=\"g\" gradientUnits=\"userSpaceOnUse\"></linearGradient>\
+         <circle cx=\"1\" cy=\"1\" r=\"1\"></circle><clipPath></clipPath></svg>",
+        tree.render()
+    );
+}
+
+#[test]
+fn test_tree_invalid_names() {
+    let mut tree = Tree::parse("<p>Hi</p>");
+
+    tree.walk_mut(|element| {
+        element.set_attr("on click\"", "a");
+        element.set_attr("\" =", "b");
+        element.attrs.push(Attribute {
+            name: "x>y".to_string(),
+            value: None,
+        });
+        element.attrs.push(Attribute {
+            name: "/".to_string(),
+            value: None,
+        });
+        element.name = "p><script".to_string();
+    });
+
+    assert_eq!("<pscript onclick=\"a\" xy>Hi</pscript>", tree.render());
+}
+
+#[test]
+fn test_tree_changes_cant_break_out() {
+    let mut tree = Tree::parse("<script>x()</script><style>a{}</style>");
+    tree.walk_mut(|element| {
+        element.children.push(TreeNode::Text(format!(
+            "</{}><img src=x onerror=alert(1)>",
+            element.name.to_uppercase()
+        )));
+    });
+    tree.nodes
+        .push(TreeNode::Comment("--><b>pwn</b><!--".to_string()));
+    tree.nodes.push(TreeNode::Comment(">x--!>".to_string()));
+    tree.nodes
+        .push(TreeNode::Doctype("html><b>pwn</b".to_string()));
+
+    assert_eq!(
+        "<div><script>x()<\\/SCRIPT><img src=x onerror=alert(1)></script>\
+         <style>a{}<\\/STYLE><img src=x onerror=alert(1)></style>\
+         <!----&gt;<b>pwn</b><!----><!--&gt;x--!&gt;--><!html<bpwn</b></div>",
+        div().child(tree).render()
+    );
+}
@@ -0,0 +1,139 @@
+use lira::prelude::*;
+
+#[test]
+fn test_href_query() {
+    assert_eq!(
+        "/search?q=a%20b&page=2",
+        href_query("/search", [("q", "a b"), ("page", "2")])
+    );
+    assert_eq!("/search", href_query("/search", Vec::<(&str, &str)>::new()));
+}
+
+#[test]
+fn test_href_query_numbers() {
+    assert_eq!("/posts?page=3", href_query("/posts", [("page", 3)]));
+}
+
+#[test]
+fn test_existing_query_and_fragment() {
+    assert_eq!("/a?x=1&y=2#top", href_query("/a?x=1#top", [("y", 2)]));
+    assert_eq!("/a?y=2", href_query("/a?", [("y", 2)]));
+    assert_eq!("/a?x=1&y=2", href_query("/a?x=1&", [("y", 2)]));
+}
+
+#[test]
+fn test_encoding() {
+    let query = QueryString::new().param("a&b", "1+1=2").param("ü", "/?#");
+    assert_eq!("a%26b=1%2B1%3D2&%C3%BC=%2F%3F%23", query.to_string());
+}
+
+#[test]
+fn test_param_opt() {
+    let query = QueryString::new()
+        .param_opt("a", Some(1))
+        .param_opt("b", None::<i32>);
+    assert_eq!("a=1", query.to_string());
+    assert!(QueryString::new().is_empty());
+}
+
+#[test]
+fn test_href_attribute_escape() {
+    let res = a()
+        .href(href_query("/", [("a", 1), ("b", 2)]))
+        .text("x")
+        .render();
+    assert_eq!("<a href=\"/?a=1&amp;b=2\">x</a>", res);
+}
+
+#[cfg(feature = "serde")]
+mod serde_params {
+    use std::collections::BTreeMap;
+
+    use lira::prelude::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    enum Sort {
+        NewestFirst,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Filter {
+        search_term: String,
+        sort: Sort,
+        page: Option<u32>,
+        tags: Vec<&'static str>,
+    }
+
+    #[test]
+    fn test_serialize_struct() {
+        let filter = Filter {
+            search_term: "a b".to_string(),
+            sort: Sort::NewestFirst,
+            page: None,
+            tags: vec!["x", "y"],
+        };
+        assert_eq!(
+            "/?searchTerm=a%20b&sort=newestFirst&tags=x&tags=y",
+            QueryString::serialize(&filter).unwrap().to("/")
+        );
+    }
+
+    #[test]
+    fn test_serialize_map() {
+        let map = BTreeMap::from([("a", 1), ("b", 2)]);
+        assert_eq!("a=1&b=2", QueryString::serialize(&map).unwrap().to_string());
+    }
+
+    #[test]
+    fn test_serialize_unsupported() {
+        #[derive(Serialize)]
+        struct Outer {
+            inner: BTreeMap<&'static str, i32>,
+        }
+
+        assert!(QueryString::serialize(&42).is_err());
+        assert!(
+            QueryString::serialize(&Outer {
+                inner: BTreeMap::new()
+            })
+            .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_mailto() {
+    assert_eq!(
+        "mailto:info@example.com",
+        mailto("info@example.com").to_string()
+    );
+    assert_eq!(
+        "mailto:a@example.com,b@example.com?cc=c%2Bx@example.com&bcc=d@example.com&subject=Hello%20there",
+        String::from(
+            mailto("a@example.com,b@example.com")
+                .cc("c+x@example.com")
+                .bcc("d@example.com")
+                .subject("Hello there")
+        )
+    );
+}
+
+#[test]
+fn test_mailto_body_line_breaks() {
+    assert_eq!(
+        "mailto:a@example.com?body=one%0D%0Atwo%0D%0Athree",
+        mailto("a@example.com")
+            .body("one\ntwo\r\nthree")
+            .to_string()
+    );
+}
+
+#[test]
+fn test_tel() {
+    assert_eq!("tel:+15550100", tel("+1 555 0100"));
+    assert_eq!("tel:+1-555-0100", tel("+1-555-0100"));
+    assert_eq!("tel:030.123%2342", tel("030.123 #42"));
+}